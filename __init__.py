@@ -1,6 +1,52 @@
+from .exceptions import *
 from .abi import *
 from .arbitrage import *
 from .chainlink import *
 from .token import *
 from .router import *
 from .liquiditypool import *
+
+from .cancellation import *
+from .signing import *
+from .address_utils import *
+from .mining import *
+from .rlp import *
+from .fees import *
+from .rpc import *
+from .storage import *
+from .proofs import *
+from .bloom import *
+from .provider import *
+from .erc20 import *
+from .permit import *
+from .oracle import *
+from .analytics import *
+from .pool_state import *
+from .backtest import *
+from .snapshot import *
+from .uniswap_v3 import *
+from .uniswap_v4 import *
+from .price_utils import *
+from .quoter import *
+from .txpool import *
+from .router_decoder import *
+from .relay import *
+from .vault_math import *
+from .fixed_point import *
+from .integer_math import *
+from .constants import *
+from .log import *
+from .metrics import *
+from .cache import *
+from .v2_math import *
+from .arrow_interop import *
+from .journal import *
+from .registry import *
+from .router_encoder import *
+from .exchanges import *
+from .security import *
+from .tx_builder import *
+from .wallet import *
+from .units import *
+from .curve_math import *
+from .filters import *
\ No newline at end of file
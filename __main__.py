@@ -0,0 +1,126 @@
+import argparse
+import sys
+import time
+from typing import Dict, List, Optional
+from .pool_state import V3PoolState, validate_v3_tick_map
+from .quoter import PathHop, quote_path, quote_path_exact_output
+from .snapshot import load_snapshot, save_snapshot
+
+
+def _path(snapshot: Dict, hops: List[str]) -> List[PathHop]:
+    pools = {address.lower(): pool for address, pool in snapshot.items()}
+    path = []
+    for hop in hops:
+        try:
+            address, token_in, token_out = hop.split(":")
+            path.append(PathHop(pools[address.lower()], int(token_in), int(token_out)))
+        except ValueError:
+            sys.exit(f"Invalid hop '{hop}', expected POOL:TOKEN_IN:TOKEN_OUT")
+        except KeyError:
+            sys.exit(f"Pool {address} is not in the snapshot")
+    return path
+
+
+def _quote(path: List[PathHop], amount: int, exact_output: bool) -> List[int]:
+    if exact_output:
+        return quote_path_exact_output(path, amount)
+    return quote_path(path, amount)
+
+
+def _cmd_quote(args) -> int:
+    path = _path(load_snapshot(args.snapshot), args.hop)
+    for amount in _quote(path, args.amount, args.exact_output):
+        print(amount)
+    return 0
+
+
+def _cmd_convert(args) -> int:
+    snapshot = load_snapshot(args.input)
+    binary = None if args.format is None else args.format == "binary"
+    save_snapshot(snapshot, args.output, binary=binary)
+    print(f"Wrote {len(snapshot)} pools to {args.output}")
+    return 0
+
+
+def _cmd_validate(args) -> int:
+    snapshot = load_snapshot(args.snapshot)
+    invalid = 0
+    for address, pool in snapshot.items():
+        if not isinstance(pool, V3PoolState):
+            continue
+        problems = validate_v3_tick_map(pool)
+        if problems:
+            invalid += 1
+            print(f"{address}:")
+            for problem in problems:
+                print(f"    {problem}")
+    print(f"{invalid} of {len(snapshot)} pools have an invalid tick map")
+    return 1 if invalid else 0
+
+
+def _cmd_bench(args) -> int:
+    path = _path(load_snapshot(args.snapshot), args.hop)
+    start = time.perf_counter()
+    for _ in range(args.iterations):
+        _quote(path, args.amount, args.exact_output)
+    elapsed = time.perf_counter() - start
+    print(
+        f"{args.iterations} quotes in {elapsed:.3f}s "
+        f"({1e6 * elapsed / args.iterations:.1f} us per quote)"
+    )
+    return 0
+
+
+def main(argv: Optional[List[str]] = None) -> int:
+    parser = argparse.ArgumentParser(
+        prog="python -m degenbot",
+        description="Offline quoting and snapshot tools",
+    )
+    subparsers = parser.add_subparsers(dest="command", required=True)
+
+    def _add_path_arguments(subparser) -> None:
+        subparser.add_argument("snapshot", help="snapshot file (JSON or binary)")
+        subparser.add_argument(
+            "--hop",
+            action="append",
+            required=True,
+            help="POOL:TOKEN_IN:TOKEN_OUT with coin indices, repeated for each hop",
+        )
+        subparser.add_argument("--amount", type=int, required=True)
+        subparser.add_argument(
+            "--exact-output",
+            action="store_true",
+            help="treat the amount as the desired output",
+        )
+
+    quote = subparsers.add_parser("quote", help="quote a swap path from a snapshot")
+    _add_path_arguments(quote)
+    quote.set_defaults(handler=_cmd_quote)
+
+    convert = subparsers.add_parser("convert", help="convert a snapshot file")
+    convert.add_argument("input")
+    convert.add_argument("output")
+    convert.add_argument(
+        "--format",
+        choices=("json", "binary"),
+        help="output format, from the output extension by default",
+    )
+    convert.set_defaults(handler=_cmd_convert)
+
+    validate = subparsers.add_parser(
+        "validate", help="check the tick maps of the V3 pools in a snapshot"
+    )
+    validate.add_argument("snapshot")
+    validate.set_defaults(handler=_cmd_validate)
+
+    bench = subparsers.add_parser("bench", help="benchmark quoting a swap path")
+    _add_path_arguments(bench)
+    bench.add_argument("--iterations", type=int, default=10_000)
+    bench.set_defaults(handler=_cmd_bench)
+
+    args = parser.parse_args(argv)
+    return args.handler(args)
+
+
+if __name__ == "__main__":
+    sys.exit(main())
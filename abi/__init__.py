@@ -1 +1,3 @@
-from .abi import *
\ No newline at end of file
+from .abi import *
+from .codec import *
+from .revert import *
\ No newline at end of file
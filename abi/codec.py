@@ -0,0 +1,72 @@
+from typing import Dict, List, Sequence, Tuple
+from eth_utils import keccak
+from ..cache import cached_function
+from ..exceptions import AbiError
+
+__all__ = [
+    "abi_encode",
+    "abi_decode",
+    "canonical_type",
+    "abi_signature",
+    "function_selector",
+    "input_types",
+]
+
+try:
+    from eth_abi import decode as _decode, encode as _encode
+except ImportError:
+    # eth_abi < 4.0
+    from eth_abi import decode_abi as _decode, encode_abi as _encode
+
+
+def abi_encode(types: Sequence[str], values: Sequence) -> bytes:
+    """
+    ABI-encodes values as a tuple of the given types, raising AbiError on failure
+    """
+    try:
+        return bytes(_encode(list(types), list(values)))
+    except Exception as e:
+        raise AbiError(f"Could not encode {values} as ({','.join(types)}): {e}") from e
+
+
+def abi_decode(types: Sequence[str], data: bytes) -> Tuple:
+    """
+    Decodes ABI-encoded data as a tuple of the given types, raising AbiError on failure
+    """
+    try:
+        return tuple(_decode(list(types), bytes(data)))
+    except Exception as e:
+        raise AbiError(f"Could not decode data as ({','.join(types)}): {e}") from e
+
+
+def canonical_type(parameter: Dict) -> str:
+    """
+    Returns the canonical type string of an ABI JSON parameter, expanding tuples
+    (structs) into their component types
+    """
+    param_type = parameter["type"]
+    if param_type.startswith("tuple"):
+        components = ",".join(canonical_type(c) for c in parameter["components"])
+        return f"({components}){param_type[len('tuple'):]}"
+    return param_type
+
+
+def abi_signature(item: Dict) -> str:
+    """
+    Returns the signature of an ABI JSON function, event or error,
+    e.g. "Transfer(address,address,uint256)"
+    """
+    types = ",".join(canonical_type(parameter) for parameter in item.get("inputs", []))
+    return f"{item['name']}({types})"
+
+
+@cached_function
+def function_selector(signature: str) -> bytes:
+    """
+    Returns the 4-byte selector for a signature such as "transfer(address,uint256)"
+    """
+    return keccak(text=signature)[:4]
+
+
+def input_types(item: Dict) -> List[str]:
+    return [canonical_type(parameter) for parameter in item.get("inputs", [])]
@@ -0,0 +1,119 @@
+from dataclasses import dataclass, field
+from typing import Dict, List, Optional, Tuple, Union
+from .codec import abi_decode, abi_signature, function_selector, input_types
+from ..exceptions import AbiError
+
+__all__ = [
+    "ERROR_STRING_SELECTOR",
+    "PANIC_SELECTOR",
+    "PANIC_REASONS",
+    "DecodedRevert",
+    "decode_revert",
+]
+
+ERROR_STRING_SELECTOR = function_selector("Error(string)")
+PANIC_SELECTOR = function_selector("Panic(uint256)")
+
+# Panic codes emitted by the Solidity compiler (>= 0.8.0)
+PANIC_REASONS = {
+    0x00: "generic compiler panic",
+    0x01: "assert(false)",
+    0x11: "arithmetic overflow or underflow",
+    0x12: "division or modulo by zero",
+    0x21: "invalid enum conversion",
+    0x22: "incorrectly encoded storage byte array",
+    0x31: "pop() on an empty array",
+    0x32: "array index out of bounds",
+    0x41: "out of memory or array too large",
+    0x51: "call to a zero-initialized internal function",
+}
+
+
+@dataclass
+class DecodedRevert:
+    """
+    A revert payload interpreted as one of:
+        - "empty": no data (require without a message, out of gas, invalid opcode...)
+        - "error": Error(string), with the reason in `message`
+        - "panic": Panic(uint256), with the code in `args` and a description in
+          `message`
+        - "custom": a custom error matched against the supplied ABIs
+        - "unknown": a payload matching none of the above
+    """
+
+    kind: str
+    data: bytes
+    selector: Optional[bytes] = None
+    name: Optional[str] = None
+    message: Optional[str] = None
+    args: Tuple = field(default_factory=tuple)
+
+    def __str__(self) -> str:
+        if self.kind in ("error", "panic"):
+            return f"{self.name}: {self.message}"
+        if self.kind == "custom":
+            return f"{self.name}{self.args}"
+        if self.kind == "empty":
+            return "reverted without data"
+        return f"unknown revert 0x{self.data.hex()}"
+
+
+def decode_revert(
+    data: Union[bytes, str],
+    custom_error_abis: Optional[List[Dict]] = None,
+) -> DecodedRevert:
+    """
+    Interprets EVM revert data. Standard Error(string) and Panic(uint256) payloads are
+    always recognized. Custom errors are matched by selector against
+    `custom_error_abis`, which may be a full contract ABI (non-error entries are
+    ignored) or a list of error entries
+    """
+    if isinstance(data, str):
+        data = bytes.fromhex(data[2:] if data.startswith("0x") else data)
+    data = bytes(data)
+
+    if not data:
+        return DecodedRevert(kind="empty", data=data)
+
+    selector, payload = data[:4], data[4:]
+
+    try:
+        if selector == ERROR_STRING_SELECTOR:
+            (message,) = abi_decode(["string"], payload)
+            return DecodedRevert(
+                kind="error",
+                data=data,
+                selector=selector,
+                name="Error",
+                message=message,
+                args=(message,),
+            )
+
+        if selector == PANIC_SELECTOR:
+            (code,) = abi_decode(["uint256"], payload)
+            return DecodedRevert(
+                kind="panic",
+                data=data,
+                selector=selector,
+                name="Panic",
+                message=PANIC_REASONS.get(code, f"unknown panic code {hex(code)}"),
+                args=(code,),
+            )
+
+        for item in custom_error_abis or []:
+            if item.get("type", "error") != "error":
+                continue
+            if function_selector(abi_signature(item)) != selector:
+                continue
+            return DecodedRevert(
+                kind="custom",
+                data=data,
+                selector=selector,
+                name=item["name"],
+                args=abi_decode(input_types(item), payload),
+            )
+    except AbiError:
+        # a matching selector with a malformed payload is reported as unknown
+        pass
+
+    return DecodedRevert(kind="unknown", data=data, selector=selector)
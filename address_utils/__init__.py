@@ -0,0 +1 @@
+from .address_utils import *
\ No newline at end of file
@@ -0,0 +1,172 @@
+from typing import TYPE_CHECKING, Iterable, List, Optional, Tuple, Union
+from eth_utils import keccak, to_checksum_address
+from ..cache import cached_function
+
+if TYPE_CHECKING:
+    import numpy
+
+__all__ = [
+    "create2_address_bytes",
+    "compute_create2_address",
+    "to_checksum_addresses",
+    "check_address_array",
+    "clone_init_code_hash",
+    "compute_solidly_pair_address",
+    "compute_solidly_pair_addresses",
+]
+
+
+def _to_bytes(value: Union[bytes, str]) -> bytes:
+    if isinstance(value, str):
+        return bytes.fromhex(value[2:] if value.startswith("0x") else value)
+    return bytes(value)
+
+
+def create2_address_bytes(
+    deployer: bytes,
+    salt: bytes,
+    init_code_hash: bytes,
+) -> bytes:
+    """
+    Returns the raw 20-byte CREATE2 address, skipping input conversion and checksumming.
+    Intended for hot loops where the inputs are already validated bytes
+    """
+    return keccak(b"\xff" + deployer + salt + init_code_hash)[12:]
+
+
+@cached_function
+def compute_create2_address(
+    deployer: Union[bytes, str],
+    salt: Union[bytes, str, int],
+    init_code_hash: Union[bytes, str],
+) -> str:
+    """
+    Computes the checksummed address of a contract deployed via CREATE2:
+    keccak256(0xff ++ deployer ++ salt ++ keccak256(init_code))[12:]
+    """
+    deployer = _to_bytes(deployer)
+    salt = salt.to_bytes(32, "big") if isinstance(salt, int) else _to_bytes(salt)
+    init_code_hash = _to_bytes(init_code_hash)
+
+    assert len(deployer) == 20, f"Expected a 20-byte deployer, found {len(deployer)}"
+    assert len(salt) == 32, f"Expected a 32-byte salt, found {len(salt)}"
+    assert (
+        len(init_code_hash) == 32
+    ), f"Expected a 32-byte init code hash, found {len(init_code_hash)}"
+
+    return to_checksum_address(create2_address_bytes(deployer, salt, init_code_hash))
+
+
+def to_checksum_addresses(
+    addresses: Iterable[Union[bytes, str]], out: Optional["numpy.ndarray"] = None
+) -> Union[List[str], "numpy.ndarray"]:
+    """
+    Checksums a batch of addresses. By default a list of str is returned. When `out`
+    is a numpy array of fixed-width byte strings (dtype "S42"), the ASCII checksummed
+    addresses are written into it instead and the array is returned, avoiding one
+    Python str allocation per address
+    """
+    if out is None:
+        return [to_checksum_address(address) for address in addresses]
+    check_address_array(out)
+    for i, address in enumerate(addresses):
+        out[i] = to_checksum_address(address).encode()
+    return out
+
+
+def check_address_array(out: "numpy.ndarray") -> None:
+    assert (
+        out.dtype.kind == "S" and out.dtype.itemsize >= 42
+    ), f"Expected an array of dtype S42, found {out.dtype}"
+
+
+# creation code of an EIP-1167 minimal proxy, around the implementation address
+_CLONE_PREFIX = bytes.fromhex("3d602d80600a3d3981f3363d3d373d3d3d363d73")
+_CLONE_SUFFIX = bytes.fromhex("5af43d82803e903d91602b57fd5bf3")
+
+
+def clone_init_code_hash(implementation: Union[bytes, str]) -> bytes:
+    """
+    Returns the init code hash of an EIP-1167 minimal proxy of `implementation`, as
+    deployed by OpenZeppelin's Clones.cloneDeterministic
+    """
+    implementation = _to_bytes(implementation)
+    assert len(implementation) == 20, "Expected a 20-byte implementation address"
+    return keccak(_CLONE_PREFIX + implementation + _CLONE_SUFFIX)
+
+
+def _solidly_salt(token_a: bytes, token_b: bytes, stable: bool) -> bytes:
+    token0, token1 = (token_a, token_b) if token_a < token_b else (token_b, token_a)
+    return keccak(token0 + token1 + (b"\x01" if stable else b"\x00"))
+
+
+def _solidly_init_code_hash(
+    init_code_hash: Union[bytes, str, None],
+    implementation: Union[bytes, str, None],
+) -> bytes:
+    assert (init_code_hash is None) != (
+        implementation is None
+    ), "Give either the pair init code hash or the clone implementation"
+    if implementation is not None:
+        return clone_init_code_hash(implementation)
+    init_code_hash = _to_bytes(init_code_hash)
+    assert (
+        len(init_code_hash) == 32
+    ), f"Expected a 32-byte init code hash, found {len(init_code_hash)}"
+    return init_code_hash
+
+
+def compute_solidly_pair_address(
+    factory: Union[bytes, str],
+    token0: Union[bytes, str],
+    token1: Union[bytes, str],
+    stable: bool,
+    init_code_hash: Optional[Union[bytes, str]] = None,
+    implementation: Optional[Union[bytes, str]] = None,
+) -> str:
+    """
+    Computes the address of a Solidly-style pair, salted with
+    keccak256(abi.encodePacked(token0, token1, stable)). The tokens may be given in
+    either order.
+
+    Pairs deployed from their own creation code (Solidly, Velodrome V1) need its
+    `init_code_hash`. Factories deploying minimal proxy clones of a pool
+    implementation (Velodrome V2, Aerodrome) need the `implementation` address instead
+    """
+    factory = _to_bytes(factory)
+    assert len(factory) == 20, f"Expected a 20-byte factory, found {len(factory)}"
+    return to_checksum_address(
+        create2_address_bytes(
+            factory,
+            _solidly_salt(_to_bytes(token0), _to_bytes(token1), stable),
+            _solidly_init_code_hash(init_code_hash, implementation),
+        )
+    )
+
+
+def compute_solidly_pair_addresses(
+    factory: Union[bytes, str],
+    pairs: Iterable[Tuple[Union[bytes, str], Union[bytes, str], bool]],
+    init_code_hash: Optional[Union[bytes, str]] = None,
+    implementation: Optional[Union[bytes, str]] = None,
+    out: Optional["numpy.ndarray"] = None,
+) -> Union[List[str], "numpy.ndarray"]:
+    """
+    Computes the addresses of many (token_a, token_b, stable) pairs of one factory,
+    e.g. every stable and volatile pair of a token list for pool discovery. The
+    results are returned like to_checksum_addresses, optionally into `out`
+    """
+    factory = _to_bytes(factory)
+    assert len(factory) == 20, f"Expected a 20-byte factory, found {len(factory)}"
+    init_code_hash = _solidly_init_code_hash(init_code_hash, implementation)
+    return to_checksum_addresses(
+        (
+            create2_address_bytes(
+                factory,
+                _solidly_salt(_to_bytes(token_a), _to_bytes(token_b), stable),
+                init_code_hash,
+            )
+            for token_a, token_b, stable in pairs
+        ),
+        out,
+    )
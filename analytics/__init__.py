@@ -0,0 +1,2 @@
+from .analytics import *
+from .event_parquet import *
\ No newline at end of file
@@ -0,0 +1,386 @@
+import math
+from dataclasses import dataclass
+from fractions import Fraction
+from typing import Dict, Iterable, List, Optional, Tuple, Union
+from ..pool_state import V3PoolState
+from ..constants import MAX_SQRT_RATIO, MIN_SQRT_RATIO, Q96
+from ..uniswap_v3 import (
+    get_amount0_delta,
+    get_amount1_delta,
+    get_sqrt_ratio_at_tick,
+    get_tick_at_sqrt_ratio,
+)
+
+__all__ = [
+    "SwapEvent",
+    "Candle",
+    "PoolVolume",
+    "vwap",
+    "ohlcv_candles",
+    "volume_by_pool",
+    "LiquidityLevel",
+    "liquidity_profile",
+    "LpPnl",
+    "lp_pnl",
+]
+
+
+@dataclass(frozen=True)
+class SwapEvent:
+    """
+    A decoded swap, with amounts given as signed balance changes of the pool
+    (positive = paid into the pool). For V3 these are the Swap event's amount0 and
+    amount1, for V2 they are amount0In - amount0Out and amount1In - amount1Out
+    """
+
+    pool: str
+    timestamp: int
+    block_number: int
+    log_index: int
+    amount0: int
+    amount1: int
+
+
+@dataclass
+class Candle:
+    start: int
+    open: float
+    high: float
+    low: float
+    close: float
+    # raw token amounts traded, in the smallest units
+    volume0: int
+    volume1: int
+    swap_count: int
+
+
+@dataclass
+class PoolVolume:
+    pool: str
+    volume0: int
+    volume1: int
+    swap_count: int
+    first_timestamp: int
+    last_timestamp: int
+
+
+def _ordered(swaps: Iterable[SwapEvent]) -> List[SwapEvent]:
+    return sorted(
+        (swap for swap in swaps if swap.amount0 and swap.amount1),
+        key=lambda swap: (swap.timestamp, swap.block_number, swap.log_index),
+    )
+
+
+def _price(
+    amount0: int, amount1: int, token0_decimals: int, token1_decimals: int
+) -> float:
+    # token1 per token0 in human-readable units, rounded once
+    return float(
+        Fraction(abs(amount1) * 10 ** token0_decimals)
+        / (abs(amount0) * 10 ** token1_decimals)
+    )
+
+
+def vwap(
+    swaps: Iterable[SwapEvent],
+    token0_decimals: int = 0,
+    token1_decimals: int = 0,
+) -> Optional[float]:
+    """
+    Returns the volume-weighted average price of token0 in units of token1. The
+    volume sums are accumulated as integers, so rounding only happens in the final
+    division. Returns None if there are no swaps
+    """
+    volume0 = volume1 = 0
+    for swap in swaps:
+        if swap.amount0 and swap.amount1:
+            volume0 += abs(swap.amount0)
+            volume1 += abs(swap.amount1)
+    if not volume0:
+        return None
+    return _price(volume0, volume1, token0_decimals, token1_decimals)
+
+
+def ohlcv_candles(
+    swaps: Iterable[SwapEvent],
+    interval: int,
+    token0_decimals: int = 0,
+    token1_decimals: int = 0,
+) -> List[Candle]:
+    """
+    Aggregates swaps into OHLCV candles of `interval` seconds, aligned to multiples of
+    the interval. Each swap's execution price (token1 per token0) is used for the
+    open, high, low and close. Intervals without swaps produce no candle
+    """
+    assert interval > 0, "Candle interval must be positive"
+
+    candles: List[Candle] = []
+    for swap in _ordered(swaps):
+        price = _price(swap.amount0, swap.amount1, token0_decimals, token1_decimals)
+        start = swap.timestamp - swap.timestamp % interval
+        if not candles or candles[-1].start != start:
+            candles.append(
+                Candle(
+                    start=start,
+                    open=price,
+                    high=price,
+                    low=price,
+                    close=price,
+                    volume0=0,
+                    volume1=0,
+                    swap_count=0,
+                )
+            )
+        candle = candles[-1]
+        candle.high = max(candle.high, price)
+        candle.low = min(candle.low, price)
+        candle.close = price
+        candle.volume0 += abs(swap.amount0)
+        candle.volume1 += abs(swap.amount1)
+        candle.swap_count += 1
+    return candles
+
+
+def volume_by_pool(swaps: Iterable[SwapEvent]) -> Dict[str, PoolVolume]:
+    """
+    Summarizes the traded volume and swap count of each pool
+    """
+    summary: Dict[str, PoolVolume] = {}
+    for swap in swaps:
+        volume = summary.get(swap.pool)
+        if volume is None:
+            volume = summary[swap.pool] = PoolVolume(
+                pool=swap.pool,
+                volume0=0,
+                volume1=0,
+                swap_count=0,
+                first_timestamp=swap.timestamp,
+                last_timestamp=swap.timestamp,
+            )
+        volume.volume0 += abs(swap.amount0)
+        volume.volume1 += abs(swap.amount1)
+        volume.swap_count += 1
+        volume.first_timestamp = min(volume.first_timestamp, swap.timestamp)
+        volume.last_timestamp = max(volume.last_timestamp, swap.timestamp)
+    return summary
+
+
+@dataclass
+class LiquidityLevel:
+    tick: int
+    sqrt_price_x96: int
+    # raw price of token0 in units of token1
+    price: float
+    # cumulative amounts the pool holds between the current price and this level:
+    # token0 sold by the pool when the price rises, token1 when it falls
+    amount0: int
+    amount1: int
+
+
+def _sqrt_price_from_price(price: Union[Fraction, float, int]) -> int:
+    sqrt_price_x96 = math.isqrt(int(Fraction(price) * 2 ** 192))
+    return min(max(sqrt_price_x96, MIN_SQRT_RATIO), MAX_SQRT_RATIO - 1)
+
+
+def liquidity_profile(
+    pool_state: V3PoolState,
+    price_range: Tuple[Union[Fraction, float, int], Union[Fraction, float, int]],
+    steps: int,
+) -> List[LiquidityLevel]:
+    """
+    Converts the tick map of a V3 pool into the cumulative liquidity available from
+    the current price to each of `steps` levels spaced evenly in tick space across
+    `price_range` (raw token1/token0 prices). Levels above the current price report
+    the token0 the pool would sell to reach them, levels below report the token1.
+    Suitable for depth charts and slippage curves
+    """
+    assert steps >= 2, "At least two price levels are required"
+    assert pool_state.sqrt_price_x96, "The pool price is not initialized"
+
+    tick_low = get_tick_at_sqrt_ratio(_sqrt_price_from_price(min(price_range)))
+    tick_high = get_tick_at_sqrt_ratio(_sqrt_price_from_price(max(price_range)))
+    level_ticks = sorted(
+        {tick_low + (tick_high - tick_low) * i // (steps - 1) for i in range(steps)}
+    )
+
+    current_sqrt_price = pool_state.sqrt_price_x96
+    levels_above = [
+        tick
+        for tick in level_ticks
+        if get_sqrt_ratio_at_tick(tick) > current_sqrt_price
+    ]
+    levels_below = [
+        tick
+        for tick in reversed(level_ticks)
+        if get_sqrt_ratio_at_tick(tick) <= current_sqrt_price
+    ]
+    initialized = sorted(pool_state.ticks)
+    amounts: Dict[int, Tuple[int, int]] = {}
+
+    # walk upwards, crossing initialized ticks above the current tick
+    liquidity = pool_state.liquidity
+    sqrt_price = current_sqrt_price
+    cumulative = 0
+    crossings = iter([tick for tick in initialized if tick > pool_state.tick])
+    next_tick = next(crossings, None)
+    for level in levels_above:
+        level_sqrt_price = get_sqrt_ratio_at_tick(level)
+        while next_tick is not None and next_tick <= level:
+            tick_sqrt_price = get_sqrt_ratio_at_tick(next_tick)
+            cumulative += get_amount0_delta(
+                sqrt_price, tick_sqrt_price, liquidity, False
+            )
+            liquidity += pool_state.ticks[next_tick].liquidity_net
+            sqrt_price = tick_sqrt_price
+            next_tick = next(crossings, None)
+        cumulative += get_amount0_delta(sqrt_price, level_sqrt_price, liquidity, False)
+        sqrt_price = level_sqrt_price
+        amounts[level] = (cumulative, 0)
+
+    # walk downwards, crossing initialized ticks at or below the current tick
+    liquidity = pool_state.liquidity
+    sqrt_price = current_sqrt_price
+    cumulative = 0
+    crossings = iter(
+        [tick for tick in reversed(initialized) if tick <= pool_state.tick]
+    )
+    next_tick = next(crossings, None)
+    for level in levels_below:
+        level_sqrt_price = get_sqrt_ratio_at_tick(level)
+        while next_tick is not None and next_tick > level:
+            tick_sqrt_price = get_sqrt_ratio_at_tick(next_tick)
+            cumulative += get_amount1_delta(
+                tick_sqrt_price, sqrt_price, liquidity, False
+            )
+            liquidity -= pool_state.ticks[next_tick].liquidity_net
+            sqrt_price = tick_sqrt_price
+            next_tick = next(crossings, None)
+        cumulative += get_amount1_delta(level_sqrt_price, sqrt_price, liquidity, False)
+        sqrt_price = level_sqrt_price
+        amounts[level] = (0, cumulative)
+
+    return [
+        LiquidityLevel(
+            tick=tick,
+            sqrt_price_x96=get_sqrt_ratio_at_tick(tick),
+            price=1.0001 ** tick,
+            amount0=amounts[tick][0],
+            amount1=amounts[tick][1],
+        )
+        for tick in level_ticks
+    ]
+
+
+@dataclass
+class LpPnl:
+    """
+    Result of lp_pnl. Amounts are raw token amounts, values are exact and denominated
+    in raw token1 units
+    """
+
+    entry_amount0: int
+    entry_amount1: int
+    exit_amount0: int
+    exit_amount1: int
+    # the entry amounts valued at the entry price
+    entry_value: Fraction
+    # the entry amounts held outside the pool, valued at the exit price
+    hold_value: Fraction
+    # the exit amounts valued at the exit price
+    position_value: Fraction
+    fees_value: Fraction
+    # position value minus hold value, zero or negative
+    impermanent_loss: Fraction
+    impermanent_loss_ratio: Fraction
+    # position value plus fees, minus entry value
+    pnl: Fraction
+
+
+def _position_amounts(
+    sqrt_price_x96: int,
+    liquidity: int,
+    sqrt_range: Optional[Tuple[int, int]],
+    round_up: bool,
+) -> Tuple[int, int]:
+    if sqrt_range is None:
+        # full-range V2 position: x = L / sqrt(P), y = L * sqrt(P)
+        if round_up:
+            return (
+                -(-liquidity * Q96 // sqrt_price_x96),
+                -(-liquidity * sqrt_price_x96 // Q96),
+            )
+        return liquidity * Q96 // sqrt_price_x96, liquidity * sqrt_price_x96 // Q96
+
+    sqrt_lower, sqrt_upper = sqrt_range
+    if sqrt_price_x96 <= sqrt_lower:
+        return get_amount0_delta(sqrt_lower, sqrt_upper, liquidity, round_up), 0
+    if sqrt_price_x96 >= sqrt_upper:
+        return 0, get_amount1_delta(sqrt_lower, sqrt_upper, liquidity, round_up)
+    return (
+        get_amount0_delta(sqrt_price_x96, sqrt_upper, liquidity, round_up),
+        get_amount1_delta(sqrt_lower, sqrt_price_x96, liquidity, round_up),
+    )
+
+
+def lp_pnl(
+    entry_price: Union[Fraction, float, int],
+    exit_price: Union[Fraction, float, int],
+    price_range: Optional[
+        Tuple[Union[Fraction, float, int], Union[Fraction, float, int]]
+    ],
+    liquidity: int,
+    fees_collected: Tuple[int, int] = (0, 0),
+) -> LpPnl:
+    """
+    Computes the impermanent loss and PnL of a liquidity position between entry and
+    exit, with prices given as raw token1/token0 prices.
+
+    A `price_range` of None describes a full-range V2 position, where `liquidity` is
+    sqrt(reserve0 * reserve1) scaled by the LP's share of the pool supply. Otherwise
+    it is the (lower, upper) price range of a concentrated V3 position with
+    `liquidity` as reported by the pool. Token amounts follow the pool's rounding
+    (deposits round up, withdrawals round down). `fees_collected` are the raw
+    amounts of token0 and token1 earned while the position was open
+    """
+    assert liquidity >= 0, "Liquidity must be non-negative"
+
+    entry_sqrt_price = _sqrt_price_from_price(entry_price)
+    exit_sqrt_price = _sqrt_price_from_price(exit_price)
+    sqrt_range = None
+    if price_range is not None:
+        sqrt_range = (
+            _sqrt_price_from_price(min(price_range)),
+            _sqrt_price_from_price(max(price_range)),
+        )
+        assert sqrt_range[0] < sqrt_range[1], "The price range is empty"
+
+    entry_amount0, entry_amount1 = _position_amounts(
+        entry_sqrt_price, liquidity, sqrt_range, round_up=True
+    )
+    exit_amount0, exit_amount1 = _position_amounts(
+        exit_sqrt_price, liquidity, sqrt_range, round_up=False
+    )
+
+    entry_price = Fraction(entry_sqrt_price ** 2, 2 ** 192)
+    exit_price = Fraction(exit_sqrt_price ** 2, 2 ** 192)
+    entry_value = entry_amount0 * entry_price + entry_amount1
+    hold_value = entry_amount0 * exit_price + entry_amount1
+    position_value = exit_amount0 * exit_price + exit_amount1
+    fees_value = fees_collected[0] * exit_price + fees_collected[1]
+    impermanent_loss = position_value - hold_value
+
+    return LpPnl(
+        entry_amount0=entry_amount0,
+        entry_amount1=entry_amount1,
+        exit_amount0=exit_amount0,
+        exit_amount1=exit_amount1,
+        entry_value=entry_value,
+        hold_value=hold_value,
+        position_value=position_value,
+        fees_value=fees_value,
+        impermanent_loss=impermanent_loss,
+        impermanent_loss_ratio=(
+            impermanent_loss / hold_value if hold_value else Fraction(0)
+        ),
+        pnl=position_value + fees_value - entry_value,
+    )
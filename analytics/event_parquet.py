@@ -0,0 +1,129 @@
+from dataclasses import fields
+from decimal import Decimal
+from typing import Iterable, Iterator, List, Union
+from .analytics import SwapEvent
+from ..pool_state import V2SyncEvent, V3BurnEvent, V3MintEvent, V3SwapEvent
+
+__all__ = ["EVENT_TYPES", "DecodedEvent", "write_events_parquet", "read_events_parquet"]
+
+# event_type column value for each supported event class
+EVENT_TYPES = {
+    SwapEvent: "swap",
+    V2SyncEvent: "v2_sync",
+    V3SwapEvent: "v3_swap",
+    V3MintEvent: "v3_mint",
+    V3BurnEvent: "v3_burn",
+}
+
+_EVENT_CLASSES = {event_type: cls for cls, event_type in EVENT_TYPES.items()}
+
+# integer columns wider than 64 bits, stored as Decimal256(76, 0)
+_AMOUNT_COLUMNS = (
+    "amount0",
+    "amount1",
+    "reserve0",
+    "reserve1",
+    "sqrt_price_x96",
+    "liquidity",
+    "amount",
+)
+
+DecodedEvent = Union[SwapEvent, V2SyncEvent, V3SwapEvent, V3MintEvent, V3BurnEvent]
+
+
+def _event_schema(pa):
+    amount = pa.decimal256(76, 0)
+    return pa.schema(
+        [
+            pa.field("event_type", pa.string(), nullable=False),
+            pa.field("pool", pa.string(), nullable=False),
+            pa.field("block_number", pa.uint64(), nullable=False),
+            pa.field("log_index", pa.uint32(), nullable=False),
+            pa.field("timestamp", pa.int64()),
+            pa.field("amount0", amount),
+            pa.field("amount1", amount),
+            pa.field("reserve0", amount),
+            pa.field("reserve1", amount),
+            pa.field("sqrt_price_x96", amount),
+            pa.field("liquidity", amount),
+            pa.field("tick", pa.int32()),
+            pa.field("tick_lower", pa.int32()),
+            pa.field("tick_upper", pa.int32()),
+            pa.field("amount", amount),
+        ]
+    )
+
+
+def _pyarrow_parquet():
+    try:
+        import pyarrow
+        import pyarrow.parquet
+    except ImportError as e:
+        raise ImportError(
+            "Parquet output requires the optional pyarrow dependency "
+            "(pip install pyarrow)"
+        ) from e
+    return pyarrow, pyarrow.parquet
+
+
+def _row(event: DecodedEvent) -> dict:
+    event_type = EVENT_TYPES.get(type(event))
+    assert event_type is not None, f"Unsupported event type {type(event).__name__}"
+    row = {"event_type": event_type}
+    for field in fields(event):
+        value = getattr(event, field.name)
+        row[field.name] = Decimal(value) if field.name in _AMOUNT_COLUMNS else value
+    return row
+
+
+def write_events_parquet(
+    events: Iterable[DecodedEvent],
+    path: str,
+    row_group_size: int = 100_000,
+    compression: str = "zstd",
+) -> int:
+    """
+    Streams decoded swap, sync, mint and burn events to a Parquet file, writing one
+    row group per `row_group_size` events, so arbitrarily long histories can be
+    written from a generator. All event types share one stable schema: an
+    `event_type` column, the pool, block number and log index, and a nullable column
+    for every other event field, with wide integers stored as Decimal256(76, 0).
+    Returns the number of events written
+    """
+    pa, pq = _pyarrow_parquet()
+    schema = _event_schema(pa)
+    written = 0
+    with pq.ParquetWriter(path, schema, compression=compression) as writer:
+        rows: List[dict] = []
+        for event in events:
+            rows.append(_row(event))
+            if len(rows) >= row_group_size:
+                writer.write_table(pa.Table.from_pylist(rows, schema=schema))
+                written += len(rows)
+                rows = []
+        if rows:
+            writer.write_table(pa.Table.from_pylist(rows, schema=schema))
+            written += len(rows)
+    return written
+
+
+def read_events_parquet(path: str) -> Iterator[DecodedEvent]:
+    """
+    Reads events written by write_events_parquet back into event objects, one row
+    group at a time, in the order they were written
+    """
+    _, pq = _pyarrow_parquet()
+    parquet_file = pq.ParquetFile(path)
+    for row_group in range(parquet_file.num_row_groups):
+        for row in parquet_file.read_row_group(row_group).to_pylist():
+            cls = _EVENT_CLASSES[row["event_type"]]
+            yield cls(
+                **{
+                    field.name: (
+                        int(row[field.name])
+                        if field.name in _AMOUNT_COLUMNS
+                        else row[field.name]
+                    )
+                    for field in fields(cls)
+                }
+            )
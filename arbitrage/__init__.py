@@ -2,3 +2,10 @@ from .flash_borrow_to_router_swap import *
 from .flash_borrow_to_lp_swap import *
 from .flash_borrow_to_lp_swap_new import *
 from .flash_borrow_to_lp_swap_with_future import *
+
+from .arbitrage_math import *
+from .ranking import *
+from .backrun import *
+from .sandwich import *
+from .split import *
+from .paths import *
\ No newline at end of file
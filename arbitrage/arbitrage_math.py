@@ -0,0 +1,148 @@
+import math
+import multiprocessing
+from fractions import Fraction
+from typing import Iterable, List, Optional, Tuple, Union
+import numpy
+from ..exceptions import DegenbotError
+from ..pool_state import V2PoolState, V3PoolState
+from ..quoter import Path, quote_path
+from ..uniswap_v3 import get_sqrt_ratio_at_tick
+
+__all__ = ["amount_to_target_price", "profit_curve"]
+
+# large enough to never be the binding constraint of a price-limited V3 swap
+_UNBOUNDED_INPUT = 2 ** 255 - 1
+
+
+def _v2_price_after(pool: V2PoolState, amount_in: int, zero_for_one: bool) -> Fraction:
+    """
+    Price of token0 in token1 after swapping `amount_in`
+    """
+    amount_out = pool.get_amount_out(amount_in, zero_for_one) if amount_in else 0
+    if zero_for_one:
+        return Fraction(pool.reserve1 - amount_out, pool.reserve0 + amount_in)
+    return Fraction(pool.reserve1 + amount_in, pool.reserve0 - amount_out)
+
+
+def _v2_amount_to_target_price(
+    pool: V2PoolState, target_price: Fraction, zero_for_one: bool
+) -> int:
+    # in the direction of the swap: reserve_in grows, reserve_out shrinks
+    if zero_for_one:
+        reserve_in, reserve_out, target = pool.reserve0, pool.reserve1, target_price
+    else:
+        reserve_in, reserve_out, target = pool.reserve1, pool.reserve0, 1 / target_price
+    gamma = 1 - pool.fee
+
+    # with dx in, (reserve_out') / (reserve_in') = target expands to the quadratic
+    # gamma*T*dx^2 + T*r_in*(1+gamma)*dx + T*r_in^2 - r_in*r_out = 0
+    a = gamma * target
+    b = target * reserve_in * (1 + gamma)
+    c = target * reserve_in ** 2 - reserve_in * reserve_out
+    discriminant = b * b - 4 * a * c
+    root = Fraction(
+        math.isqrt(discriminant.numerator * discriminant.denominator),
+        discriminant.denominator,
+    )
+    amount_in = max(0, math.ceil((root - b) / (2 * a)))
+
+    # the closed form is exact over the rationals, so only the integer rounding of
+    # the pool math needs correcting to find the smallest sufficient input
+    def reached(amount: int) -> bool:
+        price = _v2_price_after(pool, amount, zero_for_one)
+        return price <= target_price if zero_for_one else price >= target_price
+
+    while not reached(amount_in):
+        amount_in += 1
+    while amount_in > 0 and reached(amount_in - 1):
+        amount_in -= 1
+    return amount_in
+
+
+def amount_to_target_price(
+    pool_state: Union[V2PoolState, V3PoolState],
+    target_sqrt_price_x96: Optional[int] = None,
+    target_tick: Optional[int] = None,
+) -> Tuple[int, bool]:
+    """
+    Returns (amount_in, zero_for_one): the exact input, including fees, that moves a
+    pool's price to a target sqrt price or tick, and the swap direction that gets
+    there. V2 pools are solved in closed form, V3 pools by walking the tick range up to
+    the target. Returns an input of 0 if the pool is already at the target.
+
+    For V3 the amount is what the pool takes from a swap with the target as its
+    sqrtPriceLimitX96, which is how the swap should be sent: without the limit, fee
+    rounding may move the price marginally past the target
+    """
+    assert (target_sqrt_price_x96 is None) != (
+        target_tick is None
+    ), "Provide exactly one of target_sqrt_price_x96 or target_tick"
+    if target_sqrt_price_x96 is None:
+        target_sqrt_price_x96 = get_sqrt_ratio_at_tick(target_tick)
+
+    if isinstance(pool_state, V2PoolState):
+        target_price = Fraction(target_sqrt_price_x96 ** 2, 2 ** 192)
+        current_price = Fraction(pool_state.reserve1, pool_state.reserve0)
+        if target_price == current_price:
+            return 0, True
+        zero_for_one = target_price < current_price
+        return (
+            _v2_amount_to_target_price(pool_state, target_price, zero_for_one),
+            zero_for_one,
+        )
+
+    if isinstance(pool_state, V3PoolState):
+        if target_sqrt_price_x96 == pool_state.sqrt_price_x96:
+            return 0, True
+        zero_for_one = target_sqrt_price_x96 < pool_state.sqrt_price_x96
+        result = pool_state.simulate_swap(
+            _UNBOUNDED_INPUT, zero_for_one, target_sqrt_price_x96
+        )
+        return (result.amount0 if zero_for_one else result.amount1), zero_for_one
+
+    raise TypeError(f"Unsupported pool state {type(pool_state).__name__}")
+
+
+def _path_profit(path: Path, amount_in: int) -> Optional[int]:
+    try:
+        return quote_path(path, amount_in)[-1] - amount_in
+    except (AssertionError, ArithmeticError, DegenbotError):
+        # the pools cannot absorb this input
+        return None
+
+
+def _profit_chunk(path: Path, amounts: List[int]) -> List[Optional[int]]:
+    return [_path_profit(path, amount) for amount in amounts]
+
+
+def profit_curve(
+    path: Path,
+    amounts: Iterable[int],
+    workers: int = 1,
+    chunk_size: int = 1_000,
+) -> numpy.ndarray:
+    """
+    Evaluates the profit of a cyclic arbitrage path (the output token is the input
+    token) at each input amount, returning an object array of exact integer profits
+    in the same order. Inputs the path cannot absorb yield None.
+
+    With workers > 1 the amounts are split into chunks of `chunk_size` and evaluated
+    across processes, so the pool states in the path must be picklable
+    """
+    amounts = [int(amount) for amount in amounts]
+    assert workers >= 1, "At least one worker is required"
+
+    if workers == 1 or len(amounts) <= chunk_size:
+        profits = _profit_chunk(path, amounts)
+    else:
+        chunks = [
+            amounts[start : start + chunk_size]
+            for start in range(0, len(amounts), chunk_size)
+        ]
+        with multiprocessing.Pool(processes=workers) as pool:
+            results = pool.starmap(_profit_chunk, [(path, chunk) for chunk in chunks])
+        profits = [profit for chunk in results for profit in chunk]
+
+    curve = numpy.empty(len(profits), dtype=object)
+    curve[:] = profits
+    return curve
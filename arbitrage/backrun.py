@@ -0,0 +1,120 @@
+from dataclasses import dataclass
+from typing import Dict, List, Optional, Sequence, Tuple
+from scipy import optimize
+from ..pool_state import (
+    CurveCryptoPoolState,
+    CurveStableswapPoolState,
+    SolidlyPoolState,
+    V2PoolState,
+    V3PoolState,
+)
+from .arbitrage_math import _path_profit
+from ..quoter import PathHop, quote_path
+
+__all__ = ["PendingSwap", "BackrunResult", "backrun_after_swap"]
+
+
+@dataclass(frozen=True)
+class PendingSwap:
+    """
+    The predicted pool-level effect of a pending transaction: an exact-input swap of
+    `amount_in` from coin index `token_in` to `token_out` in the pool at `pool`
+    """
+
+    pool: str
+    token_in: int
+    token_out: int
+    amount_in: int
+
+
+@dataclass
+class BackrunResult:
+    # copies of the pool states with the pending swap applied
+    pool_states: Dict[str, object]
+    victim_amount_out: int
+    amount_in: int
+    # amounts at every hop of the backrun, as returned by quote_path
+    amounts: List[int]
+    profit: int
+
+
+def _apply_swap(pool, token_in: int, token_out: int, amount_in: int) -> int:
+    if isinstance(pool, (CurveStableswapPoolState, CurveCryptoPoolState)):
+        return pool.exchange(token_in, token_out, amount_in)
+    if isinstance(pool, V3PoolState):
+        result = pool.swap(amount_in, token_in == 0)
+        return -(result.amount1 if token_in == 0 else result.amount0)
+    if isinstance(pool, (V2PoolState, SolidlyPoolState)):
+        return pool.swap(amount_in, token_in == 0)
+    raise TypeError(f"Unsupported pool state {type(pool).__name__}")
+
+
+def _default_max_input(hop: PathHop) -> int:
+    pool = hop.pool
+    if isinstance(pool, (CurveStableswapPoolState, CurveCryptoPoolState)):
+        return pool.balances[hop.token_in]
+    if isinstance(pool, (V2PoolState, SolidlyPoolState)):
+        return pool.reserve0 if hop.token_in == 0 else pool.reserve1
+    raise ValueError("max_input must be given for paths starting in a V3 pool")
+
+
+def backrun_after_swap(
+    pool_states: Dict[str, object],
+    pending_swap: PendingSwap,
+    arbitrage_path: Sequence[Tuple[str, int, int]],
+    max_input: Optional[int] = None,
+) -> Optional[BackrunResult]:
+    """
+    Applies a pending swap to copies of the pool states, then finds the input of the
+    cyclic `arbitrage_path` (hops of pool address, token_in, token_out) maximizing its
+    profit against the post-victim state. The originals are left untouched.
+
+    Returns None if no input is profitable
+    """
+    states = {address: pool.copy() for address, pool in pool_states.items()}
+    victim_pool = states.get(pending_swap.pool)
+    assert victim_pool is not None, f"Pool {pending_swap.pool} is not tracked"
+    victim_amount_out = _apply_swap(
+        victim_pool,
+        pending_swap.token_in,
+        pending_swap.token_out,
+        pending_swap.amount_in,
+    )
+
+    path = [
+        PathHop(states[address], token_in, token_out)
+        for address, token_in, token_out in arbitrage_path
+    ]
+    if max_input is None:
+        max_input = _default_max_input(path[0])
+
+    def _profit(amount_in: int) -> int:
+        profit = _path_profit(path, amount_in)
+        return profit if profit is not None else -amount_in
+
+    opt = optimize.minimize_scalar(
+        lambda x: -float(_profit(int(x))),
+        method="bounded",
+        bounds=(1, max_input),
+    )
+
+    # the optimizer works in floats, so settle on the best integer around its result
+    best_input = max(
+        (
+            amount
+            for amount in range(int(opt.x) - 1, int(opt.x) + 2)
+            if 1 <= amount <= max_input
+        ),
+        key=_profit,
+    )
+    best_profit = _profit(best_input)
+    if best_profit <= 0:
+        return None
+
+    return BackrunResult(
+        pool_states=states,
+        victim_amount_out=victim_amount_out,
+        amount_in=best_input,
+        amounts=quote_path(path, best_input),
+        profit=best_profit,
+    )
@@ -0,0 +1,30 @@
+from typing import List
+from eth_utils import keccak
+from ..quoter import Path
+from ..quoter.quoter import _hops
+
+__all__ = ["path_id"]
+
+
+def _hop_bytes(pool, token_in: int, token_out: int) -> bytes:
+    address = pool if isinstance(pool, str) else pool.address
+    return bytes.fromhex(address[2:]) + bytes([token_in, token_out])
+
+
+def path_id(path: Path, cycle: bool = False) -> bytes:
+    """
+    Returns a stable 32-byte identifier of a path: the keccak hash of every hop's
+    pool address and coin indices, in order. Hops may name their pool by address
+    instead of a pool state, so identifiers can be computed for persisted paths.
+
+    With `cycle`, the path is treated as a cycle and hashed from its smallest rotation,
+    so the same loop entered at a different pool gets the same identifier
+    """
+    encoded: List[bytes] = [
+        _hop_bytes(hop.pool, hop.token_in, hop.token_out) for hop in _hops(path)
+    ]
+    if cycle:
+        encoded = min(
+            encoded[start:] + encoded[:start] for start in range(len(encoded))
+        )
+    return keccak(b"".join(encoded))
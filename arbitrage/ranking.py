@@ -0,0 +1,193 @@
+import heapq
+import itertools
+import threading
+import time
+from dataclasses import dataclass
+from typing import Any, Callable, Dict, Hashable, Iterable, List, Optional
+from ..quoter import Path, quote_path
+
+__all__ = [
+    "ArbitrageCandidate",
+    "RankedOpportunity",
+    "rank_opportunities",
+    "OpportunityHeap",
+]
+
+
+@dataclass(frozen=True)
+class ArbitrageCandidate:
+    """
+    A potential arbitrage with its expected gross profit, in the profit token, and
+    estimated gas usage. `payload` carries whatever the caller needs to execute it
+    """
+
+    profit: int
+    gas_used: int
+    payload: Any = None
+
+
+@dataclass(frozen=True)
+class RankedOpportunity:
+    candidate: ArbitrageCandidate
+    # gas cost converted to the profit token
+    gas_cost: int
+    net_profit: int
+
+
+def rank_opportunities(
+    candidates: Iterable[ArbitrageCandidate],
+    gas_price: int,
+    profit_token_eth_path: Optional[Path] = None,
+    include_unprofitable: bool = False,
+) -> List[RankedOpportunity]:
+    """
+    Ranks candidates by profit net of gas, most profitable first. The gas cost (in wei)
+    is converted to the profit token by quoting it through `profit_token_eth_path`, a
+    path from WETH to the profit token; leave it unset when profits are already in
+    WETH. Candidates that do not cover their gas cost are dropped unless
+    `include_unprofitable` is set
+    """
+    conversions: Dict[int, int] = {}
+
+    def _gas_cost(gas_used: int) -> int:
+        cost = gas_used * gas_price
+        if not profit_token_eth_path or not cost:
+            return cost
+        if cost not in conversions:
+            conversions[cost] = quote_path(profit_token_eth_path, cost)[-1]
+        return conversions[cost]
+
+    ranked = []
+    for candidate in candidates:
+        gas_cost = _gas_cost(candidate.gas_used)
+        net_profit = candidate.profit - gas_cost
+        if net_profit > 0 or include_unprofitable:
+            ranked.append(RankedOpportunity(candidate, gas_cost, net_profit))
+    ranked.sort(key=lambda opportunity: opportunity.net_profit, reverse=True)
+    return ranked
+
+
+class OpportunityHeap:
+    """
+    Max-heap of ranked opportunities keyed by net profit, shared between the
+    evaluation pipeline (which pushes) and the execution loop (which pops). Each
+    opportunity has a key identifying its path: pushing an existing key replaces the
+    previous entry, so re-evaluating a path every block never leaves duplicates.
+    Entries expire `ttl` seconds after they were pushed, as measured by `clock`.
+
+    Superseded and expired entries are discarded lazily when they reach the top, so
+    every operation is logarithmic. All methods are thread-safe
+    """
+
+    def __init__(
+        self,
+        ttl: Optional[float] = None,
+        clock: Callable[[], float] = time.monotonic,
+    ) -> None:
+        self.ttl = ttl
+        self.clock = clock
+        self._heap: List[list] = []
+        # live entry of each key: [-net_profit, sequence, key, opportunity, expiry]
+        self._entries: Dict[Hashable, list] = {}
+        self._sequence = itertools.count()
+        self._lock = threading.Lock()
+
+    def __len__(self) -> int:
+        with self._lock:
+            self._expire()
+            return len(self._entries)
+
+    def __contains__(self, key: Hashable) -> bool:
+        with self._lock:
+            entry = self._entries.get(key)
+            return entry is not None and not self._expired(entry, self.clock())
+
+    @staticmethod
+    def _expired(entry: list, now: float) -> bool:
+        return entry[4] is not None and entry[4] <= now
+
+    def _expire(self) -> None:
+        now = self.clock()
+        for key in [
+            key for key, entry in self._entries.items() if self._expired(entry, now)
+        ]:
+            self._entries.pop(key)[3] = None
+
+    def push(
+        self,
+        opportunity: RankedOpportunity,
+        key: Hashable,
+        ttl: Optional[float] = None,
+    ) -> None:
+        """
+        Adds an opportunity, replacing any with the same key. `ttl` overrides the
+        heap's default lifetime
+        """
+        ttl = self.ttl if ttl is None else ttl
+        expiry = None if ttl is None else self.clock() + ttl
+        with self._lock:
+            entry = [
+                -opportunity.net_profit,
+                next(self._sequence),
+                key,
+                opportunity,
+                expiry,
+            ]
+            previous = self._entries.pop(key, None)
+            if previous is not None:
+                previous[3] = None
+            self._entries[key] = entry
+            heapq.heappush(self._heap, entry)
+            if len(self._heap) > 2 * len(self._entries) + 64:
+                # superseded entries below the top are never popped, so rebuild the
+                # heap before they dominate it
+                self._heap = list(self._entries.values())
+                heapq.heapify(self._heap)
+
+    def remove(self, key: Hashable) -> bool:
+        """
+        Drops the opportunity with `key`, returning whether there was one
+        """
+        with self._lock:
+            entry = self._entries.pop(key, None)
+            if entry is None:
+                return False
+            entry[3] = None
+            return True
+
+    def _top(self) -> Optional[list]:
+        now = self.clock()
+        while self._heap:
+            entry = self._heap[0]
+            if entry[3] is not None and not self._expired(entry, now):
+                return entry
+            heapq.heappop(self._heap)
+            if entry[3] is not None:
+                del self._entries[entry[2]]
+        return None
+
+    def peek(self) -> Optional[RankedOpportunity]:
+        """
+        Returns the most profitable live opportunity without removing it, or None
+        """
+        with self._lock:
+            entry = self._top()
+            return None if entry is None else entry[3]
+
+    def pop(self) -> Optional[RankedOpportunity]:
+        """
+        Removes and returns the most profitable live opportunity, or None if there is
+        none
+        """
+        with self._lock:
+            entry = self._top()
+            if entry is None:
+                return None
+            heapq.heappop(self._heap)
+            del self._entries[entry[2]]
+            return entry[3]
+
+    def clear(self) -> None:
+        with self._lock:
+            self._heap.clear()
+            self._entries.clear()
@@ -0,0 +1,141 @@
+from dataclasses import dataclass
+from typing import Dict, List, Optional, Sequence, Tuple
+from scipy import optimize
+from .backrun import PendingSwap, _apply_swap
+from ..exceptions import SwapSimulationError, TickMathError
+
+__all__ = ["SwapExposure", "SlippageExposure", "slippage_exposure"]
+
+
+@dataclass
+class SwapExposure:
+    # position of the swap in the bundle
+    index: int
+    swap: PendingSwap
+    amount_out_min: int
+    # output of the swap if it is not sandwiched
+    amount_out: int
+    # the most profitable frontrun (in token_in), and the adversary's profit from it
+    frontrun_amount: int
+    profit: int
+    # output of the swap when sandwiched by frontrun_amount
+    sandwiched_amount_out: int
+
+
+@dataclass
+class SlippageExposure:
+    swaps: List[SwapExposure]
+
+    @property
+    def max_profit(self) -> int:
+        """
+        Upper bound of the total profit extractable by sandwiching every swap
+        separately. Each profit is denominated in that swap's input token
+        """
+        return sum(swap.profit for swap in self.swaps)
+
+
+def _sandwich(pool, swap: PendingSwap, frontrun_amount: int) -> Tuple[int, int]:
+    """
+    Simulates a frontrun of `frontrun_amount` in the direction of `swap`, the swap
+    itself, and a backrun selling the frontrun output. Returns the output of the swap
+    and the adversary's profit, in units of the input token
+    """
+    pool = pool.copy()
+    frontrun_out = (
+        _apply_swap(pool, swap.token_in, swap.token_out, frontrun_amount)
+        if frontrun_amount
+        else 0
+    )
+    amount_out = _apply_swap(pool, swap.token_in, swap.token_out, swap.amount_in)
+    backrun_out = (
+        _apply_swap(pool, swap.token_out, swap.token_in, frontrun_out)
+        if frontrun_out
+        else 0
+    )
+    return amount_out, backrun_out - frontrun_amount
+
+
+def _swap_exposure(
+    index: int, pool, swap: PendingSwap, amount_out_min: int
+) -> SwapExposure:
+    amount_out, _ = _sandwich(pool, swap, 0)
+    if amount_out < amount_out_min:
+        raise ValueError(
+            f"Swap {index} would revert: output {amount_out} is below the minimum "
+            f"{amount_out_min}"
+        )
+
+    def _outcome(frontrun_amount: int) -> Optional[Tuple[int, int]]:
+        try:
+            outcome = _sandwich(pool, swap, frontrun_amount)
+        except (AssertionError, ArithmeticError, SwapSimulationError, TickMathError):
+            # the pool cannot fill a frontrun this large
+            return None
+        return outcome if outcome[0] >= amount_out_min else None
+
+    # the output of the swap falls as the frontrun grows, so find the largest
+    # frontrun that keeps it above the minimum by doubling, then bisection
+    low, high = 0, max(swap.amount_in, 1)
+    while _outcome(high) is not None and high < 2 ** 128:
+        low, high = high, 2 * high
+    while high - low > 1:
+        middle = (low + high) // 2
+        if _outcome(middle) is not None:
+            low = middle
+        else:
+            high = middle
+    max_frontrun = low
+
+    def _profit(frontrun_amount: int) -> int:
+        outcome = _outcome(frontrun_amount)
+        return outcome[1] if outcome is not None else -frontrun_amount
+
+    # profit usually peaks where the slippage limit binds, but fees can move the
+    # optimum inside the feasible range
+    candidates = {0, max_frontrun}
+    if max_frontrun > 1:
+        opt = optimize.minimize_scalar(
+            lambda x: -float(_profit(int(x))),
+            method="bounded",
+            bounds=(1, max_frontrun),
+        )
+        candidates.add(int(opt.x))
+    frontrun_amount = max(sorted(candidates), key=_profit)
+    sandwiched_amount_out, profit = _outcome(frontrun_amount)
+
+    if profit <= 0:
+        frontrun_amount, profit, sandwiched_amount_out = 0, 0, amount_out
+    return SwapExposure(
+        index=index,
+        swap=swap,
+        amount_out_min=amount_out_min,
+        amount_out=amount_out,
+        frontrun_amount=frontrun_amount,
+        profit=profit,
+        sandwiched_amount_out=sandwiched_amount_out,
+    )
+
+
+def slippage_exposure(
+    bundle_swaps: Sequence[Tuple[PendingSwap, int]],
+    pool_states: Dict[str, object],
+) -> SlippageExposure:
+    """
+    Measures how much an adversary could extract by sandwiching the swaps of a bundle,
+    given as (swap, minimum accepted output) pairs in execution order. Each swap is
+    evaluated against the pool states left by the swaps before it: the adversary
+    frontruns in the same direction as far as the slippage limit allows, and backruns
+    by selling the frontrun output. The pool states are not modified.
+
+    A swap whose output falls below its minimum without any frontrun would revert,
+    which raises a ValueError
+    """
+    states = {address: pool.copy() for address, pool in pool_states.items()}
+    exposures = []
+    for index, (swap, amount_out_min) in enumerate(bundle_swaps):
+        pool = states.get(swap.pool)
+        assert pool is not None, f"Pool {swap.pool} is not tracked"
+        exposures.append(_swap_exposure(index, pool, swap, amount_out_min))
+        _apply_swap(pool, swap.token_in, swap.token_out, swap.amount_in)
+    return SlippageExposure(exposures)
@@ -0,0 +1,87 @@
+import heapq
+from dataclasses import dataclass
+from typing import List, Optional, Sequence
+from ..exceptions import SwapSimulationError
+from ..pool_state import V3PoolState
+
+__all__ = ["SplitRoute", "optimal_split"]
+
+
+@dataclass
+class SplitRoute:
+    # input and output in each pool, in the order the pools were given
+    amounts_in: List[int]
+    amounts_out: List[int]
+    amount_out: int
+
+
+def _amount_out(pool, amount_in: int, zero_for_one: bool) -> Optional[int]:
+    """
+    Returns the output of an exact-input swap, or None if the pool cannot fill it
+    """
+    if amount_in == 0:
+        return 0
+    if isinstance(pool, V3PoolState):
+        try:
+            result = pool.simulate_swap(amount_in, zero_for_one)
+        except SwapSimulationError:
+            return None
+        if (result.amount0 if zero_for_one else result.amount1) != amount_in:
+            # ran out of liquidity before the input was used up
+            return None
+        return -(result.amount1 if zero_for_one else result.amount0)
+    return pool.get_amount_out(amount_in, zero_for_one)
+
+
+def optimal_split(
+    pools: Sequence[object],
+    amount_in: int,
+    zero_for_one: bool,
+    steps: int = 100,
+) -> SplitRoute:
+    """
+    Splits an exact-input swap across pools of the same pair (V2, Solidly and V3
+    states, mixed) to maximize the total output. The input is allocated in `steps`
+    equal chunks, each to the pool offering the most output for it. Since the output
+    of every pool is concave in its input, this converges on the split that equalizes
+    the marginal prices of all pools used, to within one chunk per pool
+    """
+    assert pools, "At least one pool is required"
+    assert amount_in > 0, "Swap amount must be positive"
+    steps = min(steps, amount_in)
+    chunk, remainder = divmod(amount_in, steps)
+    chunks = [chunk + 1] * remainder + [chunk] * (steps - remainder)
+
+    amounts_in = [0] * len(pools)
+    amounts_out = [0] * len(pools)
+
+    def _gain(i: int, size: int):
+        amount_out = _amount_out(pools[i], amounts_in[i] + size, zero_for_one)
+        return None if amount_out is None else amount_out - amounts_out[i]
+
+    # max-heap of the output gained by sending the next chunk to each pool. Chunks
+    # only shrink by one unit, so a pool's gain is re-evaluated after it is chosen
+    heap = []
+    for i in range(len(pools)):
+        gain = _gain(i, chunks[0])
+        if gain is not None:
+            heap.append((-gain, i))
+    heapq.heapify(heap)
+
+    for size in chunks:
+        while heap:
+            _, i = heapq.heappop(heap)
+            gain = _gain(i, size)
+            if gain is not None:
+                break
+        else:
+            raise SwapSimulationError(
+                f"The pools cannot fill {amount_in} (filled {sum(amounts_in)})"
+            )
+        amounts_in[i] += size
+        amounts_out[i] += gain
+        next_gain = _gain(i, size)
+        if next_gain is not None:
+            heapq.heappush(heap, (-next_gain, i))
+
+    return SplitRoute(amounts_in, amounts_out, sum(amounts_out))
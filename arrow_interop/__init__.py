@@ -0,0 +1 @@
+from .arrow_interop import *
\ No newline at end of file
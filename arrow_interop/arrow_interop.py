@@ -0,0 +1,166 @@
+from decimal import Decimal
+from typing import TYPE_CHECKING, Iterable, List, Union
+from ..analytics import Candle, SwapEvent
+from ..rpc import Log, Receipt, ReceiptColumns
+
+if TYPE_CHECKING:
+    import pyarrow
+
+__all__ = [
+    "AMOUNT_PRECISION",
+    "logs_to_record_batch",
+    "receipts_to_record_batch",
+    "candles_to_record_batch",
+    "swaps_to_record_batch",
+]
+
+# 256-bit amounts do not fit Arrow's integer types. Decimal256 holds 76 digits,
+# enough for any realistic token amount
+AMOUNT_PRECISION = 76
+
+
+def _pyarrow():
+    try:
+        import pyarrow
+    except ImportError as e:
+        raise ImportError(
+            "Arrow output requires the optional pyarrow dependency "
+            "(pip install pyarrow)"
+        ) from e
+    return pyarrow
+
+
+def _amounts(pa, values: Iterable[int]):
+    return pa.array(
+        [Decimal(value) for value in values], type=pa.decimal256(AMOUNT_PRECISION, 0)
+    )
+
+
+def logs_to_record_batch(logs: List[Log]) -> "pyarrow.RecordBatch":
+    """
+    Converts parsed logs (e.g. from parse_logs) to a pyarrow RecordBatch, one row per
+    log, with topics as a list<binary> column
+    """
+    pa = _pyarrow()
+    return pa.RecordBatch.from_arrays(
+        [
+            pa.array([log.address for log in logs], type=pa.string()),
+            pa.array([log.topics for log in logs], type=pa.list_(pa.binary())),
+            pa.array([log.data for log in logs], type=pa.binary()),
+            pa.array([log.block_number for log in logs], type=pa.uint64()),
+            pa.array([log.block_hash for log in logs], type=pa.binary()),
+            pa.array([log.transaction_hash for log in logs], type=pa.binary()),
+            pa.array([log.transaction_index for log in logs], type=pa.uint32()),
+            pa.array([log.log_index for log in logs], type=pa.uint32()),
+            pa.array([log.removed for log in logs], type=pa.bool_()),
+        ],
+        names=[
+            "address",
+            "topics",
+            "data",
+            "block_number",
+            "block_hash",
+            "transaction_hash",
+            "transaction_index",
+            "log_index",
+            "removed",
+        ],
+    )
+
+
+def receipts_to_record_batch(
+    receipts: Union[List[Receipt], ReceiptColumns]
+) -> "pyarrow.RecordBatch":
+    """
+    Converts receipts to a pyarrow RecordBatch, one row per receipt. Receipts parsed
+    with parse_block_receipts are converted without copying the numeric columns,
+    a list of Receipt objects (from parse_receipts) is accepted as well
+    """
+    pa = _pyarrow()
+    if isinstance(receipts, ReceiptColumns):
+        columns = {
+            "transaction_hash": pa.array(receipts.transaction_hash, type=pa.binary()),
+            "transaction_index": pa.array(receipts.transaction_index),
+            "status": pa.array(receipts.status),
+            "gas_used": pa.array(receipts.gas_used),
+            "cumulative_gas_used": pa.array(receipts.cumulative_gas_used),
+            "effective_gas_price": pa.array(receipts.effective_gas_price),
+            "sender": pa.array(receipts.sender, type=pa.string()),
+            "to": pa.array(receipts.to, type=pa.string()),
+        }
+    else:
+        columns = {
+            "transaction_hash": pa.array(
+                [receipt.transaction_hash for receipt in receipts], type=pa.binary()
+            ),
+            "transaction_index": pa.array(
+                [receipt.transaction_index for receipt in receipts], type=pa.uint64()
+            ),
+            "status": pa.array(
+                [receipt.status for receipt in receipts], type=pa.uint8()
+            ),
+            "gas_used": pa.array(
+                [receipt.gas_used for receipt in receipts], type=pa.uint64()
+            ),
+            "cumulative_gas_used": pa.array(
+                [receipt.cumulative_gas_used for receipt in receipts],
+                type=pa.uint64(),
+            ),
+            "effective_gas_price": pa.array(
+                [receipt.effective_gas_price for receipt in receipts],
+                type=pa.uint64(),
+            ),
+            "sender": pa.array(
+                [receipt.sender for receipt in receipts], type=pa.string()
+            ),
+            "to": pa.array([receipt.to for receipt in receipts], type=pa.string()),
+        }
+    return pa.RecordBatch.from_arrays(list(columns.values()), names=list(columns))
+
+
+def candles_to_record_batch(candles: List[Candle]) -> "pyarrow.RecordBatch":
+    """
+    Converts candles from ohlcv_candles to a pyarrow RecordBatch
+    """
+    pa = _pyarrow()
+    return pa.RecordBatch.from_arrays(
+        [
+            pa.array([candle.start for candle in candles], type=pa.int64()),
+            pa.array([candle.open for candle in candles], type=pa.float64()),
+            pa.array([candle.high for candle in candles], type=pa.float64()),
+            pa.array([candle.low for candle in candles], type=pa.float64()),
+            pa.array([candle.close for candle in candles], type=pa.float64()),
+            _amounts(pa, (candle.volume0 for candle in candles)),
+            _amounts(pa, (candle.volume1 for candle in candles)),
+            pa.array([candle.swap_count for candle in candles], type=pa.uint32()),
+        ],
+        names=[
+            "start",
+            "open",
+            "high",
+            "low",
+            "close",
+            "volume0",
+            "volume1",
+            "swap_count",
+        ],
+    )
+
+
+def swaps_to_record_batch(swaps: List[SwapEvent]) -> "pyarrow.RecordBatch":
+    """
+    Converts swap events to a pyarrow RecordBatch, with the signed amounts as
+    Decimal256 columns
+    """
+    pa = _pyarrow()
+    return pa.RecordBatch.from_arrays(
+        [
+            pa.array([swap.pool for swap in swaps], type=pa.string()),
+            pa.array([swap.timestamp for swap in swaps], type=pa.int64()),
+            pa.array([swap.block_number for swap in swaps], type=pa.uint64()),
+            pa.array([swap.log_index for swap in swaps], type=pa.uint32()),
+            _amounts(pa, (swap.amount0 for swap in swaps)),
+            _amounts(pa, (swap.amount1 for swap in swaps)),
+        ],
+        names=["pool", "timestamp", "block_number", "log_index", "amount0", "amount1"],
+    )
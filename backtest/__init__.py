@@ -0,0 +1 @@
+from .backtest import *
\ No newline at end of file
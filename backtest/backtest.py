@@ -0,0 +1,114 @@
+from dataclasses import dataclass, field
+from typing import Callable, Dict, Iterable, List, Optional
+from ..pool_state import PoolEvent
+
+__all__ = ["BacktestTrade", "BacktestContext", "BacktestResult", "Backtest"]
+
+
+@dataclass
+class BacktestTrade:
+    """
+    A trade the strategy would have executed. `profit` is denominated in the
+    strategy's accounting token (usually WETH), gas is paid at `gas_price`
+    """
+
+    block_number: int
+    profit: int
+    gas_used: int
+    gas_price: int
+    description: str = ""
+
+    @property
+    def gas_cost(self) -> int:
+        return self.gas_used * self.gas_price
+
+    @property
+    def net_profit(self) -> int:
+        return self.profit - self.gas_cost
+
+
+@dataclass
+class BacktestContext:
+    """
+    Passed to the strategy at the end of each block. The pools reflect every event up
+    to and including `block_number`, and must not be modified by the strategy (use
+    pool.copy() to simulate trades)
+    """
+
+    block_number: int
+    pools: Dict[str, object]
+    events: List[PoolEvent]
+
+
+@dataclass
+class BacktestResult:
+    blocks_processed: int = 0
+    events_processed: int = 0
+    trades: List[BacktestTrade] = field(default_factory=list)
+
+    @property
+    def gross_profit(self) -> int:
+        return sum(trade.profit for trade in self.trades)
+
+    @property
+    def gas_cost(self) -> int:
+        return sum(trade.gas_cost for trade in self.trades)
+
+    @property
+    def net_profit(self) -> int:
+        return self.gross_profit - self.gas_cost
+
+
+class Backtest:
+    """
+    Replays an ordered stream of pool events (V2 Sync, V3 Swap/Mint/Burn) through
+    offline pool states, calling `strategy` at every block boundary. The strategy
+    returns the trades it would have made in that block (or None), which are
+    accumulated into the simulated PnL. Events for pools that are not tracked are
+    skipped
+    """
+
+    def __init__(
+        self,
+        pools: Iterable,
+        strategy: Callable[[BacktestContext], Optional[List[BacktestTrade]]],
+    ) -> None:
+        self.pools = {pool.address: pool for pool in pools}
+        self.strategy = strategy
+
+    def _end_block(
+        self, block_number: int, events: List[PoolEvent], result: BacktestResult
+    ) -> None:
+        trades = self.strategy(BacktestContext(block_number, self.pools, events))
+        if trades:
+            result.trades.extend(trades)
+        result.blocks_processed += 1
+
+    def run(self, events: Iterable[PoolEvent]) -> BacktestResult:
+        result = BacktestResult()
+        block_number = None
+        block_events: List[PoolEvent] = []
+        last_position = None
+
+        for event in events:
+            position = (event.block_number, event.log_index)
+            assert (
+                last_position is None or position > last_position
+            ), f"Events out of order: {position} after {last_position}"
+            last_position = position
+
+            if block_number is not None and event.block_number != block_number:
+                self._end_block(block_number, block_events, result)
+                block_events = []
+            block_number = event.block_number
+
+            pool = self.pools.get(event.pool)
+            if pool is None:
+                continue
+            pool.apply_event(event)
+            block_events.append(event)
+            result.events_processed += 1
+
+        if block_number is not None:
+            self._end_block(block_number, block_events, result)
+        return result
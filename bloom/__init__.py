@@ -0,0 +1 @@
+from .bloom import *
\ No newline at end of file
@@ -0,0 +1,73 @@
+from typing import Iterable, Optional, Union
+from eth_utils import keccak
+
+__all__ = ["BLOOM_BYTE_LENGTH", "build_bloom", "bloom_contains", "bloom_matches"]
+
+BLOOM_BYTE_LENGTH = 256
+
+
+def _to_bytes(value: Union[bytes, str]) -> bytes:
+    if isinstance(value, str):
+        return bytes.fromhex(value[2:] if value.startswith("0x") else value)
+    return bytes(value)
+
+
+def _bloom_bits(entry: Union[bytes, str]) -> int:
+    """
+    Returns the 3 bits set by an entry, as a 2048-bit int mask
+    """
+    entry_hash = keccak(_to_bytes(entry))
+    mask = 0
+    for i in (0, 2, 4):
+        mask |= 1 << (((entry_hash[i] << 8) | entry_hash[i + 1]) & 2047)
+    return mask
+
+
+def build_bloom(entries: Iterable[Union[bytes, str]]) -> bytes:
+    """
+    Builds a 256-byte logs bloom containing all entries (log addresses and topics)
+    """
+    bloom = 0
+    for entry in entries:
+        bloom |= _bloom_bits(entry)
+    return bloom.to_bytes(BLOOM_BYTE_LENGTH, "big")
+
+
+def bloom_contains(logs_bloom: Union[bytes, str], entry: Union[bytes, str]) -> bool:
+    """
+    Checks if an address or topic may be present in a logs bloom.
+    Blooms have false positives but never false negatives
+    """
+    mask = _bloom_bits(entry)
+    return int.from_bytes(_to_bytes(logs_bloom), "big") & mask == mask
+
+
+def bloom_matches(
+    logs_bloom: Union[bytes, str],
+    addresses: Optional[Iterable[Union[bytes, str]]] = None,
+    topics: Optional[Iterable[Union[bytes, str]]] = None,
+) -> bool:
+    """
+    Checks a block's logs bloom against a set of watched addresses and topics, in the
+    same way an eth_getLogs filter would be applied: the bloom matches if any of the
+    addresses AND any of the topics may be present. An empty or missing list places
+    no restriction.
+
+    A False result proves that no log in the block matches, so the eth_getLogs call
+    can be skipped
+    """
+
+    bloom = int.from_bytes(_to_bytes(logs_bloom), "big")
+
+    def _any_present(entries) -> bool:
+        for entry in entries:
+            mask = _bloom_bits(entry)
+            if bloom & mask == mask:
+                return True
+        return False
+
+    if addresses and not _any_present(addresses):
+        return False
+    if topics and not _any_present(topics):
+        return False
+    return True
@@ -0,0 +1,107 @@
+import functools
+import threading
+from typing import Any, Callable, Dict, List, Optional
+
+__all__ = [
+    "DEFAULT_CACHE_SIZE",
+    "cached_function",
+    "configure_caches",
+    "clear_caches",
+    "caches_info",
+]
+
+DEFAULT_CACHE_SIZE = 16_384
+
+_cached_functions: List["_CachedFunction"] = []
+_enabled = False
+_maxsize = DEFAULT_CACHE_SIZE
+# guards the settings above and the swap of each function's lru_cache, so that
+# concurrent reconfiguration never leaves functions cached with mixed settings
+_lock = threading.Lock()
+
+
+class _CachedFunction:
+    """
+    Wraps a pure function with an LRU cache that can be enabled, resized and cleared
+    at runtime for the whole process. Calls with unhashable arguments bypass it
+    """
+
+    def __init__(self, function: Callable) -> None:
+        functools.update_wrapper(self, function)
+        self._function = function
+        self._cached = None
+        self._configure()
+
+    def _configure(self) -> None:
+        # called with _lock held
+        self._cached = (
+            functools.lru_cache(maxsize=_maxsize)(self._function) if _enabled else None
+        )
+
+    def __call__(self, *args: Any, **kwargs: Any) -> Any:
+        # read once: another thread may swap the cache during the call
+        cached = self._cached
+        if cached is None:
+            return self._function(*args, **kwargs)
+        try:
+            hash((args, tuple(kwargs.values())))
+        except TypeError:
+            # unhashable arguments, e.g. a bytearray
+            return self._function(*args, **kwargs)
+        # a TypeError raised by the function itself propagates without a second call
+        return cached(*args, **kwargs)
+
+    def cache_info(self) -> Optional["functools._CacheInfo"]:
+        cached = self._cached
+        return None if cached is None else cached.cache_info()
+
+    def cache_clear(self) -> None:
+        cached = self._cached
+        if cached is not None:
+            cached.cache_clear()
+
+
+def cached_function(function: Callable) -> Callable:
+    """
+    Decorator registering a pure function with the process-wide caches
+    """
+    with _lock:
+        wrapper = _CachedFunction(function)
+        _cached_functions.append(wrapper)
+    return wrapper
+
+
+def configure_caches(enabled: bool = True, maxsize: int = DEFAULT_CACHE_SIZE) -> None:
+    """
+    Enables (or disables) memoization of tick math, CREATE2 address and selector
+    computations, keeping up to `maxsize` results per function. Caches are disabled
+    by default. Reconfiguring discards everything cached so far. Safe to call from
+    any thread, while cached functions are in use
+    """
+    global _enabled, _maxsize
+    assert maxsize is None or maxsize > 0, "Cache size must be positive"
+    with _lock:
+        _enabled = enabled
+        _maxsize = maxsize
+        for function in _cached_functions:
+            function._configure()
+
+
+def clear_caches() -> None:
+    with _lock:
+        functions = list(_cached_functions)
+    for function in functions:
+        function.cache_clear()
+
+
+def caches_info() -> Dict[str, object]:
+    """
+    Returns the hit/miss statistics of each cached function, keyed by its qualified
+    name (None for every function while caching is disabled)
+    """
+    with _lock:
+        functions = list(_cached_functions)
+    return {
+        f"{function.__module__}.{function.__qualname__}": function.cache_info()
+        for function in functions
+    }
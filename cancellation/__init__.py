@@ -0,0 +1 @@
+from .cancellation import *
\ No newline at end of file
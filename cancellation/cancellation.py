@@ -0,0 +1,59 @@
+import threading
+from typing import Callable, Optional
+from ..exceptions import OperationCancelled
+
+__all__ = ["CancellationToken", "ProgressReporter"]
+
+
+class CancellationToken:
+    """
+    Thread-safe cancellation flag shared between the caller of a long-running operation
+    and the operation itself. The operation polls the token at regular intervals, so
+    cancelling from another thread, a signal handler or an asyncio task takes effect
+    promptly instead of waiting for the operation to finish
+    """
+
+    def __init__(self) -> None:
+        self._event = threading.Event()
+
+    def cancel(self) -> None:
+        self._event.set()
+
+    @property
+    def cancelled(self) -> bool:
+        return self._event.is_set()
+
+    def raise_if_cancelled(self) -> None:
+        if self._event.is_set():
+            raise OperationCancelled
+
+    def wait(self, timeout: Optional[float] = None) -> bool:
+        """
+        Blocks until the token is cancelled or the timeout expires, returning True if
+        the token was cancelled
+        """
+        return self._event.wait(timeout)
+
+
+class ProgressReporter:
+    """
+    Invokes `callback` with the running total of completed work items, at most once
+    every `interval` items
+    """
+
+    def __init__(
+        self,
+        callback: Optional[Callable[[int], None]] = None,
+        interval: int = 1,
+    ) -> None:
+        assert interval >= 1, "Progress interval must be at least 1"
+        self.callback = callback
+        self.interval = interval
+        self.completed = 0
+        self._next_report = interval
+
+    def advance(self, count: int = 1) -> None:
+        self.completed += count
+        if self.callback is not None and self.completed >= self._next_report:
+            self.callback(self.completed)
+            self._next_report = self.completed + self.interval
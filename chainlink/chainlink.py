@@ -1,4 +1,69 @@
+from dataclasses import dataclass
+from typing import Union
 import brownie
+from ..abi import abi_decode, function_selector
+
+LATEST_ROUND_DATA_SELECTOR = function_selector("latestRoundData()")
+
+
+@dataclass
+class ChainlinkRoundData:
+    round_id: int
+    answer: int
+    started_at: int
+    updated_at: int
+    answered_in_round: int
+
+
+def decode_latest_round_data(data: Union[bytes, str]) -> ChainlinkRoundData:
+    """
+    Decodes the return data of an aggregator's latestRoundData (or getRoundData) call
+    """
+    if isinstance(data, str):
+        data = bytes.fromhex(data[2:] if data.startswith("0x") else data)
+    return ChainlinkRoundData(
+        *abi_decode(["uint80", "int256", "uint256", "uint256", "uint80"], data)
+    )
+
+
+def normalize_chainlink_answer(
+    answer: int,
+    feed_decimals: int,
+    target_decimals: int = 18,
+) -> int:
+    """
+    Rescales a feed answer from the feed's decimals to `target_decimals`, so answers
+    from feeds with different precision (e.g. 8-decimal USD feeds and 18-decimal ETH
+    feeds) can be compared directly. Reducing precision truncates toward zero
+    """
+    if target_decimals >= feed_decimals:
+        return answer * 10 ** (target_decimals - feed_decimals)
+    scale = 10 ** (feed_decimals - target_decimals)
+    return -(-answer // scale) if answer < 0 else answer // scale
+
+
+def chainlink_round_age(round_data: ChainlinkRoundData, timestamp: int) -> int:
+    """
+    Returns the number of seconds between the round's last update and `timestamp`
+    """
+    return max(0, timestamp - round_data.updated_at)
+
+
+def is_chainlink_round_stale(
+    round_data: ChainlinkRoundData,
+    timestamp: int,
+    max_age: int,
+) -> bool:
+    """
+    Checks whether a round should be rejected: it was last updated more than `max_age`
+    seconds before `timestamp` (usually the feed's heartbeat plus some margin), it
+    was never completed (updatedAt of zero), or its answer is not positive
+    """
+    return (
+        round_data.updated_at == 0
+        or round_data.answer <= 0
+        or chainlink_round_age(round_data, timestamp) > max_age
+    )
 
 
 class ChainlinkPriceContract:
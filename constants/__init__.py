@@ -0,0 +1 @@
+from .constants import *
\ No newline at end of file
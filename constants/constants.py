@@ -0,0 +1,65 @@
+from enum import IntEnum
+
+__all__ = [
+    "MAX_UINT128",
+    "MAX_UINT160",
+    "MAX_UINT256",
+    "Q96",
+    "Q128",
+    "BPS_DENOMINATOR",
+    "MIN_TICK",
+    "MAX_TICK",
+    "MIN_SQRT_RATIO",
+    "MAX_SQRT_RATIO",
+    "FeeTier",
+    "TICK_SPACINGS",
+    "PANCAKESWAP_V3_TICK_SPACINGS",
+    "FEE_TIERS",
+]
+
+# Constants shared across the protocol ports. Every module imports them from here
+# rather than defining its own copy
+MAX_UINT128 = 2 ** 128 - 1
+MAX_UINT160 = 2 ** 160 - 1
+MAX_UINT256 = 2 ** 256 - 1
+
+# fixed-point resolutions of sqrt prices (Q64.96) and fee growth (Q128.128)
+Q96 = 2 ** 96
+Q128 = 2 ** 128
+
+BPS_DENOMINATOR = 10_000
+
+# bounds of Uniswap V3 TickMath
+MIN_TICK = -887272
+MAX_TICK = 887272
+MIN_SQRT_RATIO = 4295128739
+MAX_SQRT_RATIO = 1461446703485210103287273052203988822378723970342
+
+
+class FeeTier(IntEnum):
+    """
+    Uniswap V3 fee tiers, in hundredths of a bip (the `fee` value of a pool)
+    """
+
+    LOWEST = 100
+    LOW = 500
+    MEDIUM = 3000
+    HIGH = 10000
+
+    @property
+    def tick_spacing(self) -> int:
+        return TICK_SPACINGS[self]
+
+
+# tick spacing enabled for each fee tier by the Uniswap V3 factory
+TICK_SPACINGS = {
+    FeeTier.LOWEST: 1,
+    FeeTier.LOW: 10,
+    FeeTier.MEDIUM: 60,
+    FeeTier.HIGH: 200,
+}
+
+# PancakeSwap V3 replaces the 0.3% tier with 0.25%
+PANCAKESWAP_V3_TICK_SPACINGS = {100: 1, 500: 10, 2500: 50, 10000: 200}
+
+FEE_TIERS = tuple(FeeTier)
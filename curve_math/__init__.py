@@ -0,0 +1,2 @@
+from .crypto import *
+from .ema import *
\ No newline at end of file
@@ -0,0 +1,260 @@
+from typing import List, Sequence
+
+__all__ = [
+    "A_MULTIPLIER",
+    "CURVE_FEE_DENOMINATOR",
+    "CURVE_MAX_ITERATIONS",
+    "geometric_mean",
+    "reduction_coefficient",
+    "dynamic_fee",
+    "newton_d",
+    "newton_y",
+    "halfpow",
+    "sqrt_int",
+    "crypto_xp",
+]
+
+# Port of the Curve CryptoSwap math (CurveCryptoMath3, used by tricrypto pools).
+# `ann` is A * N**N scaled by A_MULTIPLIER, as returned by the pool's A()
+A_MULTIPLIER = 10_000
+# fees of every Curve pool are in units of 1e-10
+CURVE_FEE_DENOMINATOR = 10 ** 10
+# iteration limit of the pools' Newton's method loops
+CURVE_MAX_ITERATIONS = 255
+
+_MIN_GAMMA = 10 ** 10
+_MAX_GAMMA = 5 * 10 ** 16
+_EXP_PRECISION = 10 ** 10
+
+
+def _check_a_gamma(ann: int, gamma: int, n_coins: int) -> None:
+    min_a = n_coins ** n_coins * A_MULTIPLIER // 100
+    max_a = n_coins ** n_coins * A_MULTIPLIER * 1000
+    assert min_a <= ann <= max_a, f"Unsafe value of A: {ann}"
+    assert _MIN_GAMMA <= gamma <= _MAX_GAMMA, f"Unsafe value of gamma: {gamma}"
+
+
+def geometric_mean(x: Sequence[int], sort: bool = True) -> int:
+    """
+    Geometric mean of the balances in 1e18 precision, found by Newton's method
+    """
+    n_coins = len(x)
+    x = sorted(x, reverse=True) if sort else list(x)
+    d = x[0]
+    for _ in range(CURVE_MAX_ITERATIONS):
+        d_prev = d
+        tmp = 10 ** 18
+        for x_i in x:
+            tmp = tmp * x_i // d
+        d = d * ((n_coins - 1) * 10 ** 18 + tmp) // (n_coins * 10 ** 18)
+        diff = abs(d - d_prev)
+        if diff <= 1 or diff * 10 ** 18 < d:
+            return d
+    raise ArithmeticError("Geometric mean did not converge")
+
+
+def reduction_coefficient(x: Sequence[int], fee_gamma: int) -> int:
+    """
+    fee_gamma / (fee_gamma + 1 - K), with K = prod(x) / (sum(x) / N)**N, the weight
+    of the mid fee in the dynamic fee of balances `x`
+    """
+    n_coins = len(x)
+    k = 10 ** 18
+    total = sum(x)
+    for x_i in x:
+        k = k * n_coins * x_i // total
+    if fee_gamma > 0:
+        k = fee_gamma * 10 ** 18 // (fee_gamma + 10 ** 18 - k)
+    return k
+
+
+def dynamic_fee(xp: Sequence[int], mid_fee: int, out_fee: int, fee_gamma: int) -> int:
+    """
+    The fee charged at price-scaled balances `xp`, moving from `mid_fee` at the
+    balanced point towards `out_fee` as the pool gets imbalanced (in 1e10 units)
+    """
+    f = reduction_coefficient(xp, fee_gamma)
+    return (mid_fee * f + out_fee * (10 ** 18 - f)) // 10 ** 18
+
+
+def newton_d(ann: int, gamma: int, x_unsorted: Sequence[int]) -> int:
+    """
+    Finds the invariant D of price-scaled balances by Newton's method, exactly as
+    newton_D
+    """
+    n_coins = len(x_unsorted)
+    _check_a_gamma(ann, gamma, n_coins)
+
+    x = sorted(x_unsorted, reverse=True)
+    assert 10 ** 9 <= x[0] <= 10 ** 33, f"Unsafe value of x[0]: {x[0]}"
+    for x_i in x[1:]:
+        assert x_i * 10 ** 18 // x[0] >= 10 ** 11, f"Unsafe value of x[i]: {x_i}"
+
+    # start from the invariant of the constant product curve
+    d = n_coins * geometric_mean(x, False)
+    total = sum(x)
+
+    for _ in range(CURVE_MAX_ITERATIONS):
+        d_prev = d
+
+        k0 = 10 ** 18
+        for x_i in x:
+            k0 = k0 * x_i * n_coins // d
+
+        g1k0 = gamma + 10 ** 18
+        g1k0 = g1k0 - k0 + 1 if g1k0 > k0 else k0 - g1k0 + 1
+
+        # D / (A * N**N) * g1k0**2 / gamma**2
+        mul1 = 10 ** 18 * d // gamma * g1k0 // gamma * g1k0 * A_MULTIPLIER // ann
+        # 2 * N * K0 / g1k0
+        mul2 = 2 * 10 ** 18 * n_coins * k0 // g1k0
+
+        neg_fprime = (
+            (total + total * mul2 // 10 ** 18)
+            + mul1 * n_coins // k0
+            - mul2 * d // 10 ** 18
+        )
+
+        d_plus = d * (neg_fprime + total) // neg_fprime
+        d_minus = d * d // neg_fprime
+        if 10 ** 18 > k0:
+            d_minus += d * (mul1 // neg_fprime) // 10 ** 18 * (10 ** 18 - k0) // k0
+        else:
+            d_minus -= d * (mul1 // neg_fprime) // 10 ** 18 * (k0 - 10 ** 18) // k0
+
+        d = d_plus - d_minus if d_plus > d_minus else (d_minus - d_plus) // 2
+
+        if abs(d - d_prev) * 10 ** 14 < max(10 ** 16, d):
+            # the balances must also be safe for the next newton_y
+            for x_i in x:
+                frac = x_i * 10 ** 18 // d
+                assert 10 ** 16 <= frac <= 10 ** 20, f"Unsafe value of x[i]: {x_i}"
+            return d
+
+    raise ArithmeticError("Curve crypto invariant D did not converge")
+
+
+def newton_y(ann: int, gamma: int, x: Sequence[int], d: int, i: int) -> int:
+    """
+    Finds the price-scaled balance of coin `i` which keeps the invariant at `d`, given
+    the other balances, exactly as newton_y
+    """
+    n_coins = len(x)
+    _check_a_gamma(ann, gamma, n_coins)
+    assert 10 ** 17 <= d <= 10 ** 33, f"Unsafe value of D: {d}"
+    for k, x_k in enumerate(x):
+        if k != i:
+            frac = x_k * 10 ** 18 // d
+            assert 10 ** 16 <= frac <= 10 ** 20, f"Unsafe value of x[{k}]: {x_k}"
+
+    y = d // n_coins
+    k0_i = 10 ** 18
+    total_i = 0
+
+    x_sorted = list(x)
+    x_sorted[i] = 0
+    x_sorted = sorted(x_sorted, reverse=True)
+
+    convergence_limit = max(x_sorted[0] // 10 ** 14, d // 10 ** 14, 100)
+    for j in range(2, n_coins + 1):
+        # small balances first
+        x_j = x_sorted[n_coins - j]
+        y = y * d // (x_j * n_coins)
+        total_i += x_j
+    for j in range(n_coins - 1):
+        # large balances first
+        k0_i = k0_i * x_sorted[j] * n_coins // d
+
+    for _ in range(CURVE_MAX_ITERATIONS):
+        y_prev = y
+
+        k0 = k0_i * y * n_coins // d
+        total = total_i + y
+
+        g1k0 = gamma + 10 ** 18
+        g1k0 = g1k0 - k0 + 1 if g1k0 > k0 else k0 - g1k0 + 1
+
+        # D / (A * N**N) * g1k0**2 / gamma**2
+        mul1 = 10 ** 18 * d // gamma * g1k0 // gamma * g1k0 * A_MULTIPLIER // ann
+        # 1 + 2 * K0 / g1k0
+        mul2 = 10 ** 18 + 2 * 10 ** 18 * k0 // g1k0
+
+        yfprime = 10 ** 18 * y + total * mul2 + mul1
+        dyfprime = d * mul2
+        if yfprime < dyfprime:
+            y = y_prev // 2
+            continue
+        yfprime -= dyfprime
+        fprime = yfprime // y
+
+        y_minus = mul1 // fprime
+        y_plus = (yfprime + 10 ** 18 * d) // fprime + y_minus * 10 ** 18 // k0
+        y_minus += 10 ** 18 * total // fprime
+
+        y = y_prev // 2 if y_plus < y_minus else y_plus - y_minus
+
+        if abs(y - y_prev) < max(convergence_limit, y // 10 ** 14):
+            frac = y * 10 ** 18 // d
+            assert 10 ** 16 <= frac <= 10 ** 20, f"Unsafe value of y: {y}"
+            return y
+
+    raise ArithmeticError("Curve crypto invariant y did not converge")
+
+
+def halfpow(power: int) -> int:
+    """
+    1e18 * 0.5 ** (power / 1e18), the decay of the pool's moving average price
+    """
+    intpow = power // 10 ** 18
+    otherpow = power - intpow * 10 ** 18
+    if intpow > 59:
+        return 0
+    result = 10 ** 18 // 2 ** intpow
+    if otherpow == 0:
+        return result
+
+    term = 10 ** 18
+    x = 5 * 10 ** 17
+    total = 10 ** 18
+    neg = False
+    for i in range(1, 256):
+        k = i * 10 ** 18
+        c = k - 10 ** 18
+        if otherpow > c:
+            c = otherpow - c
+            neg = not neg
+        else:
+            c -= otherpow
+        term = term * (c * x // 10 ** 18) // k
+        total = total - term if neg else total + term
+        if term < _EXP_PRECISION:
+            return result * total // 10 ** 18
+    raise ArithmeticError("halfpow did not converge")
+
+
+def sqrt_int(x: int) -> int:
+    """
+    Square root of a 1e18 fixed-point number, as the pool computes it
+    """
+    if x == 0:
+        return 0
+    z = (x + 10 ** 18) // 2
+    y = x
+    for _ in range(256):
+        if z == y:
+            return y
+        y = z
+        z = (x * 10 ** 18 // z + z) // 2
+    raise ArithmeticError("sqrt_int did not converge")
+
+
+def crypto_xp(
+    balances: Sequence[int], precisions: Sequence[int], price_scale: Sequence[int]
+) -> List[int]:
+    """
+    Scales raw balances to 18 decimals and to the value of coin 0 at `price_scale`
+    """
+    xp = [balances[0] * precisions[0]]
+    for k in range(1, len(balances)):
+        xp.append(balances[k] * precisions[k] * price_scale[k - 1] // 10 ** 18)
+    return xp
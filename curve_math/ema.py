@@ -0,0 +1,64 @@
+__all__ = ["wad_exp", "ema_price"]
+
+
+def _div(a: int, b: int) -> int:
+    # signed division truncating towards zero, as the EVM's SDIV
+    q = abs(a) // abs(b)
+    return q if (a < 0) == (b < 0) else -q
+
+
+def wad_exp(x: int) -> int:
+    """
+    e ** (x / 1e18) in 1e18 precision, bit-exact with the wad_exp (Solady expWad)
+    used by newer Curve pools
+    """
+    if x <= -41446531673892822313:
+        return 0
+    if x >= 135305999368893231589:
+        raise OverflowError("wad_exp overflow")
+
+    # convert to a 2**96 base, then factor out powers of two so that
+    # exp(x) = exp(x') * 2**k with x' in (-ln 2 / 2, ln 2 / 2)
+    x = _div(x << 78, 5 ** 18)
+    k = (_div(x << 96, 54916777467707473351141471128) + 2 ** 95) >> 96
+    x -= k * 54916777467707473351141471128
+
+    # (6, 7)-term rational approximation
+    y = x + 1346386616545796478920950773328
+    y = ((y * x) >> 96) + 57155421227552351082224309758442
+    p = y + x - 94201549194550492254356042504812
+    p = ((p * y) >> 96) + 28719021644029726153956944680412240
+    p = p * x + (4385272521454847904659076985693276 << 96)
+    q = x - 2855989394907223263936484059900
+    q = ((q * x) >> 96) + 50020603652535783019961831881945
+    q = ((q * x) >> 96) - 533845033583426703283633433725380
+    q = ((q * x) >> 96) + 3604857256930695427073651918091429
+    q = ((q * x) >> 96) - 14423608567350463180887372962807573
+    q = ((q * x) >> 96) + 26449188498355588339934803723976023
+    r = _div(p, q)
+
+    # scale by ~6.031367120, 2**k and 1e18 / 2**96 at once
+    return (r % 2 ** 256) * 3822833074963236453042738258902158003155416615667 >> (
+        195 - k
+    )
+
+
+def ema_price(
+    last_price: int,
+    last_timestamp: int,
+    current: int,
+    ma_time: int,
+    now: int,
+) -> int:
+    """
+    The exponential moving average price oracle of stableswap-ng and the -ng crypto
+    pools at timestamp `now`: the stored average `current`, last updated at
+    `last_timestamp`, moved towards the last traded price `last_price` with weight
+    1 - exp(-elapsed / ma_time). Prices are in 1e18 precision. Pools cap the last
+    price before storing it (e.g. at twice the price scale), so `last_price` should be
+    the stored value
+    """
+    if now <= last_timestamp:
+        return current
+    alpha = wad_exp(-((now - last_timestamp) * 10 ** 18 // ma_time))
+    return (last_price * (10 ** 18 - alpha) + current * alpha) // 10 ** 18
@@ -0,0 +1,2 @@
+from .erc20 import *
+from .metadata import *
\ No newline at end of file
@@ -0,0 +1,84 @@
+from typing import Union
+from ..abi import abi_decode, abi_encode, function_selector
+from ..constants import MAX_UINT256
+from ..exceptions import AbiError
+
+__all__ = [
+    "TRANSFER_SELECTOR",
+    "APPROVE_SELECTOR",
+    "TRANSFER_FROM_SELECTOR",
+    "BALANCE_OF_SELECTOR",
+    "ALLOWANCE_SELECTOR",
+    "erc20_transfer_calldata",
+    "erc20_approve_calldata",
+    "erc20_transfer_from_calldata",
+    "erc20_balance_of_calldata",
+    "erc20_allowance_calldata",
+    "decode_erc20_success",
+    "decode_erc20_uint",
+]
+
+TRANSFER_SELECTOR = function_selector("transfer(address,uint256)")
+APPROVE_SELECTOR = function_selector("approve(address,uint256)")
+TRANSFER_FROM_SELECTOR = function_selector("transferFrom(address,address,uint256)")
+BALANCE_OF_SELECTOR = function_selector("balanceOf(address)")
+ALLOWANCE_SELECTOR = function_selector("allowance(address,address)")
+
+
+def _check_amount(amount: int) -> None:
+    assert 0 <= amount <= MAX_UINT256, f"Amount {amount} is outside the uint256 range"
+
+
+def erc20_transfer_calldata(to: str, amount: int) -> bytes:
+    _check_amount(amount)
+    return TRANSFER_SELECTOR + abi_encode(["address", "uint256"], [to, amount])
+
+
+def erc20_approve_calldata(spender: str, amount: int = MAX_UINT256) -> bytes:
+    _check_amount(amount)
+    return APPROVE_SELECTOR + abi_encode(["address", "uint256"], [spender, amount])
+
+
+def erc20_transfer_from_calldata(sender: str, to: str, amount: int) -> bytes:
+    _check_amount(amount)
+    return TRANSFER_FROM_SELECTOR + abi_encode(
+        ["address", "address", "uint256"], [sender, to, amount]
+    )
+
+
+def erc20_balance_of_calldata(holder: str) -> bytes:
+    return BALANCE_OF_SELECTOR + abi_encode(["address"], [holder])
+
+
+def erc20_allowance_calldata(owner: str, spender: str) -> bytes:
+    return ALLOWANCE_SELECTOR + abi_encode(["address", "address"], [owner, spender])
+
+
+def _to_bytes(data: Union[bytes, str]) -> bytes:
+    if isinstance(data, str):
+        return bytes.fromhex(data[2:] if data.startswith("0x") else data)
+    return bytes(data)
+
+
+def decode_erc20_success(data: Union[bytes, str]) -> bool:
+    """
+    Interprets the return data of transfer, transferFrom or approve the same way as
+    OpenZeppelin's SafeERC20: empty return data (USDT, BNB and other tokens predating
+    the final standard) counts as success, otherwise the first word must be non-zero.
+    Tokens returning a non-bool word (e.g. the transferred amount) are accepted when
+    the value is non-zero
+    """
+    data = _to_bytes(data)
+    if not data:
+        return True
+    if len(data) < 32:
+        raise AbiError(f"Return data is too short for a bool: 0x{data.hex()}")
+    return int.from_bytes(data[:32], "big") != 0
+
+
+def decode_erc20_uint(data: Union[bytes, str]) -> int:
+    """
+    Decodes the uint256 returned by balanceOf, allowance or totalSupply
+    """
+    (value,) = abi_decode(["uint256"], _to_bytes(data))
+    return value
@@ -0,0 +1,127 @@
+from dataclasses import dataclass
+from typing import List, Optional, Sequence, Tuple
+from ..abi import abi_decode, abi_encode, function_selector
+from ..exceptions import AbiError
+
+__all__ = [
+    "MULTICALL3_ADDRESS",
+    "AGGREGATE3_SELECTOR",
+    "NAME_SELECTOR",
+    "SYMBOL_SELECTOR",
+    "DECIMALS_SELECTOR",
+    "TOTAL_SUPPLY_SELECTOR",
+    "TokenMetadata",
+    "encode_metadata_calls",
+    "decode_erc20_string",
+    "decode_metadata_results",
+]
+
+# deployed at the same address on most chains
+MULTICALL3_ADDRESS = "0xcA11bde05977b3631167028862bE2a173976CA11"
+AGGREGATE3_SELECTOR = function_selector("aggregate3((address,bool,bytes)[])")
+
+NAME_SELECTOR = function_selector("name()")
+SYMBOL_SELECTOR = function_selector("symbol()")
+DECIMALS_SELECTOR = function_selector("decimals()")
+TOTAL_SUPPLY_SELECTOR = function_selector("totalSupply()")
+
+_METADATA_SELECTORS = (
+    NAME_SELECTOR,
+    SYMBOL_SELECTOR,
+    DECIMALS_SELECTOR,
+    TOTAL_SUPPLY_SELECTOR,
+)
+
+
+@dataclass
+class TokenMetadata:
+    # fields are None where the call reverted or returned undecodable data
+    address: str
+    name: Optional[str]
+    symbol: Optional[str]
+    decimals: Optional[int]
+    total_supply: Optional[int]
+
+
+def encode_metadata_calls(
+    addresses: Sequence[str],
+    tokens_per_call: int = 250,
+) -> List[bytes]:
+    """
+    Builds Multicall3 aggregate3 calldata querying name, symbol, decimals and
+    totalSupply of each token, in chunks of `tokens_per_call` tokens to keep each
+    eth_call within gas and response size limits. Every call is allowed to fail, so a
+    broken token does not revert the whole batch
+    """
+    assert tokens_per_call > 0, "Expected at least one token per call"
+    payloads = []
+    for start in range(0, len(addresses), tokens_per_call):
+        calls = [
+            (address, True, selector)
+            for address in addresses[start : start + tokens_per_call]
+            for selector in _METADATA_SELECTORS
+        ]
+        payloads.append(
+            AGGREGATE3_SELECTOR + abi_encode(["(address,bool,bytes)[]"], [calls])
+        )
+    return payloads
+
+
+def decode_erc20_string(data: bytes) -> Optional[str]:
+    """
+    Decodes the result of name() or symbol(), accepting both ABI strings and the
+    bytes32 values returned by early tokens such as MKR. Returns None for empty or
+    undecodable data
+    """
+    if len(data) == 32:
+        # a bytes32 value, padded with zero bytes on the right
+        return data.rstrip(b"\x00").decode("utf-8", errors="replace")
+    try:
+        (value,) = abi_decode(["string"], data)
+    except AbiError:
+        return None
+    # some tokens pad their ABI-encoded string with zero bytes
+    return value.rstrip("\x00")
+
+
+def _decode_uint(data: bytes) -> Optional[int]:
+    if len(data) < 32:
+        return None
+    return int.from_bytes(data[:32], "big")
+
+
+def decode_metadata_results(
+    addresses: Sequence[str],
+    results: Sequence[bytes],
+) -> List[TokenMetadata]:
+    """
+    Decodes the return data of the eth_calls built by encode_metadata_calls (one per
+    payload, in order) into the metadata of each token. Decimals that do not fit a
+    uint8 are treated as undecodable
+    """
+    outcomes: List[Tuple[bool, bytes]] = []
+    for data in results:
+        (call_results,) = abi_decode(["(bool,bytes)[]"], data)
+        outcomes.extend(call_results)
+    assert len(outcomes) == 4 * len(addresses), (
+        f"Expected {4 * len(addresses)} call results for {len(addresses)} tokens, "
+        f"found {len(outcomes)}"
+    )
+
+    tokens = []
+    for i, address in enumerate(addresses):
+        name, symbol, decimals, total_supply = (
+            bytes(data) if success else b""
+            for success, data in outcomes[4 * i : 4 * i + 4]
+        )
+        decimals = _decode_uint(decimals)
+        tokens.append(
+            TokenMetadata(
+                address=address,
+                name=decode_erc20_string(name) if name else None,
+                symbol=decode_erc20_string(symbol) if symbol else None,
+                decimals=decimals if decimals is not None and decimals < 256 else None,
+                total_supply=_decode_uint(total_supply),
+            )
+        )
+    return tokens
@@ -0,0 +1 @@
+from .exceptions import *
\ No newline at end of file
@@ -0,0 +1,85 @@
+from typing import Any
+
+__all__ = [
+    "DegenbotError",
+    "AbiError",
+    "RlpError",
+    "ProofVerificationError",
+    "TickMathError",
+    "SwapSimulationError",
+    "EvmRevertError",
+    "RpcResponseError",
+    "OperationCancelled",
+    "IntegerOverflowError",
+]
+
+
+class DegenbotError(Exception):
+    """
+    Base exception for all errors raised by degenbot, so callers can catch everything
+    raised by the library with a single except clause
+    """
+
+
+class AbiError(DegenbotError, ValueError):
+    """
+    ABI-encoded data could not be encoded or decoded
+    """
+
+
+class RlpError(DegenbotError, ValueError):
+    """
+    An RLP item could not be encoded, or the input is not canonical RLP
+    """
+
+
+class ProofVerificationError(DegenbotError, ValueError):
+    """
+    A Merkle-Patricia proof is malformed or does not match its claimed root or values
+    """
+
+
+class TickMathError(DegenbotError, ValueError):
+    """
+    A tick or sqrt price is outside of the range supported by the tick math
+    """
+
+
+class SwapSimulationError(DegenbotError):
+    """
+    An offline swap could not be simulated against the given pool state
+    """
+
+
+class EvmRevertError(DegenbotError):
+    """
+    An EVM call reverted. The raw revert payload is available as `data`
+    """
+
+    def __init__(self, message: str = "execution reverted", data: bytes = b"") -> None:
+        super().__init__(message)
+        self.data = data
+
+
+class RpcResponseError(DegenbotError):
+    """
+    An error object returned by a JSON-RPC endpoint
+    """
+
+    def __init__(self, code: int, message: str, data: Any = None) -> None:
+        super().__init__(f"JSON-RPC error {code}: {message}")
+        self.code = code
+        self.message = message
+        self.data = data
+
+
+class OperationCancelled(DegenbotError):
+    """
+    Raised by a long-running operation that stopped because its token was cancelled
+    """
+
+
+class IntegerOverflowError(DegenbotError, OverflowError):
+    """
+    A fixed-width integer operation overflowed or underflowed its type
+    """
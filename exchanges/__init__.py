@@ -0,0 +1 @@
+from .exchanges import *
\ No newline at end of file
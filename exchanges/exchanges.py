@@ -0,0 +1,191 @@
+from dataclasses import dataclass
+from fractions import Fraction
+from typing import Dict, List, Optional, Tuple
+from eth_utils import keccak, to_checksum_address
+from ..abi import abi_encode
+from ..address_utils import compute_create2_address, compute_solidly_pair_address
+from ..uniswap_v3 import ExchangeVariant
+
+__all__ = [
+    "UNISWAP_V2",
+    "UNISWAP_V3",
+    "SOLIDLY",
+    "ExchangeDeployment",
+    "sorted_tokens",
+    "register_exchange",
+    "get_exchange",
+    "exchanges_on_chain",
+    "exchange_by_factory",
+]
+
+UNISWAP_V2 = "uniswap_v2"
+UNISWAP_V3 = "uniswap_v3"
+# pairs salted with their stability flag. For factories deploying clones, the init
+# code hash is that of the clone (see clone_init_code_hash)
+SOLIDLY = "solidly"
+
+
+@dataclass(frozen=True)
+class ExchangeDeployment:
+    """
+    A DEX deployment on one chain, with what is needed to derive its pool addresses
+    offline. `kind` selects the derivation rule. V2-style exchanges charge a single
+    `fee`, V3-style exchanges have a set of `fee_tiers` (in hundredths of a bip).
+    `deployer` is the contract that creates the pools, if it is not the factory, and
+    `variant` the fork of the V3 contracts its pools run
+    """
+
+    name: str
+    chain_id: int
+    kind: str
+    factory: str
+    init_code_hash: str
+    fee: Optional[Fraction] = None
+    fee_tiers: Tuple[int, ...] = ()
+    deployer: Optional[str] = None
+    variant: Optional[ExchangeVariant] = None
+
+    def pool_address(
+        self,
+        token_a: str,
+        token_b: str,
+        fee: Optional[int] = None,
+        stable: Optional[bool] = None,
+    ) -> str:
+        """
+        Computes the address of the pool for a pair of tokens (in any order). V3-style
+        exchanges need the fee tier, Solidly-style exchanges the stability flag
+        """
+        token0, token1 = sorted_tokens(token_a, token_b)
+        if self.kind == SOLIDLY:
+            assert stable is not None, "Solidly pair addresses depend on `stable`"
+            return compute_solidly_pair_address(
+                self.deployer or self.factory,
+                token0,
+                token1,
+                stable,
+                self.init_code_hash,
+            )
+        if self.kind == UNISWAP_V2:
+            salt = keccak(bytes.fromhex(token0[2:]) + bytes.fromhex(token1[2:]))
+        elif self.kind == UNISWAP_V3:
+            assert fee is not None, "V3 pool addresses depend on the fee tier"
+            assert (
+                fee in self.fee_tiers
+            ), f"{fee} is not a fee tier of {self.name} on chain {self.chain_id}"
+            salt = keccak(
+                abi_encode(["address", "address", "uint24"], [token0, token1, fee])
+            )
+        else:
+            raise ValueError(f"Unknown derivation rule {self.kind}")
+        return compute_create2_address(
+            self.deployer or self.factory, salt, self.init_code_hash
+        )
+
+
+def sorted_tokens(token_a: str, token_b: str) -> Tuple[str, str]:
+    """
+    Returns the checksummed tokens of a pair as (token0, token1), ordered as factories
+    order them
+    """
+    assert token_a.lower() != token_b.lower(), "A pool needs two different tokens"
+    token_a, token_b = to_checksum_address(token_a), to_checksum_address(token_b)
+    if int(token_a, 16) < int(token_b, 16):
+        return token_a, token_b
+    return token_b, token_a
+
+
+_UNISWAP_V3_FEE_TIERS = (100, 500, 3000, 10000)
+_PANCAKESWAP_V3_FEE_TIERS = (100, 500, 2500, 10000)
+
+_EXCHANGES: Dict[Tuple[int, str], ExchangeDeployment] = {}
+
+
+def register_exchange(deployment: ExchangeDeployment) -> None:
+    """
+    Adds a deployment to the registry, replacing any with the same chain and name
+    """
+    _EXCHANGES[deployment.chain_id, deployment.name] = deployment
+
+
+def get_exchange(chain_id: int, name: str) -> ExchangeDeployment:
+    try:
+        return _EXCHANGES[chain_id, name]
+    except KeyError:
+        raise KeyError(f"No exchange {name} registered on chain {chain_id}") from None
+
+
+def exchanges_on_chain(chain_id: int) -> List[ExchangeDeployment]:
+    return [
+        deployment
+        for (deployment_chain_id, _), deployment in _EXCHANGES.items()
+        if deployment_chain_id == chain_id
+    ]
+
+
+def exchange_by_factory(chain_id: int, factory: str) -> Optional[ExchangeDeployment]:
+    """
+    Identifies the exchange of a pool from its factory() address
+    """
+    factory = factory.lower()
+    for deployment in exchanges_on_chain(chain_id):
+        if deployment.factory.lower() == factory:
+            return deployment
+    return None
+
+
+# forks and other chains are added with register_exchange
+for _deployment in (
+    ExchangeDeployment(
+        name="uniswap_v2",
+        chain_id=1,
+        kind=UNISWAP_V2,
+        factory="0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f",
+        init_code_hash=(
+            "0x96e8ac4277198ff8b6f785478aa9a39f403cb768dd02cbee326c3e7da348845f"
+        ),
+        fee=Fraction(3, 1000),
+    ),
+    ExchangeDeployment(
+        name="pancakeswap_v2",
+        chain_id=56,
+        kind=UNISWAP_V2,
+        factory="0xcA143Ce32Fe78f1f7019d7d551a6402fC5350c73",
+        init_code_hash=(
+            "0x00fb7f630766e6a796048ea87d01acd3068e8ff67d078148a3fa3f4a84f69bd5"
+        ),
+        fee=Fraction(25, 10000),
+    ),
+    # Uniswap V3 shares its factory address across these chains
+    *(
+        ExchangeDeployment(
+            name="uniswap_v3",
+            chain_id=chain_id,
+            kind=UNISWAP_V3,
+            factory="0x1F98431c8aD98523631AE4a59f267346ea31F984",
+            init_code_hash=(
+                "0xe34f199b19b2b4f47f68442619d555527d244f78a3297ea89325f843f87b8b54"
+            ),
+            fee_tiers=_UNISWAP_V3_FEE_TIERS,
+            variant=ExchangeVariant.UNISWAP_V3,
+        )
+        for chain_id in (1, 10, 137, 42161)
+    ),
+    # PancakeSwap V3 pools are created by a separate deployer contract
+    *(
+        ExchangeDeployment(
+            name="pancakeswap_v3",
+            chain_id=chain_id,
+            kind=UNISWAP_V3,
+            factory="0x0BFbCF9fa4f9C56B0F40a671Ad40E0805A091865",
+            init_code_hash=(
+                "0x6ce8eb472fa82df5469c6ab6d485f17c3ad13c8cd7af59b3d4a8026c5ce0f7e2"
+            ),
+            fee_tiers=_PANCAKESWAP_V3_FEE_TIERS,
+            deployer="0x41ff9AA7e16B8B1a8a8dc4f0eFacd93D02d071c9",
+            variant=ExchangeVariant.PANCAKESWAP_V3,
+        )
+        for chain_id in (1, 56)
+    ),
+):
+    register_exchange(_deployment)
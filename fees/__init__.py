@@ -0,0 +1,2 @@
+from .fees import *
+from .fee_oracle import *
\ No newline at end of file
@@ -0,0 +1,141 @@
+from dataclasses import dataclass
+from typing import Dict, List, Sequence, Tuple, Union
+from .fees import max_base_fee_after, next_base_fee
+from ..rpc import Transaction, parse_block_header, parse_transaction
+from ..rpc.responses import _load
+
+__all__ = [
+    "FeeBlock",
+    "FeeSuggestion",
+    "FeeSuggestions",
+    "effective_priority_fee",
+    "parse_fee_block",
+    "suggest_fees",
+]
+
+
+@dataclass
+class FeeBlock:
+    number: int
+    base_fee_per_gas: int
+    gas_used: int
+    gas_limit: int
+    # effective priority fee paid by each transaction in the block
+    priority_fees: List[int]
+
+
+@dataclass
+class FeeSuggestion:
+    max_priority_fee_per_gas: int
+    max_fee_per_gas: int
+
+
+@dataclass
+class FeeSuggestions:
+    # base fee of the block after the most recent one
+    next_base_fee: int
+    low: FeeSuggestion
+    medium: FeeSuggestion
+    high: FeeSuggestion
+    # (percentile, priority fee) pairs from the cheapest to the most urgent, giving
+    # the tip needed to outbid that share of recent transactions
+    urgency_curve: List[Tuple[int, int]]
+
+
+def effective_priority_fee(transaction: Transaction, base_fee: int) -> int:
+    """
+    Returns the tip per gas a transaction paid to the block builder at `base_fee`
+    """
+    if transaction.max_fee_per_gas is not None:
+        return min(
+            transaction.max_priority_fee_per_gas,
+            transaction.max_fee_per_gas - base_fee,
+        )
+    return transaction.gas_price - base_fee
+
+
+def parse_fee_block(data: Union[bytes, str, Dict]) -> FeeBlock:
+    """
+    Extracts the fee data of a block from an eth_getBlockByNumber result (or full
+    response) fetched with full transaction bodies
+    """
+    block = _load(data)
+    if "result" in block:
+        block = block["result"]
+    header = parse_block_header(block)
+    base_fee = header.base_fee_per_gas or 0
+    return FeeBlock(
+        number=header.number,
+        base_fee_per_gas=base_fee,
+        gas_used=header.gas_used,
+        gas_limit=header.gas_limit,
+        priority_fees=[
+            effective_priority_fee(parse_transaction(transaction), base_fee)
+            for transaction in block["transactions"]
+            if isinstance(transaction, dict)
+        ],
+    )
+
+
+def _percentile(sorted_values: List[int], percentile: int) -> int:
+    # nearest-rank percentile
+    rank = max(1, -(-percentile * len(sorted_values) // 100))
+    return sorted_values[rank - 1]
+
+
+def _median(values: List[int]) -> int:
+    values = sorted(values)
+    return values[(len(values) - 1) // 2]
+
+
+def suggest_fees(
+    recent_blocks: Sequence[FeeBlock],
+    low_percentile: int = 10,
+    medium_percentile: int = 50,
+    high_percentile: int = 90,
+    inclusion_blocks: Tuple[int, int, int] = (10, 3, 1),
+    curve_step: int = 5,
+) -> FeeSuggestions:
+    """
+    Suggests EIP-1559 fees from recent blocks (oldest first). The priority fee of a
+    percentile is that percentile of each block's tips, taking the median over the
+    blocks so that a single spiky block does not dominate. The max fee adds the
+    priority fee to the highest base fee reachable within `inclusion_blocks` blocks
+    (for the low, medium and high suggestions), so the transaction stays includable
+    that long even if every block is full
+    """
+    assert recent_blocks, "At least one block is required"
+    latest = recent_blocks[-1]
+    base_fee = next_base_fee(latest.base_fee_per_gas, latest.gas_used, latest.gas_limit)
+    block_tips = [
+        sorted(max(0, tip) for tip in block.priority_fees)
+        for block in recent_blocks
+        if block.priority_fees
+    ]
+
+    def _priority_fee(percentile: int) -> int:
+        if not block_tips:
+            return 0
+        return _median([_percentile(tips, percentile) for tips in block_tips])
+
+    def _suggestion(percentile: int, blocks: int) -> FeeSuggestion:
+        priority_fee = _priority_fee(percentile)
+        return FeeSuggestion(
+            max_priority_fee_per_gas=priority_fee,
+            max_fee_per_gas=max_base_fee_after(
+                base_fee, latest.gas_limit, max(0, blocks - 1)
+            )
+            + priority_fee,
+        )
+
+    low_blocks, medium_blocks, high_blocks = inclusion_blocks
+    return FeeSuggestions(
+        next_base_fee=base_fee,
+        low=_suggestion(low_percentile, low_blocks),
+        medium=_suggestion(medium_percentile, medium_blocks),
+        high=_suggestion(high_percentile, high_blocks),
+        urgency_curve=[
+            (percentile, _priority_fee(percentile))
+            for percentile in range(0, 101, curve_step)
+        ],
+    )
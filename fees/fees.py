@@ -0,0 +1,383 @@
+from typing import Dict, List, Optional, Sequence, Tuple, Union
+
+__all__ = [
+    "BASE_FEE_ELASTICITY_MULTIPLIER",
+    "BASE_FEE_MAX_CHANGE_DENOMINATOR",
+    "GAS_PER_BLOB",
+    "MIN_BASE_FEE_PER_BLOB_GAS",
+    "BLOB_BASE_FEE_UPDATE_FRACTION",
+    "BLOB_BASE_FEE_UPDATE_FRACTION_PRAGUE",
+    "TARGET_BLOB_GAS_PER_BLOCK",
+    "TARGET_BLOB_GAS_PER_BLOCK_PRAGUE",
+    "TX_BASE_GAS",
+    "TX_CREATE_GAS",
+    "TX_DATA_ZERO_GAS",
+    "TX_DATA_NON_ZERO_GAS",
+    "TX_ACCESS_LIST_ADDRESS_GAS",
+    "TX_ACCESS_LIST_STORAGE_KEY_GAS",
+    "INITCODE_WORD_GAS",
+    "TOTAL_COST_FLOOR_PER_TOKEN",
+    "OP_FJORD_COST_INTERCEPT",
+    "OP_FJORD_FASTLZ_COEFFICIENT",
+    "OP_FJORD_MIN_TRANSACTION_SIZE",
+    "OP_FJORD_DIVISOR",
+    "OP_UNSIGNED_TX_PADDING",
+    "next_base_fee",
+    "project_base_fees",
+    "max_base_fee_after",
+    "fake_exponential",
+    "blob_base_fee",
+    "next_excess_blob_gas",
+    "blob_fee",
+    "flz_compress_len",
+    "op_l1_fee",
+    "calldata_gas",
+    "floor_data_gas",
+    "intrinsic_gas",
+]
+
+# EIP-1559 defaults for Ethereum mainnet
+BASE_FEE_ELASTICITY_MULTIPLIER = 2
+BASE_FEE_MAX_CHANGE_DENOMINATOR = 8
+
+# EIP-4844 blob parameters. Cancun values are the defaults, Prague (EIP-7691) raised
+# the target and the update fraction
+GAS_PER_BLOB = 2 ** 17
+MIN_BASE_FEE_PER_BLOB_GAS = 1
+BLOB_BASE_FEE_UPDATE_FRACTION = 3_338_477
+BLOB_BASE_FEE_UPDATE_FRACTION_PRAGUE = 5_007_716
+TARGET_BLOB_GAS_PER_BLOCK = 3 * GAS_PER_BLOB
+TARGET_BLOB_GAS_PER_BLOCK_PRAGUE = 6 * GAS_PER_BLOB
+
+# Transaction gas schedule, current as of Prague
+TX_BASE_GAS = 21_000
+TX_CREATE_GAS = 32_000
+TX_DATA_ZERO_GAS = 4
+TX_DATA_NON_ZERO_GAS = 16
+TX_ACCESS_LIST_ADDRESS_GAS = 2_400
+TX_ACCESS_LIST_STORAGE_KEY_GAS = 1_900
+INITCODE_WORD_GAS = 2
+TOTAL_COST_FLOOR_PER_TOKEN = 10
+
+# OP-Stack Fjord L1 cost parameters, see the GasPriceOracle predeploy
+OP_FJORD_COST_INTERCEPT = -42_585_600
+OP_FJORD_FASTLZ_COEFFICIENT = 836_500
+OP_FJORD_MIN_TRANSACTION_SIZE = 100
+OP_FJORD_DIVISOR = 10 ** 12
+# upper bound for the signature fields missing from an unsigned transaction
+OP_UNSIGNED_TX_PADDING = 68
+
+
+def next_base_fee(
+    parent_base_fee: int,
+    gas_used: int,
+    gas_limit: int,
+    elasticity: int = BASE_FEE_ELASTICITY_MULTIPLIER,
+    denominator: int = BASE_FEE_MAX_CHANGE_DENOMINATOR,
+) -> int:
+    """
+    Calculates the base fee of the next block from its parent, using the exact integer
+    arithmetic of the EIP-1559 specification. OP-Stack and other chains with modified
+    parameters can supply their own elasticity and denominator
+    """
+
+    assert parent_base_fee >= 0, "Base fee must be non-negative"
+    assert 0 <= gas_used <= gas_limit, "Gas used must be between 0 and the gas limit"
+
+    gas_target = gas_limit // elasticity
+    if gas_target == 0:
+        raise ValueError(
+            f"Gas limit {gas_limit} is below the elasticity multiplier {elasticity}"
+        )
+
+    if gas_used == gas_target:
+        return parent_base_fee
+    elif gas_used > gas_target:
+        base_fee_delta = max(
+            parent_base_fee * (gas_used - gas_target) // gas_target // denominator,
+            1,
+        )
+        return parent_base_fee + base_fee_delta
+    else:
+        base_fee_delta = (
+            parent_base_fee * (gas_target - gas_used) // gas_target // denominator
+        )
+        return parent_base_fee - base_fee_delta
+
+
+def project_base_fees(
+    parent_base_fee: int,
+    gas_limit: int,
+    gas_used: List[int],
+    elasticity: int = BASE_FEE_ELASTICITY_MULTIPLIER,
+    denominator: int = BASE_FEE_MAX_CHANGE_DENOMINATOR,
+) -> List[int]:
+    """
+    Projects the base fee forward over several blocks, given the gas expected to be
+    used in each one. Returns the base fee of each projected block, in order
+    """
+
+    base_fees = []
+    base_fee = parent_base_fee
+    for block_gas_used in gas_used:
+        base_fee = next_base_fee(
+            base_fee, block_gas_used, gas_limit, elasticity, denominator
+        )
+        base_fees.append(base_fee)
+    return base_fees
+
+
+def max_base_fee_after(
+    parent_base_fee: int,
+    gas_limit: int,
+    blocks: int,
+    elasticity: int = BASE_FEE_ELASTICITY_MULTIPLIER,
+    denominator: int = BASE_FEE_MAX_CHANGE_DENOMINATOR,
+) -> int:
+    """
+    Returns the highest possible base fee `blocks` blocks ahead (every block full),
+    which is a safe maxFeePerGas for a transaction that must remain includable
+    """
+
+    if blocks == 0:
+        return parent_base_fee
+    return project_base_fees(
+        parent_base_fee, gas_limit, [gas_limit] * blocks, elasticity, denominator
+    )[-1]
+
+
+def fake_exponential(factor: int, numerator: int, denominator: int) -> int:
+    """
+    Integer approximation of factor * e ** (numerator / denominator), evaluated by
+    Taylor expansion exactly as specified by EIP-4844
+    """
+
+    i = 1
+    output = 0
+    numerator_accum = factor * denominator
+    while numerator_accum > 0:
+        output += numerator_accum
+        numerator_accum = (numerator_accum * numerator) // (denominator * i)
+        i += 1
+    return output // denominator
+
+
+def blob_base_fee(
+    excess_blob_gas: int,
+    update_fraction: int = BLOB_BASE_FEE_UPDATE_FRACTION,
+) -> int:
+    """
+    Calculates the base fee per unit of blob gas for a block with the given excess
+    blob gas
+    """
+
+    assert excess_blob_gas >= 0, "Excess blob gas must be non-negative"
+    return fake_exponential(MIN_BASE_FEE_PER_BLOB_GAS, excess_blob_gas, update_fraction)
+
+
+def next_excess_blob_gas(
+    parent_excess_blob_gas: int,
+    parent_blob_gas_used: int,
+    target_blob_gas: int = TARGET_BLOB_GAS_PER_BLOCK,
+) -> int:
+    """
+    Calculates the excess blob gas of the next block from its parent
+    """
+
+    if parent_excess_blob_gas + parent_blob_gas_used < target_blob_gas:
+        return 0
+    return parent_excess_blob_gas + parent_blob_gas_used - target_blob_gas
+
+
+def blob_fee(
+    excess_blob_gas: int,
+    blob_count: int,
+    update_fraction: int = BLOB_BASE_FEE_UPDATE_FRACTION,
+) -> int:
+    """
+    Returns the total blob fee (in wei) paid by a transaction carrying `blob_count`
+    blobs in a block with the given excess blob gas
+    """
+
+    return GAS_PER_BLOB * blob_count * blob_base_fee(excess_blob_gas, update_fraction)
+
+
+def flz_compress_len(data: bytes) -> int:
+    """
+    Returns the length of the FastLZ (level 1) compression of `data` without building
+    the output. This is a line-for-line port of op-geth's FlzCompressLen / Solady's
+    LibZip.flzCompress, including its quirks, so that the result matches the chain
+    """
+
+    data = bytes(data)
+    length = len(data)
+    hash_table = [0] * 8192
+    n = 0
+
+    def u24(i: int) -> int:
+        return data[i] | (data[i + 1] << 8) | (data[i + 2] << 16)
+
+    def hash_u24(v: int) -> int:
+        return (((2654435769 * v) & 0xFFFFFFFF) >> 19) & 0x1FFF
+
+    def cmp(p: int, q: int, e: int) -> int:
+        l = 0
+        e -= q
+        while l < e:
+            if data[p + l] != data[q + l]:
+                e = 0
+            l += 1
+        return l
+
+    def literals(r: int) -> int:
+        size = 0x21 * (r // 0x20)
+        r %= 0x20
+        if r:
+            size += r + 1
+        return size
+
+    def match(l: int) -> int:
+        l -= 1
+        return 3 * (l // 262) + (3 if l % 262 >= 6 else 2)
+
+    anchor = 0
+    ip_limit = length - 13 if length >= 13 else 0
+    ip = anchor + 2
+    while ip < ip_limit:
+        while True:
+            s = u24(ip)
+            h = hash_u24(s)
+            r = hash_table[h]
+            hash_table[h] = ip
+            d = ip - r
+            if ip >= ip_limit:
+                break
+            ip += 1
+            if d <= 0x1FFF and s == u24(r):
+                break
+        if ip >= ip_limit:
+            break
+        ip -= 1
+        if ip > anchor:
+            n += literals(ip - anchor)
+        l = cmp(r + 3, ip + 3, ip_limit + 9)
+        n += match(l)
+        ip += l
+        hash_table[hash_u24(u24(ip))] = ip
+        ip += 1
+        hash_table[hash_u24(u24(ip))] = ip
+        ip += 1
+        anchor = ip
+
+    n += literals(length - anchor)
+    return n
+
+
+def op_l1_fee(
+    tx_bytes: bytes,
+    l1_base_fee: int,
+    l1_blob_base_fee: int,
+    base_fee_scalar: int,
+    blob_base_fee_scalar: int,
+    fjord: bool = True,
+    unsigned: bool = False,
+) -> int:
+    """
+    Estimates the L1 data fee (in wei) charged by an OP-Stack chain for a serialized
+    transaction, using the L1 base fee, L1 blob base fee and the two fee scalars
+    published by the GasPriceOracle / L1Block predeploys.
+
+    With `fjord=True` the Fjord formula is used, estimating the data-availability size
+    from the FastLZ-compressed length. With `fjord=False`, the Ecotone formula is used
+    (16 gas per non-zero byte, 4 gas per zero byte) for chains that have not activated
+    Fjord.
+
+    Set `unsigned=True` when passing an unsigned transaction, which pads the estimate
+    for the missing signature the same way the GasPriceOracle does
+    """
+
+    tx_bytes = bytes(tx_bytes)
+
+    if fjord:
+        fastlz_size = flz_compress_len(tx_bytes)
+        if unsigned:
+            fastlz_size += OP_UNSIGNED_TX_PADDING
+        estimated_size = max(
+            OP_FJORD_MIN_TRANSACTION_SIZE * 10 ** 6,
+            OP_FJORD_COST_INTERCEPT + OP_FJORD_FASTLZ_COEFFICIENT * fastlz_size,
+        )
+        fee_scaled = (
+            base_fee_scalar * l1_base_fee * 16 + blob_base_fee_scalar * l1_blob_base_fee
+        )
+        return estimated_size * fee_scaled // OP_FJORD_DIVISOR
+
+    zero_bytes = tx_bytes.count(0)
+    calldata_gas = 4 * zero_bytes + 16 * (len(tx_bytes) - zero_bytes)
+    if unsigned:
+        calldata_gas += 16 * OP_UNSIGNED_TX_PADDING
+    fee_scaled = (
+        base_fee_scalar * l1_base_fee * 16 + blob_base_fee_scalar * l1_blob_base_fee
+    )
+    return calldata_gas * fee_scaled // (16 * 10 ** 6)
+
+
+def calldata_gas(data: bytes) -> int:
+    """
+    Returns the calldata cost of `data`: 4 gas per zero byte, 16 gas per non-zero byte
+    """
+
+    data = bytes(data)
+    zero_bytes = data.count(0)
+    return TX_DATA_ZERO_GAS * zero_bytes + TX_DATA_NON_ZERO_GAS * (
+        len(data) - zero_bytes
+    )
+
+
+def floor_data_gas(data: bytes) -> int:
+    """
+    Returns the EIP-7623 calldata floor: the minimum gas a transaction is charged,
+    regardless of how little execution gas it uses
+    """
+
+    data = bytes(data)
+    zero_bytes = data.count(0)
+    tokens = zero_bytes + 4 * (len(data) - zero_bytes)
+    return TX_BASE_GAS + TOTAL_COST_FLOOR_PER_TOKEN * tokens
+
+
+def intrinsic_gas(
+    data: bytes = b"",
+    access_list: Optional[Sequence[Union[Dict, Tuple[str, List[str]]]]] = None,
+    is_creation: bool = False,
+    apply_floor: bool = True,
+) -> int:
+    """
+    Calculates the intrinsic gas of a transaction: the base cost, the calldata cost,
+    the EIP-2930 access list cost, and for contract creations the CREATE surcharge and
+    EIP-3860 initcode word cost.
+
+    The access list may be given in JSON-RPC form ({"address": ..., "storageKeys":
+    [...]}) or as (address, storage_keys) pairs.
+
+    When `apply_floor` is set, the result is raised to the EIP-7623 calldata floor, so
+    it is the minimum gas the transaction can be charged
+    """
+
+    data = bytes(data)
+    gas = TX_BASE_GAS + calldata_gas(data)
+
+    if is_creation:
+        gas += TX_CREATE_GAS + INITCODE_WORD_GAS * ((len(data) + 31) // 32)
+
+    for entry in access_list or []:
+        if isinstance(entry, dict):
+            storage_keys = entry.get("storageKeys", [])
+        else:
+            _, storage_keys = entry
+        gas += TX_ACCESS_LIST_ADDRESS_GAS
+        gas += TX_ACCESS_LIST_STORAGE_KEY_GAS * len(storage_keys)
+
+    if apply_floor:
+        gas = max(gas, floor_data_gas(data))
+
+    return gas
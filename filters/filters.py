@@ -0,0 +1,111 @@
+from typing import Iterable, Iterator, Optional, Union
+from eth_utils import to_checksum_address
+
+__all__ = ["ADDRESS_SET_MAGIC", "AddressSet"]
+
+ADDRESS_SET_MAGIC = b"DBADDR1\n"
+
+
+def _address_key(address: Union[bytes, str]) -> bytes:
+    if isinstance(address, str):
+        key = bytes.fromhex(address[2:] if address.startswith("0x") else address)
+    else:
+        key = bytes(address)
+    assert len(key) == 20, f"Expected a 20-byte address, found {len(key)} bytes"
+    return key
+
+
+class AddressSet:
+    """
+    Set of addresses stored as raw 20-byte keys, for blacklists and allowlists.
+    Membership tests accept checksummed, lowercase or unprefixed hex strings and
+    bytes alike, without checksumming. Iteration yields checksummed addresses
+    """
+
+    def __init__(self, addresses: Iterable[Union[bytes, str]] = ()) -> None:
+        self._keys = {_address_key(address) for address in addresses}
+
+    def __repr__(self) -> str:
+        return f"AddressSet({len(self._keys)} addresses)"
+
+    def __len__(self) -> int:
+        return len(self._keys)
+
+    def __iter__(self) -> Iterator[str]:
+        return (to_checksum_address(key) for key in self._keys)
+
+    def __contains__(self, address: Union[bytes, str]) -> bool:
+        try:
+            return _address_key(address) in self._keys
+        except (AssertionError, ValueError):
+            return False
+
+    def __eq__(self, other: object) -> bool:
+        return isinstance(other, AddressSet) and self._keys == other._keys
+
+    def contains(self, address: Union[bytes, str]) -> bool:
+        return address in self
+
+    def add(self, address: Union[bytes, str]) -> None:
+        self._keys.add(_address_key(address))
+
+    def update(self, addresses: Iterable[Union[bytes, str]]) -> None:
+        self._keys.update(_address_key(address) for address in addresses)
+
+    def remove(self, address: Union[bytes, str]) -> bool:
+        """
+        Removes an address, returning whether it was in the set
+        """
+        key = _address_key(address)
+        if key not in self._keys:
+            return False
+        self._keys.remove(key)
+        return True
+
+    def to_bytes(self) -> bytes:
+        """
+        Serializes the set as a magic header followed by the sorted 20-byte keys
+        """
+        return ADDRESS_SET_MAGIC + b"".join(sorted(self._keys))
+
+    @classmethod
+    def from_bytes(cls, data: bytes) -> "AddressSet":
+        assert data.startswith(ADDRESS_SET_MAGIC), "Not a serialized AddressSet"
+        body = data[len(ADDRESS_SET_MAGIC) :]
+        assert len(body) % 20 == 0, "Truncated AddressSet data"
+        address_set = cls()
+        address_set._keys = {body[i : i + 20] for i in range(0, len(body), 20)}
+        return address_set
+
+    def save(self, path: str, binary: Optional[bool] = None) -> None:
+        """
+        Writes the set to a file, in the binary format of to_bytes or as text with one
+        checksummed address per line. By default, the binary format is used unless
+        the path ends with ".txt"
+        """
+        if binary is None:
+            binary = not path.endswith(".txt")
+        if binary:
+            with open(path, "wb") as file:
+                file.write(self.to_bytes())
+            return
+        with open(path, "w") as file:
+            for key in sorted(self._keys):
+                file.write(to_checksum_address(key) + "\n")
+
+    @classmethod
+    def load(cls, path: str) -> "AddressSet":
+        """
+        Reads a set written by save, detecting its format. Text files hold one address
+        per line, and blank lines and text after a "#" are ignored
+        """
+        with open(path, "rb") as file:
+            data = file.read()
+        if data.startswith(ADDRESS_SET_MAGIC):
+            return cls.from_bytes(data)
+        addresses = []
+        for line in data.decode().splitlines():
+            line = line.split("#", 1)[0].strip()
+            if line:
+                addresses.append(line)
+        return cls(addresses)
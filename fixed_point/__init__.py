@@ -0,0 +1 @@
+from .fixed_point import *
\ No newline at end of file
@@ -0,0 +1,157 @@
+import math
+from decimal import Decimal, localcontext
+from typing import Union
+
+__all__ = ["X96", "X128"]
+
+_UINT256_LIMIT = 2 ** 256
+
+
+class _FixedPoint:
+    """
+    An unsigned binary fixed-point number stored as its raw integer representation,
+    with the same truncation as Solidity's FullMath: products and quotients round
+    down unless `round_up` is set, and results must fit in 256 bits
+    """
+
+    RESOLUTION: int
+
+    __slots__ = ("raw",)
+
+    def __init__(self, raw: int) -> None:
+        assert isinstance(raw, int), f"Expected an int, found {type(raw).__name__}"
+        assert 0 <= raw < _UINT256_LIMIT, f"Value {raw} is outside the uint256 range"
+        self.raw = raw
+
+    @classmethod
+    def one(cls):
+        return cls(1 << cls.RESOLUTION)
+
+    @classmethod
+    def from_int(cls, value: int):
+        return cls(value << cls.RESOLUTION)
+
+    @classmethod
+    def from_decimal(cls, value: Union[Decimal, str]):
+        """
+        Converts a decimal value, truncating digits beyond the fixed-point resolution
+        """
+        with localcontext() as context:
+            context.prec = 160
+            return cls(int(Decimal(value) * (1 << cls.RESOLUTION)))
+
+    def to_int(self) -> int:
+        """
+        Returns the integer part, rounded down
+        """
+        return self.raw >> self.RESOLUTION
+
+    def to_decimal(self) -> Decimal:
+        """
+        Returns the exact value as a Decimal (binary fractions always terminate)
+        """
+        with localcontext() as context:
+            context.prec = 160
+            return Decimal(self.raw) / (1 << self.RESOLUTION)
+
+    def _raw(self, other) -> int:
+        if isinstance(other, type(self)):
+            return other.raw
+        assert not isinstance(
+            other, _FixedPoint
+        ), "Cannot mix fixed-point values of different resolutions"
+        return other << self.RESOLUTION
+
+    def mul(self, other, round_up: bool = False):
+        """
+        Multiplies by another value of the same type or by an int
+        """
+        if isinstance(other, int):
+            return type(self)(self.raw * other)
+        product = self.raw * self._raw(other)
+        if round_up:
+            return type(self)(-(-product >> self.RESOLUTION))
+        return type(self)(product >> self.RESOLUTION)
+
+    def div(self, other, round_up: bool = False):
+        """
+        Divides by another value of the same type or by an int
+        """
+        if isinstance(other, int):
+            numerator, denominator = self.raw, other
+        else:
+            numerator, denominator = self.raw << self.RESOLUTION, self._raw(other)
+        assert denominator != 0, "Division by zero"
+        if round_up:
+            return type(self)(-(-numerator // denominator))
+        return type(self)(numerator // denominator)
+
+    def sqrt(self):
+        """
+        Returns the square root, rounded down
+        """
+        return type(self)(math.isqrt(self.raw << self.RESOLUTION))
+
+    def __add__(self, other):
+        return type(self)(self.raw + self._raw(other))
+
+    def __sub__(self, other):
+        return type(self)(self.raw - self._raw(other))
+
+    def __mul__(self, other):
+        return self.mul(other)
+
+    def __truediv__(self, other):
+        return self.div(other)
+
+    __radd__ = __add__
+    __rmul__ = __mul__
+
+    def __int__(self) -> int:
+        return self.to_int()
+
+    def __float__(self) -> float:
+        return self.raw / (1 << self.RESOLUTION)
+
+    def __eq__(self, other) -> bool:
+        if isinstance(other, (int, type(self))):
+            return self.raw == self._raw(other)
+        return NotImplemented
+
+    def __lt__(self, other) -> bool:
+        return self.raw < self._raw(other)
+
+    def __le__(self, other) -> bool:
+        return self.raw <= self._raw(other)
+
+    def __gt__(self, other) -> bool:
+        return self.raw > self._raw(other)
+
+    def __ge__(self, other) -> bool:
+        return self.raw >= self._raw(other)
+
+    def __hash__(self) -> int:
+        return hash((self.RESOLUTION, self.raw))
+
+    def __repr__(self) -> str:
+        return f"{type(self).__name__}({self.raw})"
+
+
+class X96(_FixedPoint):
+    """
+    Q64.96 fixed-point number, the format of Uniswap V3 sqrt prices
+    (e.g. X96(sqrt_price_x96).mul(X96(sqrt_price_x96)) is the raw token1/token0
+    price in Q96)
+    """
+
+    RESOLUTION = 96
+    __slots__ = ()
+
+
+class X128(_FixedPoint):
+    """
+    Q128.128 fixed-point number, the format of Uniswap V3 fee growth accumulators
+    """
+
+    RESOLUTION = 128
+    __slots__ = ()
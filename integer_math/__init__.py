@@ -0,0 +1,2 @@
+from .integer_math import *
+from .signed import *
\ No newline at end of file
@@ -0,0 +1,96 @@
+import math
+from ..exceptions import IntegerOverflowError
+
+__all__ = [
+    "isqrt",
+    "mulmod",
+    "addmod",
+    "checked_add",
+    "checked_sub",
+    "checked_mul",
+    "wrapping_add",
+    "wrapping_sub",
+    "wrapping_mul",
+]
+
+
+def _check_uint(value: int, bits: int) -> None:
+    if not 0 <= value < 1 << bits:
+        raise IntegerOverflowError(f"{value} is outside the uint{bits} range")
+
+
+def isqrt(value: int) -> int:
+    """
+    Returns the square root of a uint256, rounded down
+    """
+    _check_uint(value, 256)
+    return math.isqrt(value)
+
+
+def mulmod(a: int, b: int, m: int) -> int:
+    """
+    (a * b) % m computed without intermediate overflow, with the EVM's MULMOD
+    semantics: a modulus of zero returns zero
+    """
+    for value in (a, b, m):
+        _check_uint(value, 256)
+    return a * b % m if m else 0
+
+
+def addmod(a: int, b: int, m: int) -> int:
+    """
+    (a + b) % m computed without intermediate overflow, with the EVM's ADDMOD
+    semantics: a modulus of zero returns zero
+    """
+    for value in (a, b, m):
+        _check_uint(value, 256)
+    return (a + b) % m if m else 0
+
+
+def _checked(result: int, bits: int, operation: str) -> int:
+    if not 0 <= result < 1 << bits:
+        raise IntegerOverflowError(f"uint{bits} {operation} overflowed: {result}")
+    return result
+
+
+def checked_add(a: int, b: int, bits: int = 256) -> int:
+    """
+    Unsigned addition that raises IntegerOverflowError instead of wrapping, like
+    Solidity >= 0.8 arithmetic outside of an unchecked block
+    """
+    _check_uint(a, bits)
+    _check_uint(b, bits)
+    return _checked(a + b, bits, "addition")
+
+
+def checked_sub(a: int, b: int, bits: int = 256) -> int:
+    _check_uint(a, bits)
+    _check_uint(b, bits)
+    return _checked(a - b, bits, "subtraction")
+
+
+def checked_mul(a: int, b: int, bits: int = 256) -> int:
+    _check_uint(a, bits)
+    _check_uint(b, bits)
+    return _checked(a * b, bits, "multiplication")
+
+
+def wrapping_add(a: int, b: int, bits: int = 256) -> int:
+    """
+    Unsigned addition modulo 2**bits, like EVM ADD or Solidity unchecked blocks
+    """
+    _check_uint(a, bits)
+    _check_uint(b, bits)
+    return (a + b) & ((1 << bits) - 1)
+
+
+def wrapping_sub(a: int, b: int, bits: int = 256) -> int:
+    _check_uint(a, bits)
+    _check_uint(b, bits)
+    return (a - b) & ((1 << bits) - 1)
+
+
+def wrapping_mul(a: int, b: int, bits: int = 256) -> int:
+    _check_uint(a, bits)
+    _check_uint(b, bits)
+    return (a * b) & ((1 << bits) - 1)
@@ -0,0 +1,134 @@
+from typing import Optional, Union
+from ..exceptions import IntegerOverflowError
+
+__all__ = [
+    "INT256_MIN",
+    "INT256_MAX",
+    "INT128_MIN",
+    "INT128_MAX",
+    "to_signed",
+    "to_unsigned",
+    "signed_from_bytes",
+    "signed_to_bytes",
+    "checked_signed_add",
+    "checked_signed_sub",
+    "checked_signed_mul",
+    "saturating_signed_add",
+    "saturating_signed_sub",
+    "saturating_signed_mul",
+]
+
+INT256_MIN = -(2 ** 255)
+INT256_MAX = 2 ** 255 - 1
+INT128_MIN = -(2 ** 127)
+INT128_MAX = 2 ** 127 - 1
+
+
+def _bounds(bits: int):
+    assert bits % 8 == 0 and 8 <= bits <= 256, f"Unsupported integer width {bits}"
+    return -(1 << (bits - 1)), (1 << (bits - 1)) - 1
+
+
+def _check_int(value: int, bits: int) -> None:
+    low, high = _bounds(bits)
+    if not low <= value <= high:
+        raise IntegerOverflowError(f"{value} is outside the int{bits} range")
+
+
+def to_signed(value: int, bits: int = 256) -> int:
+    """
+    Interprets an unsigned integer as a two's-complement int of the given width, e.g.
+    a liquidityNet read with eth_getStorageAt (to_signed(raw, 128))
+    """
+    if not 0 <= value < 1 << bits:
+        raise IntegerOverflowError(f"{value} is outside the uint{bits} range")
+    return value - (1 << bits) if value >> (bits - 1) else value
+
+
+def to_unsigned(value: int, bits: int = 256) -> int:
+    """
+    Returns the two's-complement representation of a signed int as an unsigned int
+    """
+    _check_int(value, bits)
+    return value & ((1 << bits) - 1)
+
+
+def signed_from_bytes(data: Union[bytes, str], bits: Optional[int] = None) -> int:
+    """
+    Decodes big-endian two's-complement bytes (or a hex string). The width defaults
+    to the length of the data, so a 32-byte word decodes as an int256. When `bits` is
+    smaller, only the low-order bytes are used, sign-extending from that width
+    """
+    if isinstance(data, str):
+        data = data[2:] if data.startswith("0x") else data
+        data = bytes.fromhex(data.rjust(len(data) + len(data) % 2, "0"))
+    data = bytes(data)
+    bits = bits or 8 * len(data)
+    _bounds(bits)
+    return to_signed(int.from_bytes(data, "big") & ((1 << bits) - 1), bits)
+
+
+def signed_to_bytes(value: int, bits: int = 256, length: Optional[int] = None) -> bytes:
+    """
+    Encodes a signed int as big-endian two's-complement bytes of `length` bytes
+    (default bits // 8), sign-extended as the EVM does when widening
+    """
+    _check_int(value, bits)
+    length = length or bits // 8
+    assert length * 8 >= bits, "Length is too short for the integer width"
+    return value.to_bytes(length, "big", signed=True)
+
+
+def _checked(result: int, bits: int, operation: str) -> int:
+    low, high = _bounds(bits)
+    if not low <= result <= high:
+        raise IntegerOverflowError(f"int{bits} {operation} overflowed: {result}")
+    return result
+
+
+def _saturated(result: int, bits: int) -> int:
+    low, high = _bounds(bits)
+    return min(max(result, low), high)
+
+
+def checked_signed_add(a: int, b: int, bits: int = 256) -> int:
+    """
+    Signed addition that raises IntegerOverflowError outside the int range, e.g. when
+    applying a liquidityNet to an int128 liquidity
+    """
+    _check_int(a, bits)
+    _check_int(b, bits)
+    return _checked(a + b, bits, "addition")
+
+
+def checked_signed_sub(a: int, b: int, bits: int = 256) -> int:
+    _check_int(a, bits)
+    _check_int(b, bits)
+    return _checked(a - b, bits, "subtraction")
+
+
+def checked_signed_mul(a: int, b: int, bits: int = 256) -> int:
+    _check_int(a, bits)
+    _check_int(b, bits)
+    return _checked(a * b, bits, "multiplication")
+
+
+def saturating_signed_add(a: int, b: int, bits: int = 256) -> int:
+    """
+    Signed addition clamped to the int range instead of overflowing
+    """
+    _check_int(a, bits)
+    _check_int(b, bits)
+    return _saturated(a + b, bits)
+
+
+def saturating_signed_sub(a: int, b: int, bits: int = 256) -> int:
+    _check_int(a, bits)
+    _check_int(b, bits)
+    return _saturated(a - b, bits)
+
+
+def saturating_signed_mul(a: int, b: int, bits: int = 256) -> int:
+    _check_int(a, bits)
+    _check_int(b, bits)
+    return _saturated(a * b, bits)
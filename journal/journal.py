@@ -0,0 +1,254 @@
+import os
+from dataclasses import fields
+from typing import Dict, Iterable, Iterator, List, Optional, Tuple
+from ..exceptions import RlpError
+from ..pool_state import PoolEvent, V2SyncEvent, V3BurnEvent, V3MintEvent, V3SwapEvent
+from ..rlp import rlp_decode_int, rlp_encode
+from ..rlp.rlp import _decode_item
+from ..snapshot import load_snapshot, save_snapshot, take_snapshot
+
+__all__ = [
+    "JOURNAL_MAGIC",
+    "encode_journal_entry",
+    "decode_journal_entries",
+    "StateJournal",
+]
+
+# record type of each journaled event class. Codes are part of the file format and
+# must never be reused
+_EVENT_CODES = {
+    V2SyncEvent: 1,
+    V3SwapEvent: 2,
+    V3MintEvent: 3,
+    V3BurnEvent: 4,
+}
+_EVENT_CLASSES = {code: cls for cls, code in _EVENT_CODES.items()}
+
+JOURNAL_MAGIC = b"DBJRNL1\n"
+
+
+def _zigzag(value: int) -> int:
+    # maps signed ints onto the non-negative ints RLP can encode
+    return 2 * value if value >= 0 else -2 * value - 1
+
+
+def _unzigzag(value: int) -> int:
+    return value // 2 if value % 2 == 0 else -(value + 1) // 2
+
+
+def encode_journal_entry(event: PoolEvent) -> bytes:
+    """
+    Encodes an event as a journal record: an RLP list of the record type, the pool
+    address, and every event field in declaration order (signed values zigzag-encoded)
+    """
+    code = _EVENT_CODES.get(type(event))
+    assert code is not None, f"Cannot journal {type(event).__name__}"
+    values = [getattr(event, field.name) for field in fields(event)]
+    return rlp_encode(
+        [code, values[0].encode()] + [_zigzag(value) for value in values[1:]]
+    )
+
+
+def decode_journal_entries(data: bytes) -> Iterator[Tuple[PoolEvent, int]]:
+    """
+    Decodes concatenated journal records, yielding each event with the offset just
+    past its record. A truncated final record (from a crash mid-write) is ignored
+    """
+    position = 0
+    while position < len(data):
+        try:
+            item, end = _decode_item(data, position)
+        except (IndexError, RlpError):
+            return
+        if end > len(data):
+            return
+        cls = _EVENT_CLASSES[rlp_decode_int(item[0])]
+        values = [item[1].decode()] + [
+            _unzigzag(rlp_decode_int(value)) for value in item[2:]
+        ]
+        yield cls(*values), end
+        position = end
+
+
+class StateJournal:
+    """
+    Event-sourced history of a set of pool states. Every update is applied through
+    `append`, which keeps the current state (`pools`) and records the event, so the
+    state after any journaled block can be rebuilt with `replay`, and the journal can
+    be rewound with `rewind`.
+
+    With a `path`, the initial states are saved to `<path>.base` and records are
+    appended to `path` as they arrive, so `StateJournal.load(path)` reconstructs the
+    exact state after a crash. Snapshots are kept in memory every
+    `checkpoint_interval` blocks to bound the cost of a replay
+    """
+
+    def __init__(
+        self,
+        pools: Iterable,
+        path: Optional[str] = None,
+        checkpoint_interval: int = 100,
+    ) -> None:
+        assert checkpoint_interval >= 1, "The checkpoint interval must be positive"
+        self.path = path
+        self.checkpoint_interval = checkpoint_interval
+        self._base = take_snapshot(pools)
+        self._head = take_snapshot(self._base.values())
+        self._events: List[PoolEvent] = []
+        # file offset after each record, used to truncate the log on rewind
+        self._offsets: List[int] = []
+        # (block number, number of events applied, snapshot before that block)
+        self._checkpoints: List[Tuple[int, int, Dict]] = []
+        self._file = None
+        if path is not None:
+            save_snapshot(self._base, f"{path}.base", binary=True)
+            with open(path, "wb") as file:
+                file.write(JOURNAL_MAGIC)
+            self._file = open(path, "ab")
+
+    @classmethod
+    def load(cls, path: str, checkpoint_interval: int = 100) -> "StateJournal":
+        """
+        Reopens a journal written to `path`, replaying its records onto the saved
+        initial states
+        """
+        with open(path, "rb") as file:
+            data = file.read()
+        assert data.startswith(JOURNAL_MAGIC), f"{path} is not a state journal"
+
+        journal = cls(
+            load_snapshot(f"{path}.base").values(),
+            checkpoint_interval=checkpoint_interval,
+        )
+        end = len(JOURNAL_MAGIC)
+        for event, offset in decode_journal_entries(data[len(JOURNAL_MAGIC) :]):
+            journal._apply(event)
+            end = len(JOURNAL_MAGIC) + offset
+            journal._offsets.append(end)
+
+        if end < len(data):
+            # drop a partially written final record
+            with open(path, "r+b") as file:
+                file.truncate(end)
+        journal.path = path
+        journal._file = open(path, "ab")
+        return journal
+
+    def close(self) -> None:
+        if self._file is not None:
+            self._file.close()
+            self._file = None
+
+    def __len__(self) -> int:
+        return len(self._events)
+
+    @property
+    def pools(self) -> Dict:
+        """
+        The current pool states, keyed by address. Update them only through `append`
+        """
+        return self._head
+
+    @property
+    def last_block(self) -> Optional[int]:
+        return self._events[-1].block_number if self._events else None
+
+    @property
+    def events(self) -> List[PoolEvent]:
+        return list(self._events)
+
+    def _apply(self, event: PoolEvent) -> None:
+        pool = self._head.get(event.pool)
+        assert pool is not None, f"Pool {event.pool} is not tracked by the journal"
+        if self._events:
+            last = self._events[-1]
+            assert (event.block_number, event.log_index) > (
+                last.block_number,
+                last.log_index,
+            ), (
+                f"Event at {event.block_number}/{event.log_index} is not after the "
+                f"last journaled event at {last.block_number}/{last.log_index}"
+            )
+            if event.block_number != last.block_number and (
+                not self._checkpoints
+                or event.block_number - self._checkpoints[-1][0]
+                >= self.checkpoint_interval
+            ):
+                # the state before this block's events, i.e. after the previous block
+                self._checkpoints.append(
+                    (
+                        event.block_number,
+                        len(self._events),
+                        take_snapshot(self._head.values()),
+                    )
+                )
+        pool.apply_event(event)
+        self._events.append(event)
+
+    def append(self, event: PoolEvent) -> None:
+        """
+        Applies an event to its pool and records it. Events must arrive in chain order
+        """
+        self._apply(event)
+        if self._file is not None:
+            self._file.write(encode_journal_entry(event))
+            self._file.flush()
+            self._offsets.append(self._file.tell())
+
+    def extend(self, events: Iterable[PoolEvent]) -> None:
+        for event in events:
+            self.append(event)
+
+    def _restore_point(self, block_number: int) -> Tuple[int, Dict]:
+        """
+        Returns the latest checkpoint taken before `block_number`'s events, as the
+        number of events it includes and its snapshot
+        """
+        start, snapshot = 0, self._base
+        for checkpoint_block, event_count, checkpoint in self._checkpoints:
+            if checkpoint_block > block_number:
+                break
+            start, snapshot = event_count, checkpoint
+        return start, snapshot
+
+    def replay(self, block_number: int) -> Dict:
+        """
+        Returns independent copies of the pool states after every journaled event up
+        to and including `block_number`
+        """
+        start, snapshot = self._restore_point(block_number + 1)
+        pools = take_snapshot(snapshot.values())
+        for event in self._events[start:]:
+            if event.block_number > block_number:
+                break
+            pools[event.pool].apply_event(event)
+        return pools
+
+    def rewind(self, block_number: int) -> List[str]:
+        """
+        Discards every event after `block_number`, from memory and from the journal
+        file, and restores the current states accordingly. The pool objects in `pools`
+        are restored in place, so references held elsewhere remain valid. Returns the
+        addresses of the pools whose state was restored
+        """
+        kept = sum(1 for event in self._events if event.block_number <= block_number)
+        if kept == len(self._events):
+            return []
+
+        restored = self.replay(block_number)
+        affected = sorted({event.pool for event in self._events[kept:]})
+        for address in affected:
+            vars(self._head[address]).update(vars(restored[address]))
+        del self._events[kept:]
+        self._checkpoints = [
+            checkpoint
+            for checkpoint in self._checkpoints
+            if checkpoint[0] <= block_number
+        ]
+        if self._file is not None:
+            end = self._offsets[kept - 1] if kept else len(JOURNAL_MAGIC)
+            del self._offsets[kept:]
+            self._file.flush()
+            self._file.truncate(end)
+            self._file.seek(end)
+        return affected
@@ -0,0 +1 @@
+from .log import *
\ No newline at end of file
@@ -0,0 +1,43 @@
+import logging
+from typing import Optional, Union
+
+__all__ = ["logger", "get_logger", "set_log_level", "enable_logging"]
+
+# parent of every logger in the package. Nothing is printed unless the application
+# configures logging or calls enable_logging
+logger = logging.getLogger("degenbot")
+logger.addHandler(logging.NullHandler())
+
+
+def get_logger(name: str) -> logging.Logger:
+    """
+    Returns the logger of a degenbot component, e.g. "degenbot.provider"
+    """
+    return logger.getChild(name)
+
+
+def set_log_level(level: Union[int, str]) -> None:
+    """
+    Sets the level of all degenbot loggers, e.g. "DEBUG" to see retries, dropped
+    notifications and uninitialized ticks
+    """
+    logger.setLevel(level)
+
+
+def enable_logging(
+    level: Union[int, str] = logging.INFO,
+    handler: Optional[logging.Handler] = None,
+) -> logging.Handler:
+    """
+    Sends degenbot log records to `handler` (stderr by default) at the given level,
+    for applications that do not configure the logging module themselves. Returns
+    the handler, so it can be removed again with logger.removeHandler
+    """
+    if handler is None:
+        handler = logging.StreamHandler()
+        handler.setFormatter(
+            logging.Formatter("%(asctime)s %(name)s %(levelname)s: %(message)s")
+        )
+    logger.addHandler(handler)
+    logger.setLevel(level)
+    return handler
@@ -0,0 +1,150 @@
+import bisect
+import contextlib
+import threading
+import time
+from typing import Dict, Iterator, Sequence
+
+__all__ = [
+    "DEFAULT_LATENCY_BUCKETS",
+    "LatencyHistogram",
+    "set_metrics_enabled",
+    "metrics_enabled",
+    "increment_counter",
+    "observe_latency",
+    "timed_metric",
+    "metrics_snapshot",
+    "reset_metrics",
+    "metrics_prometheus_text",
+]
+
+# latency bucket upper bounds, in seconds
+DEFAULT_LATENCY_BUCKETS = (
+    1e-6,
+    5e-6,
+    1e-5,
+    5e-5,
+    1e-4,
+    5e-4,
+    1e-3,
+    5e-3,
+    1e-2,
+    5e-2,
+    0.1,
+    0.5,
+    1.0,
+    5.0,
+)
+
+
+class LatencyHistogram:
+    def __init__(self, buckets: Sequence[float] = DEFAULT_LATENCY_BUCKETS) -> None:
+        self.buckets = tuple(sorted(buckets))
+        # one count per bucket, plus one for observations above the last bound
+        self.counts = [0] * (len(self.buckets) + 1)
+        self.count = 0
+        self.sum = 0.0
+
+    def observe(self, seconds: float) -> None:
+        self.counts[bisect.bisect_left(self.buckets, seconds)] += 1
+        self.count += 1
+        self.sum += seconds
+
+
+_lock = threading.Lock()
+_counters: Dict[str, int] = {}
+_histograms: Dict[str, LatencyHistogram] = {}
+_enabled = True
+
+
+def set_metrics_enabled(enabled: bool) -> None:
+    """
+    Turns metrics collection on or off (on by default). While disabled, the
+    instrumented functions skip all bookkeeping
+    """
+    global _enabled
+    _enabled = enabled
+
+
+def metrics_enabled() -> bool:
+    return _enabled
+
+
+def increment_counter(name: str, amount: int = 1) -> None:
+    if not _enabled:
+        return
+    with _lock:
+        _counters[name] = _counters.get(name, 0) + amount
+
+
+def observe_latency(name: str, seconds: float) -> None:
+    if not _enabled:
+        return
+    with _lock:
+        histogram = _histograms.get(name)
+        if histogram is None:
+            histogram = _histograms[name] = LatencyHistogram()
+        histogram.observe(seconds)
+
+
+@contextlib.contextmanager
+def timed_metric(name: str) -> Iterator[None]:
+    """
+    Context manager recording the duration of its block in the `name` histogram
+    """
+    if not _enabled:
+        yield
+        return
+    start = time.perf_counter()
+    try:
+        yield
+    finally:
+        observe_latency(name, time.perf_counter() - start)
+
+
+def metrics_snapshot() -> Dict:
+    """
+    Returns a copy of all counters and histograms:
+        {"counters": {name: value},
+         "histograms": {name: {"buckets": {upper bound: cumulative count},
+                               "count": int, "sum": float}}}
+    """
+    with _lock:
+        histograms = {}
+        for name, histogram in _histograms.items():
+            cumulative, buckets = 0, {}
+            for bound, count in zip(histogram.buckets, histogram.counts):
+                cumulative += count
+                buckets[bound] = cumulative
+            buckets[float("inf")] = histogram.count
+            histograms[name] = {
+                "buckets": buckets,
+                "count": histogram.count,
+                "sum": histogram.sum,
+            }
+        return {"counters": dict(_counters), "histograms": histograms}
+
+
+def reset_metrics() -> None:
+    with _lock:
+        _counters.clear()
+        _histograms.clear()
+
+
+def metrics_prometheus_text(prefix: str = "degenbot_") -> str:
+    """
+    Renders the current metrics in the Prometheus text exposition format, for serving
+    from an application's /metrics endpoint
+    """
+    snapshot = metrics_snapshot()
+    lines = []
+    for name, value in sorted(snapshot["counters"].items()):
+        lines.append(f"# TYPE {prefix}{name}_total counter")
+        lines.append(f"{prefix}{name}_total {value}")
+    for name, histogram in sorted(snapshot["histograms"].items()):
+        lines.append(f"# TYPE {prefix}{name} histogram")
+        for bound, count in histogram["buckets"].items():
+            le = "+Inf" if bound == float("inf") else repr(bound)
+            lines.append(f'{prefix}{name}_bucket{{le="{le}"}} {count}')
+        lines.append(f"{prefix}{name}_sum {histogram['sum']}")
+        lines.append(f"{prefix}{name}_count {histogram['count']}")
+    return "\n".join(lines) + "\n"
@@ -0,0 +1 @@
+from .mining import *
\ No newline at end of file
@@ -0,0 +1,202 @@
+import asyncio
+import functools
+import multiprocessing
+from collections import deque
+from typing import Any, Callable, Optional, Tuple, Union
+from eth_utils import to_checksum_address
+from ..address_utils import create2_address_bytes
+from ..cancellation import CancellationToken, ProgressReporter
+from ..metrics import increment_counter
+
+__all__ = ["mine_create2_salt", "mine_create2_salt_async"]
+
+
+def _to_bytes(value: Union[bytes, str]) -> bytes:
+    if isinstance(value, str):
+        return bytes.fromhex(value[2:] if value.startswith("0x") else value)
+    return bytes(value)
+
+
+class _SaltMatcher:
+    """
+    Picklable address predicate, so the criteria can be shipped to worker processes
+    """
+
+    def __init__(
+        self,
+        prefix: str,
+        suffix: str,
+        leading_zero_bytes: int,
+        predicate: Optional[Callable[[bytes], bool]],
+    ) -> None:
+        self.prefix = prefix.lower().replace("0x", "") if prefix else ""
+        self.suffix = suffix.lower() if suffix else ""
+        self.leading_zero_bytes = leading_zero_bytes
+        self.predicate = predicate
+
+    def __call__(self, address: bytes) -> bool:
+        if self.leading_zero_bytes and any(address[: self.leading_zero_bytes]):
+            return False
+        if self.prefix or self.suffix:
+            address_hex = address.hex()
+            if not address_hex.startswith(self.prefix):
+                return False
+            if not address_hex.endswith(self.suffix):
+                return False
+        if self.predicate is not None and not self.predicate(address):
+            return False
+        return True
+
+
+def _search_chunk(
+    deployer: bytes,
+    init_code_hash: bytes,
+    salt_prefix: bytes,
+    start: int,
+    count: int,
+    matcher: _SaltMatcher,
+) -> Optional[bytes]:
+    counter_length = 32 - len(salt_prefix)
+    for counter in range(start, start + count):
+        salt = salt_prefix + counter.to_bytes(counter_length, "big")
+        if matcher(create2_address_bytes(deployer, salt, init_code_hash)):
+            return salt
+    return None
+
+
+def mine_create2_salt(
+    deployer: Union[bytes, str],
+    init_code_hash: Union[bytes, str],
+    prefix: Optional[str] = None,
+    suffix: Optional[str] = None,
+    leading_zero_bytes: int = 0,
+    predicate: Optional[Callable[[bytes], bool]] = None,
+    salt_prefix: Union[bytes, str] = b"",
+    start: int = 0,
+    max_attempts: Optional[int] = None,
+    workers: int = 1,
+    chunk_size: int = 50_000,
+    progress_callback: Optional[Callable[[int], None]] = None,
+    progress_interval: int = 1_000_000,
+    cancellation_token: Optional[CancellationToken] = None,
+) -> Optional[Tuple[bytes, str]]:
+    """
+    Searches for a CREATE2 salt producing a deployment address that matches all of the
+    supplied criteria: a hex prefix and/or suffix (case-insensitive), a number of
+    leading zero bytes, and an arbitrary predicate called with the raw 20-byte address.
+
+    Salts are built as salt_prefix ++ counter, with the counter starting at `start`.
+    A salt_prefix is useful for factories that require the salt to begin with the
+    caller's address.
+
+    The search is split into chunks of `chunk_size` salts, spread across `workers`
+    processes (the predicate must be picklable when workers > 1). Between chunks the
+    `cancellation_token` is checked, and `progress_callback` is called with the number
+    of salts searched roughly every `progress_interval` attempts.
+
+    Returns a tuple of (salt, checksummed address), or None if the search was
+    cancelled or `max_attempts` was exhausted without a match
+    """
+
+    deployer = _to_bytes(deployer)
+    init_code_hash = _to_bytes(init_code_hash)
+    salt_prefix = _to_bytes(salt_prefix)
+
+    assert len(deployer) == 20, f"Expected a 20-byte deployer, found {len(deployer)}"
+    assert (
+        len(init_code_hash) == 32
+    ), f"Expected a 32-byte init code hash, found {len(init_code_hash)}"
+    assert len(salt_prefix) < 32, "Salt prefix must leave room for a counter"
+    assert (
+        prefix or suffix or leading_zero_bytes or predicate
+    ), "At least one search criterion must be provided"
+    assert workers >= 1, "At least one worker is required"
+
+    matcher = _SaltMatcher(prefix, suffix, leading_zero_bytes, predicate)
+    counter_limit = 2 ** (8 * (32 - len(salt_prefix)))
+    end = (
+        counter_limit
+        if max_attempts is None
+        else min(start + max_attempts, counter_limit)
+    )
+
+    progress = ProgressReporter(progress_callback, progress_interval)
+
+    def _chunks():
+        chunk_start = start
+        while chunk_start < end:
+            count = min(chunk_size, end - chunk_start)
+            yield (deployer, init_code_hash, salt_prefix, chunk_start, count, matcher)
+            chunk_start += count
+
+    def _result(salt: bytes) -> Tuple[bytes, str]:
+        return salt, to_checksum_address(
+            create2_address_bytes(deployer, salt, init_code_hash)
+        )
+
+    if workers == 1:
+        for chunk in _chunks():
+            if cancellation_token is not None and cancellation_token.cancelled:
+                return None
+            salt = _search_chunk(*chunk)
+            if salt is not None:
+                return _result(salt)
+            progress.advance(chunk[4])
+            increment_counter("create2_hashes", chunk[4])
+        return None
+
+    with multiprocessing.Pool(processes=workers) as pool:
+        chunks = _chunks()
+        pending = deque()
+
+        # keep a bounded number of chunks in flight, so that cancellation is
+        # responsive and the chunk generator is never fully materialized
+        for chunk in chunks:
+            pending.append((chunk[4], pool.apply_async(_search_chunk, chunk)))
+            if len(pending) >= 2 * workers:
+                break
+
+        while pending:
+            if cancellation_token is not None and cancellation_token.cancelled:
+                pool.terminate()
+                return None
+            count, task = pending.popleft()
+            salt = task.get()
+            if salt is not None:
+                pool.terminate()
+                return _result(salt)
+            progress.advance(count)
+            increment_counter("create2_hashes", count)
+            chunk = next(chunks, None)
+            if chunk is not None:
+                pending.append((chunk[4], pool.apply_async(_search_chunk, chunk)))
+
+    return None
+
+
+async def mine_create2_salt_async(
+    deployer: Union[bytes, str],
+    init_code_hash: Union[bytes, str],
+    **kwargs: Any,
+) -> Optional[Tuple[bytes, str]]:
+    """
+    Awaitable version of mine_create2_salt, run in the event loop's default executor.
+    Cancelling the awaiting task stops the search at the next chunk boundary
+    """
+
+    cancellation_token = kwargs.pop("cancellation_token", None) or CancellationToken()
+    loop = asyncio.get_running_loop()
+    try:
+        return await loop.run_in_executor(
+            None,
+            functools.partial(
+                mine_create2_salt,
+                deployer,
+                init_code_hash,
+                cancellation_token=cancellation_token,
+                **kwargs,
+            ),
+        )
+    except asyncio.CancelledError:
+        cancellation_token.cancel()
+        raise
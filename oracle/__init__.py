@@ -0,0 +1 @@
+from .oracle import *
\ No newline at end of file
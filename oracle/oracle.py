@@ -0,0 +1,174 @@
+import bisect
+from dataclasses import dataclass
+from typing import List, Sequence, Tuple
+from ..constants import MAX_UINT128, MAX_UINT160
+from ..exceptions import EvmRevertError
+
+__all__ = ["Observation", "observe_single", "observe", "consult_cumulatives", "consult"]
+
+
+@dataclass(frozen=True)
+class Observation:
+    """
+    An entry of a Uniswap V3 pool's `observations` array
+    """
+
+    block_timestamp: int
+    tick_cumulative: int
+    seconds_per_liquidity_cumulative_x128: int
+    initialized: bool = True
+
+
+def _div_toward_zero(numerator: int, denominator: int) -> int:
+    # Solidity signed division truncates toward zero, Python floors
+    quotient = abs(numerator) // abs(denominator)
+    return quotient if (numerator >= 0) == (denominator > 0) else -quotient
+
+
+def _transform(
+    last: Observation, block_timestamp: int, tick: int, liquidity: int
+) -> Observation:
+    delta = block_timestamp - last.block_timestamp
+    return Observation(
+        block_timestamp=block_timestamp,
+        tick_cumulative=last.tick_cumulative + tick * delta,
+        seconds_per_liquidity_cumulative_x128=(
+            last.seconds_per_liquidity_cumulative_x128
+            + (delta << 128) // (liquidity if liquidity > 0 else 1)
+        )
+        & MAX_UINT160,
+    )
+
+
+def observe_single(
+    observations: Sequence[Observation],
+    time: int,
+    seconds_ago: int,
+    tick: int,
+    liquidity: int,
+) -> Tuple[int, int]:
+    """
+    Returns (tickCumulative, secondsPerLiquidityCumulativeX128) as of `seconds_ago`
+    before `time`, interpolating between the surrounding observations exactly as
+    Oracle.observeSingle. `tick` and `liquidity` are the pool's current values, used to
+    extrapolate past the latest observation.
+
+    Observations may be given in ring buffer order (uninitialized entries are ignored).
+    Timestamps are assumed not to have wrapped around 2**32
+    """
+    ordered = sorted(
+        (o for o in observations if o.initialized), key=lambda o: o.block_timestamp
+    )
+    assert ordered, "At least one initialized observation is required"
+
+    latest = ordered[-1]
+    target = time - seconds_ago
+
+    if target >= latest.block_timestamp:
+        if target != latest.block_timestamp:
+            latest = _transform(latest, target, tick, liquidity)
+        return (
+            latest.tick_cumulative,
+            latest.seconds_per_liquidity_cumulative_x128,
+        )
+
+    if target < ordered[0].block_timestamp:
+        raise EvmRevertError("OLD")
+
+    timestamps = [o.block_timestamp for o in ordered]
+    index = bisect.bisect_right(timestamps, target) - 1
+    before = ordered[index]
+    if before.block_timestamp == target:
+        return before.tick_cumulative, before.seconds_per_liquidity_cumulative_x128
+    after = ordered[index + 1]
+
+    observation_time_delta = after.block_timestamp - before.block_timestamp
+    target_delta = target - before.block_timestamp
+    tick_cumulative = (
+        before.tick_cumulative
+        + _div_toward_zero(
+            after.tick_cumulative - before.tick_cumulative, observation_time_delta
+        )
+        * target_delta
+    )
+    seconds_per_liquidity = (
+        before.seconds_per_liquidity_cumulative_x128
+        + (
+            # the accumulator is a wrapping uint160
+            (
+                (
+                    after.seconds_per_liquidity_cumulative_x128
+                    - before.seconds_per_liquidity_cumulative_x128
+                )
+                & MAX_UINT160
+            )
+            * target_delta
+        )
+        // observation_time_delta
+    ) & MAX_UINT160
+    return tick_cumulative, seconds_per_liquidity
+
+
+def observe(
+    observations: Sequence[Observation],
+    time: int,
+    seconds_agos: Sequence[int],
+    tick: int,
+    liquidity: int,
+) -> Tuple[List[int], List[int]]:
+    """
+    Offline equivalent of UniswapV3Pool.observe(secondsAgos), returning the lists of
+    tick cumulatives and seconds-per-liquidity cumulatives
+    """
+    results = [
+        observe_single(observations, time, seconds_ago, tick, liquidity)
+        for seconds_ago in seconds_agos
+    ]
+    return [r[0] for r in results], [r[1] for r in results]
+
+
+def consult_cumulatives(
+    tick_cumulatives: Sequence[int],
+    seconds_per_liquidity_cumulatives_x128: Sequence[int],
+    seconds_ago: int,
+) -> Tuple[int, int]:
+    """
+    Computes (arithmeticMeanTick, harmonicMeanLiquidity) as OracleLibrary.consult,
+    from the result of pool.observe([seconds_ago, 0])
+    """
+    assert seconds_ago > 0, "BP"
+
+    tick_cumulatives_delta = tick_cumulatives[1] - tick_cumulatives[0]
+    seconds_per_liquidity_delta = (
+        seconds_per_liquidity_cumulatives_x128[1]
+        - seconds_per_liquidity_cumulatives_x128[0]
+    ) & MAX_UINT160
+
+    arithmetic_mean_tick = _div_toward_zero(tick_cumulatives_delta, seconds_ago)
+    # always round to negative infinity
+    if tick_cumulatives_delta < 0 and tick_cumulatives_delta % seconds_ago != 0:
+        arithmetic_mean_tick -= 1
+
+    seconds_ago_x160 = seconds_ago * MAX_UINT160
+    harmonic_mean_liquidity = (
+        seconds_ago_x160 // (seconds_per_liquidity_delta << 32)
+    ) & MAX_UINT128
+    return arithmetic_mean_tick, harmonic_mean_liquidity
+
+
+def consult(
+    observations: Sequence[Observation],
+    seconds_ago: int,
+    time: int,
+    tick: int,
+    liquidity: int,
+) -> Tuple[int, int]:
+    """
+    Offline equivalent of OracleLibrary.consult(pool, seconds_ago), returning the
+    time-weighted (arithmeticMeanTick, harmonicMeanLiquidity) over the last
+    `seconds_ago` seconds before `time`, using integer math only
+    """
+    tick_cumulatives, seconds_per_liquidity = observe(
+        observations, time, [seconds_ago, 0], tick, liquidity
+    )
+    return consult_cumulatives(tick_cumulatives, seconds_per_liquidity, seconds_ago)
@@ -0,0 +1 @@
+from .permit import *
\ No newline at end of file
@@ -0,0 +1,215 @@
+from typing import Optional, Tuple, Union
+from eth_utils import keccak
+from ..abi import abi_encode
+from ..constants import MAX_UINT160
+from ..signing import sign_digest, signature_to_bytes
+
+__all__ = [
+    "PERMIT2_ADDRESS",
+    "EIP712_DOMAIN_TYPEHASH",
+    "EIP712_DOMAIN_NO_VERSION_TYPEHASH",
+    "ERC2612_PERMIT_TYPEHASH",
+    "PERMIT2_PERMIT_DETAILS_TYPEHASH",
+    "PERMIT2_PERMIT_SINGLE_TYPEHASH",
+    "PERMIT2_TOKEN_PERMISSIONS_TYPEHASH",
+    "PERMIT2_TRANSFER_FROM_TYPEHASH",
+    "eip712_domain_separator",
+    "eip712_digest",
+    "erc2612_digest",
+    "permit2_digest",
+    "permit2_transfer_digest",
+    "sign_erc2612_permit",
+    "sign_permit2",
+]
+
+PERMIT2_ADDRESS = "0x000000000022D473030F116dDEE9F6B43aC78BA3"
+
+EIP712_DOMAIN_TYPEHASH = keccak(
+    text="EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"
+)
+# Permit2 omits the version field from its domain
+EIP712_DOMAIN_NO_VERSION_TYPEHASH = keccak(
+    text="EIP712Domain(string name,uint256 chainId,address verifyingContract)"
+)
+
+ERC2612_PERMIT_TYPEHASH = keccak(
+    text="Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)"
+)
+
+_PERMIT_DETAILS_TYPE = (
+    "PermitDetails(address token,uint160 amount,uint48 expiration,uint48 nonce)"
+)
+PERMIT2_PERMIT_DETAILS_TYPEHASH = keccak(text=_PERMIT_DETAILS_TYPE)
+PERMIT2_PERMIT_SINGLE_TYPEHASH = keccak(
+    text="PermitSingle(PermitDetails details,address spender,uint256 sigDeadline)"
+    + _PERMIT_DETAILS_TYPE
+)
+
+_TOKEN_PERMISSIONS_TYPE = "TokenPermissions(address token,uint256 amount)"
+PERMIT2_TOKEN_PERMISSIONS_TYPEHASH = keccak(text=_TOKEN_PERMISSIONS_TYPE)
+PERMIT2_TRANSFER_FROM_TYPEHASH = keccak(
+    text="PermitTransferFrom(TokenPermissions permitted,address spender,uint256 nonce,"
+    "uint256 deadline)" + _TOKEN_PERMISSIONS_TYPE
+)
+
+
+def eip712_domain_separator(
+    name: str,
+    chain_id: int,
+    verifying_contract: str,
+    version: Optional[str] = None,
+) -> bytes:
+    """
+    Builds the EIP-712 domain separator for a (name, version, chainId,
+    verifyingContract) domain, or a (name, chainId, verifyingContract) domain if no
+    version is given
+    """
+    if version is None:
+        return keccak(
+            abi_encode(
+                ["bytes32", "bytes32", "uint256", "address"],
+                [
+                    EIP712_DOMAIN_NO_VERSION_TYPEHASH,
+                    keccak(text=name),
+                    chain_id,
+                    verifying_contract,
+                ],
+            )
+        )
+    return keccak(
+        abi_encode(
+            ["bytes32", "bytes32", "bytes32", "uint256", "address"],
+            [
+                EIP712_DOMAIN_TYPEHASH,
+                keccak(text=name),
+                keccak(text=version),
+                chain_id,
+                verifying_contract,
+            ],
+        )
+    )
+
+
+def eip712_digest(domain_separator: bytes, struct_hash: bytes) -> bytes:
+    return keccak(b"\x19\x01" + bytes(domain_separator) + bytes(struct_hash))
+
+
+def erc2612_digest(
+    token_address: str,
+    chain_id: int,
+    owner: str,
+    spender: str,
+    value: int,
+    nonce: int,
+    deadline: int,
+    token_name: Optional[str] = None,
+    version: str = "1",
+    domain_separator: Optional[bytes] = None,
+) -> bytes:
+    """
+    Builds the digest signed for an ERC-2612 permit. The domain is derived from the
+    token name and version unless `domain_separator` is given, which is the safer
+    choice for tokens with a non-standard domain (read it from DOMAIN_SEPARATOR())
+    """
+    if domain_separator is None:
+        assert (
+            token_name is not None
+        ), "A token name or a domain separator must be provided"
+        domain_separator = eip712_domain_separator(
+            token_name, chain_id, token_address, version
+        )
+    struct_hash = keccak(
+        abi_encode(
+            ["bytes32", "address", "address", "uint256", "uint256", "uint256"],
+            [ERC2612_PERMIT_TYPEHASH, owner, spender, value, nonce, deadline],
+        )
+    )
+    return eip712_digest(domain_separator, struct_hash)
+
+
+def permit2_digest(
+    chain_id: int,
+    token: str,
+    amount: int,
+    expiration: int,
+    nonce: int,
+    spender: str,
+    sig_deadline: int,
+    permit2_address: str = PERMIT2_ADDRESS,
+) -> bytes:
+    """
+    Builds the digest signed for a Permit2 AllowanceTransfer PermitSingle, granting
+    `spender` an allowance of `amount` until `expiration`
+    """
+    assert 0 <= amount <= MAX_UINT160, "Permit2 allowances are limited to uint160"
+    assert 0 <= expiration < 2 ** 48, "Permit2 expirations are limited to uint48"
+    assert 0 <= nonce < 2 ** 48, "Permit2 allowance nonces are limited to uint48"
+
+    details_hash = keccak(
+        abi_encode(
+            ["bytes32", "address", "uint160", "uint48", "uint48"],
+            [PERMIT2_PERMIT_DETAILS_TYPEHASH, token, amount, expiration, nonce],
+        )
+    )
+    struct_hash = keccak(
+        abi_encode(
+            ["bytes32", "bytes32", "address", "uint256"],
+            [PERMIT2_PERMIT_SINGLE_TYPEHASH, details_hash, spender, sig_deadline],
+        )
+    )
+    return eip712_digest(
+        eip712_domain_separator("Permit2", chain_id, permit2_address), struct_hash
+    )
+
+
+def permit2_transfer_digest(
+    chain_id: int,
+    token: str,
+    amount: int,
+    spender: str,
+    nonce: int,
+    deadline: int,
+    permit2_address: str = PERMIT2_ADDRESS,
+) -> bytes:
+    """
+    Builds the digest signed for a Permit2 SignatureTransfer PermitTransferFrom, a
+    one-time transfer of `amount` by `spender` (the contract calling Permit2)
+    """
+    permissions_hash = keccak(
+        abi_encode(
+            ["bytes32", "address", "uint256"],
+            [PERMIT2_TOKEN_PERMISSIONS_TYPEHASH, token, amount],
+        )
+    )
+    struct_hash = keccak(
+        abi_encode(
+            ["bytes32", "bytes32", "address", "uint256", "uint256"],
+            [
+                PERMIT2_TRANSFER_FROM_TYPEHASH,
+                permissions_hash,
+                spender,
+                nonce,
+                deadline,
+            ],
+        )
+    )
+    return eip712_digest(
+        eip712_domain_separator("Permit2", chain_id, permit2_address), struct_hash
+    )
+
+
+def sign_erc2612_permit(
+    private_key: Union[bytes, str, int], digest: bytes
+) -> Tuple[int, bytes, bytes]:
+    """
+    Signs an ERC-2612 permit digest, returning (v, r, s) as passed to permit()
+    """
+    v, r, s = sign_digest(private_key, digest)
+    return v, r.to_bytes(32, "big"), s.to_bytes(32, "big")
+
+
+def sign_permit2(private_key: Union[bytes, str, int], digest: bytes) -> bytes:
+    """
+    Signs a Permit2 digest, returning the 65-byte signature passed to Permit2
+    """
+    return signature_to_bytes(*sign_digest(private_key, digest))
@@ -0,0 +1,4 @@
+from .pool_state import *
+from .curve_pool_state import *
+from .solidly_pool_state import *
+from .checkpoint import *
\ No newline at end of file
@@ -0,0 +1,59 @@
+from dataclasses import dataclass
+from typing import Any, Dict
+
+__all__ = ["PoolCheckpoint", "CheckpointMixin"]
+
+
+@dataclass(frozen=True)
+class PoolCheckpoint:
+    """
+    Opaque token returned by checkpoint(), restoring the pool it was taken from
+    """
+
+    pool_id: int
+    state: Dict[str, Any]
+
+
+def _copy_lists(state: Dict[str, Any]) -> Dict[str, Any]:
+    # lists (Curve balances) are mutated in place, everything else is replaced
+    return {
+        name: list(value) if isinstance(value, list) else value
+        for name, value in state.items()
+    }
+
+
+class CheckpointMixin:
+    """
+    Cheap save/restore of a pool state, for branching simulations (e.g. with and
+    without a pending transaction applied) without copying the pool
+    """
+
+    def checkpoint(self) -> PoolCheckpoint:
+        """
+        Saves the current state. The returned token can be restored any number of
+        times, and only by the pool that created it
+        """
+        return PoolCheckpoint(id(self), self._checkpoint_state())
+
+    def restore(self, checkpoint: PoolCheckpoint) -> None:
+        """
+        Returns the pool to the state saved by `checkpoint`, in place
+        """
+        assert checkpoint.pool_id == id(self), "Checkpoint belongs to another pool"
+        state = vars(self)
+        state.clear()
+        state.update(self._restored_state(checkpoint.state))
+
+    def branch(self) -> Any:
+        """
+        Returns an independent copy for speculative updates. Pools with large
+        internal structures share them with the branch until either side modifies
+        them
+        """
+        return self.copy()
+
+    def _checkpoint_state(self) -> Dict[str, Any]:
+        return _copy_lists(vars(self))
+
+    def _restored_state(self, state: Dict[str, Any]) -> Dict[str, Any]:
+        return _copy_lists(state)
@@ -0,0 +1,485 @@
+import copy
+from typing import List, Optional
+from .checkpoint import CheckpointMixin
+from ..exceptions import SwapSimulationError
+from ..curve_math import (
+    CURVE_FEE_DENOMINATOR,
+    CURVE_MAX_ITERATIONS,
+    crypto_xp,
+    dynamic_fee,
+    geometric_mean,
+    halfpow,
+    newton_d,
+    newton_y,
+    sqrt_int,
+)
+
+__all__ = ["PRECISION", "CurveStableswapPoolState", "CurveCryptoPoolState"]
+
+# Port of the Curve StableSwap (3pool) exchange math
+PRECISION = 10 ** 18
+
+
+def _smallest_input(sufficient, estimate: int) -> int:
+    """
+    Smallest input for which `sufficient` holds, starting from an estimate of the
+    reverse solution. The estimate differs from the forward rounding by a few units,
+    so it is bracketed with exponential steps and bisected
+    """
+    step = 1
+    if sufficient(estimate):
+        low, high = estimate, estimate
+        while low > 0 and sufficient(low):
+            high, low = low, max(0, estimate - step)
+            step *= 2
+        if sufficient(low):
+            return low
+    else:
+        low, high = estimate, estimate + step
+        while not sufficient(high):
+            low, high = high, estimate + step
+            step *= 2
+    # invariant: sufficient(high) and not sufficient(low)
+    while high - low > 1:
+        middle = (low + high) // 2
+        if sufficient(middle):
+            high = middle
+        else:
+            low = middle
+    return high
+
+
+class CurveStableswapPoolState(CheckpointMixin):
+    """
+    Offline state of a Curve StableSwap pool. `amp` is the amplification coefficient
+    as returned by A() and `fee` uses Curve's 1e10 denominator (4000000 = 0.04%).
+    Balances are raw token amounts, normalized to 18 decimals internally. The
+    `admin_fee` share of each swap fee is withdrawn from the balances on exchange
+    """
+
+    def __init__(
+        self,
+        address: str,
+        balances: List[int],
+        decimals: List[int],
+        amp: int,
+        fee: int,
+        admin_fee: int = 5_000_000_000,
+    ) -> None:
+        assert len(balances) == len(decimals), "One decimal count per coin is required"
+        self.address = address
+        self.balances = list(balances)
+        self.decimals = list(decimals)
+        self.amp = amp
+        self.fee = fee
+        self.admin_fee = admin_fee
+        self.block_number = None
+
+    def __repr__(self) -> str:
+        return f"CurveStableswapPoolState({self.address}, balances={self.balances})"
+
+    def copy(self) -> "CurveStableswapPoolState":
+        new = copy.copy(self)
+        new.balances = list(self.balances)
+        return new
+
+    @property
+    def _rates(self) -> List[int]:
+        return [PRECISION * 10 ** (18 - decimals) for decimals in self.decimals]
+
+    def _xp(self) -> List[int]:
+        return [
+            rate * balance // PRECISION
+            for rate, balance in zip(self._rates, self.balances)
+        ]
+
+    def _get_d(self, xp: List[int]) -> int:
+        n_coins = len(xp)
+        total = sum(xp)
+        if total == 0:
+            return 0
+        d = total
+        ann = self.amp * n_coins
+        for _ in range(CURVE_MAX_ITERATIONS):
+            d_p = d
+            for x in xp:
+                d_p = d_p * d // (x * n_coins)
+            d_prev = d
+            d = (ann * total + d_p * n_coins) * d // (
+                (ann - 1) * d + (n_coins + 1) * d_p
+            )
+            if abs(d - d_prev) <= 1:
+                return d
+        raise ArithmeticError("Curve invariant D did not converge")
+
+    def _get_y(self, i: int, j: int, x: int, xp: List[int]) -> int:
+        n_coins = len(xp)
+        assert i != j and 0 <= i < n_coins and 0 <= j < n_coins, "Invalid coin indices"
+        d = self._get_d(xp)
+        c = d
+        total = 0
+        ann = self.amp * n_coins
+        for k in range(n_coins):
+            if k == i:
+                x_k = x
+            elif k != j:
+                x_k = xp[k]
+            else:
+                continue
+            total += x_k
+            c = c * d // (x_k * n_coins)
+        c = c * d // (ann * n_coins)
+        b = total + d // ann
+        y = d
+        for _ in range(CURVE_MAX_ITERATIONS):
+            y_prev = y
+            y = (y * y + c) // (2 * y + b - d)
+            if abs(y - y_prev) <= 1:
+                return y
+        raise ArithmeticError("Curve invariant y did not converge")
+
+    def get_dy(self, i: int, j: int, dx: int) -> int:
+        """
+        Output of coin `j` for an input of `dx` of coin `i`, as the pool's get_dy
+        """
+        rates = self._rates
+        xp = self._xp()
+        x = xp[i] + dx * rates[i] // PRECISION
+        y = self._get_y(i, j, x, xp)
+        dy = (xp[j] - y - 1) * PRECISION // rates[j]
+        return dy - self.fee * dy // CURVE_FEE_DENOMINATOR
+
+    def exchange(self, i: int, j: int, dx: int) -> int:
+        """
+        Applies an exchange of `dx` of coin `i` for coin `j` to the balances, as the
+        pool's exchange, and returns the output
+        """
+        rates = self._rates
+        xp = self._xp()
+        x = xp[i] + dx * rates[i] // PRECISION
+        y = self._get_y(i, j, x, xp)
+        dy = xp[j] - y - 1
+        dy_fee = dy * self.fee // CURVE_FEE_DENOMINATOR
+        dy_admin_fee = dy_fee * self.admin_fee // CURVE_FEE_DENOMINATOR
+        dy = (dy - dy_fee) * PRECISION // rates[j]
+        dy_admin_fee = dy_admin_fee * PRECISION // rates[j]
+        self.balances[i] += dx
+        self.balances[j] -= dy + dy_admin_fee
+        return dy
+
+    def get_dx(self, i: int, j: int, dy: int) -> int:
+        """
+        Smallest input of coin `i` for which get_dy returns at least `dy` of coin `j`
+        """
+        rates = self._rates
+        xp = self._xp()
+        dy_with_fee = -(
+            -dy * CURVE_FEE_DENOMINATOR // (CURVE_FEE_DENOMINATOR - self.fee)
+        )
+        y = xp[j] - (dy_with_fee * rates[j] // PRECISION + 1)
+        assert y > 0, "Insufficient liquidity for the output"
+        x = self._get_y(j, i, y, xp)
+        estimate = max(0, (x - xp[i]) * PRECISION // rates[i])
+        return _smallest_input(lambda dx: self.get_dy(i, j, dx) >= dy, estimate)
+
+
+class CurveCryptoPoolState(CheckpointMixin):
+    """
+    Offline state of a Curve CryptoSwap pool (tricrypto). `ann` and `gamma` are the
+    pool's A() and gamma(), `d` its stored D, and `price_scale` the price of every
+    coin after the first in units of coin 0 (1e18 precision). Fees use Curve's 1e10
+    denominator.
+
+    Quoting only needs the balances, the invariant parameters, the price scale and the
+    fee parameters. Exchanges also re-run the pool's tweak_price, which moves the
+    moving average oracle and the price scale, so the remaining state must be loaded
+    for sequences of exchanges to stay exact. Ramping of A and gamma is not modeled,
+    so exchanges that would lower the virtual price revert with "Loss" as they do
+    outside a ramp. Liquidity changes are not modeled either, but the admin fee claim
+    they start with is, as claim_admin_fees
+    """
+
+    def __init__(
+        self,
+        address: str,
+        balances: List[int],
+        decimals: List[int],
+        ann: int,
+        gamma: int,
+        d: int,
+        price_scale: List[int],
+        mid_fee: int,
+        out_fee: int,
+        fee_gamma: int,
+        price_oracle: Optional[List[int]] = None,
+        last_prices: Optional[List[int]] = None,
+        last_prices_timestamp: int = 0,
+        ma_half_time: int = 600,
+        adjustment_step: int = 0,
+        allowed_extra_profit: int = 0,
+        xcp_profit: int = 10 ** 18,
+        virtual_price: int = 10 ** 18,
+        total_supply: int = 0,
+        not_adjusted: bool = False,
+        xcp_profit_a: int = 10 ** 18,
+        admin_fee: int = 5 * 10 ** 9,
+    ) -> None:
+        assert len(balances) == len(decimals), "One decimal count per coin is required"
+        assert (
+            len(price_scale) == len(balances) - 1
+        ), "One price per coin after the first is required"
+        self.address = address
+        self.balances = list(balances)
+        self.decimals = list(decimals)
+        self.ann = ann
+        self.gamma = gamma
+        self.d = d
+        self.price_scale = list(price_scale)
+        self.mid_fee = mid_fee
+        self.out_fee = out_fee
+        self.fee_gamma = fee_gamma
+        self.price_oracle = list(price_oracle or price_scale)
+        self.last_prices = list(last_prices or price_scale)
+        self.last_prices_timestamp = last_prices_timestamp
+        self.ma_half_time = ma_half_time
+        self.adjustment_step = adjustment_step
+        self.allowed_extra_profit = allowed_extra_profit
+        self.xcp_profit = xcp_profit
+        self.virtual_price = virtual_price
+        self.total_supply = total_supply
+        self.not_adjusted = not_adjusted
+        # xcp_profit at the last admin fee claim, and the admin share of the profit
+        # since (1e10 units)
+        self.xcp_profit_a = xcp_profit_a
+        self.admin_fee = admin_fee
+        self.block_number = None
+
+    def __repr__(self) -> str:
+        return (
+            f"CurveCryptoPoolState({self.address}, balances={self.balances}, "
+            f"price_scale={self.price_scale})"
+        )
+
+    def copy(self) -> "CurveCryptoPoolState":
+        new = copy.copy(self)
+        for name in ("balances", "price_scale", "price_oracle", "last_prices"):
+            setattr(new, name, list(getattr(self, name)))
+        return new
+
+    @property
+    def _precisions(self) -> List[int]:
+        return [10 ** (18 - decimals) for decimals in self.decimals]
+
+    def _xcp(self, d: int, price_scale: List[int]) -> int:
+        # value of the pool's balanced position at invariant `d`
+        n_coins = len(self.balances)
+        balanced = [d // n_coins] + [
+            d * 10 ** 18 // (n_coins * price) for price in price_scale
+        ]
+        return geometric_mean(balanced, True)
+
+    def fee(self) -> int:
+        """
+        The current dynamic fee, in 1e10 units
+        """
+        xp = crypto_xp(self.balances, self._precisions, self.price_scale)
+        return dynamic_fee(xp, self.mid_fee, self.out_fee, self.fee_gamma)
+
+    def _swap(self, i: int, j: int, dx: int):
+        n_coins = len(self.balances)
+        assert i != j and 0 <= i < n_coins and 0 <= j < n_coins, "Invalid coin indices"
+        precisions = self._precisions
+        balances = list(self.balances)
+        balances[i] += dx
+        xp = crypto_xp(balances, precisions, self.price_scale)
+
+        dy = xp[j] - newton_y(self.ann, self.gamma, xp, self.d, j)
+        xp[j] -= dy
+        dy -= 1
+        if j > 0:
+            dy = dy * 10 ** 18 // self.price_scale[j - 1]
+        dy //= precisions[j]
+        dy -= dynamic_fee(xp, self.mid_fee, self.out_fee, self.fee_gamma) * dy // (
+            CURVE_FEE_DENOMINATOR
+        )
+        return dy, balances, xp
+
+    def get_dy(self, i: int, j: int, dx: int) -> int:
+        """
+        Output of coin `j` for an input of `dx` of coin `i`, as the pool's get_dy
+        """
+        dy, _, _ = self._swap(i, j, dx)
+        return dy
+
+    def get_dx(self, i: int, j: int, dy: int) -> int:
+        """
+        Smallest input of coin `i` for which get_dy returns at least `dy` of coin `j`
+        """
+        n_coins = len(self.balances)
+        assert i != j and 0 <= i < n_coins and 0 <= j < n_coins, "Invalid coin indices"
+        precisions = self._precisions
+        xp = crypto_xp(self.balances, precisions, self.price_scale)
+        fee = dynamic_fee(xp, self.mid_fee, self.out_fee, self.fee_gamma)
+        dy_with_fee = -(-dy * CURVE_FEE_DENOMINATOR // (CURVE_FEE_DENOMINATOR - fee))
+        scaled_dy = (dy_with_fee + 1) * precisions[j]
+        if j > 0:
+            scaled_dy = scaled_dy * self.price_scale[j - 1] // 10 ** 18
+        assert scaled_dy < xp[j], "Insufficient liquidity for the output"
+        xp[j] -= scaled_dy
+        dx = newton_y(self.ann, self.gamma, xp, self.d, i) - xp[i]
+        if i > 0:
+            dx = dx * 10 ** 18 // self.price_scale[i - 1]
+        estimate = max(0, dx // precisions[i])
+        return _smallest_input(lambda dx: self.get_dy(i, j, dx) >= dy, estimate)
+
+    def exchange(self, i: int, j: int, dx: int, timestamp: Optional[int] = None) -> int:
+        """
+        Applies an exchange of `dx` of coin `i` for coin `j`, as the pool's exchange at
+        block `timestamp`, and returns the output. The balances change as in get_dy,
+        after which the oracle, the price scale and D are updated by tweak_price
+        """
+        dy, balances, xp = self._swap(i, j, dx)
+        balances[j] -= dy
+
+        precisions = self._precisions
+        y = balances[j] * precisions[j]
+        if j > 0:
+            y = y * self.price_scale[j - 1] // 10 ** 18
+        xp[j] = y
+
+        # the price paid, in units of coin 0, for the oracle
+        p = 0
+        ix = j
+        if dx > 10 ** 5 and dy > 10 ** 5:
+            scaled_dx = dx * precisions[i]
+            scaled_dy = dy * precisions[j]
+            if i != 0 and j != 0:
+                p = self.last_prices[i - 1] * scaled_dx // scaled_dy
+            elif i == 0:
+                p = scaled_dx * 10 ** 18 // scaled_dy
+            else:
+                p = scaled_dy * 10 ** 18 // scaled_dx
+                ix = i
+
+        # raises before modifying the state if the exchange reverts
+        self._tweak_price(xp, ix, p, timestamp)
+        self.balances = balances
+        return dy
+
+    def _tweak_price(self, xp: List[int], i: int, p_i: int, timestamp: int) -> None:
+        n_coins = len(self.balances)
+        d_unadjusted = newton_d(self.ann, self.gamma, xp)
+        price_scale = self.price_scale
+
+        # profit numbers without price adjustment, checked before anything changes
+        old_xcp_profit = self.xcp_profit
+        old_virtual_price = self.virtual_price
+        xcp_profit = 10 ** 18
+        virtual_price = 10 ** 18
+        if old_virtual_price > 0 and self.total_supply > 0:
+            virtual_price = (
+                10 ** 18 * self._xcp(d_unadjusted, price_scale) // self.total_supply
+            )
+            xcp_profit = old_xcp_profit * virtual_price // old_virtual_price
+            if virtual_price < old_virtual_price:
+                raise SwapSimulationError("Loss")
+
+        if timestamp is not None and self.last_prices_timestamp < timestamp:
+            alpha = halfpow(
+                (timestamp - self.last_prices_timestamp) * 10 ** 18 // self.ma_half_time
+            )
+            self.price_oracle = [
+                (last * (10 ** 18 - alpha) + oracle * alpha) // 10 ** 18
+                for last, oracle in zip(self.last_prices, self.price_oracle)
+            ]
+            self.last_prices_timestamp = timestamp
+
+        if p_i > 0:
+            if i > 0:
+                self.last_prices[i - 1] = p_i
+            else:
+                # a new price of coin 0 moves all the others
+                self.last_prices = [
+                    last * 10 ** 18 // p_i for last in self.last_prices
+                ]
+        else:
+            shifted = list(xp)
+            dx_price = shifted[0] // 10 ** 6
+            shifted[0] += dx_price
+            self.last_prices = [
+                price_scale[k]
+                * dx_price
+                // (
+                    xp[k + 1]
+                    - newton_y(self.ann, self.gamma, shifted, d_unadjusted, k + 1)
+                )
+                for k in range(n_coins - 1)
+            ]
+
+        self.xcp_profit = xcp_profit
+
+        if not self.not_adjusted and (
+            virtual_price * 2 - 10 ** 18 > xcp_profit + 2 * self.allowed_extra_profit
+        ):
+            self.not_adjusted = True
+
+        if self.not_adjusted:
+            step = self.adjustment_step
+            norm = 0
+            for oracle, scale in zip(self.price_oracle, price_scale):
+                norm += (abs(oracle * 10 ** 18 // scale - 10 ** 18)) ** 2
+
+            if norm > step ** 2 and old_virtual_price > 0 and self.total_supply > 0:
+                norm = sqrt_int(norm // 10 ** 18)
+                p_new = [
+                    (scale * (norm - step) + step * oracle) // norm
+                    for oracle, scale in zip(self.price_oracle, price_scale)
+                ]
+                adjusted = [xp[0]] + [
+                    xp[k + 1] * p_new[k] // price_scale[k] for k in range(n_coins - 1)
+                ]
+                d = newton_d(self.ann, self.gamma, adjusted)
+                new_virtual_price = 10 ** 18 * self._xcp(d, p_new) // self.total_supply
+                # only move the price scale if enough profit is left afterwards
+                if (
+                    new_virtual_price > 10 ** 18
+                    and 2 * new_virtual_price - 10 ** 18 > xcp_profit
+                ):
+                    self.price_scale = p_new
+                    self.d = d
+                    self.virtual_price = new_virtual_price
+                    return
+                self.not_adjusted = False
+
+        self.d = d_unadjusted
+        self.virtual_price = virtual_price
+
+    def claim_admin_fees(self) -> int:
+        """
+        Applies the pool's claim_admin_fees: the admin share of the profit made since
+        the last claim is minted as LP tokens to the fee receiver, lowering
+        xcp_profit, and D and the virtual price are recomputed from the balances.
+        Returns the LP tokens minted. The pool also runs this first in add_liquidity
+        and remove_liquidity_one_coin
+        """
+        assert self.total_supply > 0, "Admin fees cannot be claimed without LP tokens"
+        xcp_profit = self.xcp_profit
+        minted = 0
+        if xcp_profit > self.xcp_profit_a:
+            fees = (xcp_profit - self.xcp_profit_a) * self.admin_fee // (2 * 10 ** 10)
+            if fees > 0:
+                virtual_price = self.virtual_price
+                frac = virtual_price * 10 ** 18 // (virtual_price - fees) - 10 ** 18
+                minted = self.total_supply * frac // 10 ** 18
+                self.total_supply += minted
+                xcp_profit -= 2 * fees
+                self.xcp_profit = xcp_profit
+
+        xp = crypto_xp(self.balances, self._precisions, self.price_scale)
+        self.d = newton_d(self.ann, self.gamma, xp)
+        self.virtual_price = (
+            10 ** 18 * self._xcp(self.d, self.price_scale) // self.total_supply
+        )
+        if xcp_profit > self.xcp_profit_a:
+            self.xcp_profit_a = xcp_profit
+        return minted
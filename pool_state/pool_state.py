@@ -0,0 +1,337 @@
+import copy
+from dataclasses import dataclass
+from fractions import Fraction
+from typing import Dict, List, Optional, Union
+from ..log import get_logger
+from .checkpoint import CheckpointMixin
+from ..constants import MAX_TICK, MIN_TICK
+from ..uniswap_v3 import ExchangeVariant, V3SwapResult, simulate_v3_swap
+
+__all__ = [
+    "V2SyncEvent",
+    "V3SwapEvent",
+    "V3MintEvent",
+    "V3BurnEvent",
+    "PoolEvent",
+    "V2PoolState",
+    "V3TickInfo",
+    "V3PoolState",
+    "validate_v3_tick_map",
+]
+
+_logger = get_logger("pool_state")
+
+
+@dataclass(frozen=True)
+class V2SyncEvent:
+    pool: str
+    block_number: int
+    log_index: int
+    reserve0: int
+    reserve1: int
+
+
+@dataclass(frozen=True)
+class V3SwapEvent:
+    pool: str
+    block_number: int
+    log_index: int
+    amount0: int
+    amount1: int
+    sqrt_price_x96: int
+    liquidity: int
+    tick: int
+
+
+@dataclass(frozen=True)
+class V3MintEvent:
+    pool: str
+    block_number: int
+    log_index: int
+    tick_lower: int
+    tick_upper: int
+    amount: int
+
+
+@dataclass(frozen=True)
+class V3BurnEvent:
+    pool: str
+    block_number: int
+    log_index: int
+    tick_lower: int
+    tick_upper: int
+    amount: int
+
+
+PoolEvent = Union[V2SyncEvent, V3SwapEvent, V3MintEvent, V3BurnEvent]
+
+
+class V2PoolState(CheckpointMixin):
+    """
+    Offline state of a Uniswap V2-style pool, updated from Sync events and quoted with
+    the same integer math as the pair contract
+    """
+
+    def __init__(
+        self,
+        address: str,
+        reserve0: int = 0,
+        reserve1: int = 0,
+        # default fee for most UniswapV2 AMMs is 0.3%
+        fee: Fraction = Fraction(3, 1000),
+    ) -> None:
+        self.address = address
+        self.reserve0 = reserve0
+        self.reserve1 = reserve1
+        self.fee = fee
+        self.block_number = None
+
+    def __repr__(self) -> str:
+        return (
+            f"V2PoolState({self.address}, reserve0={self.reserve0}, "
+            f"reserve1={self.reserve1})"
+        )
+
+    def copy(self) -> "V2PoolState":
+        return copy.copy(self)
+
+    def apply_event(self, event: PoolEvent) -> None:
+        assert isinstance(
+            event, V2SyncEvent
+        ), f"V2 pools cannot apply {type(event).__name__}"
+        self.reserve0 = event.reserve0
+        self.reserve1 = event.reserve1
+        self.block_number = event.block_number
+
+    def _reserves(self, zero_for_one: bool):
+        if zero_for_one:
+            return self.reserve0, self.reserve1
+        return self.reserve1, self.reserve0
+
+    def get_amount_out(self, amount_in: int, zero_for_one: bool) -> int:
+        """
+        Calculates the output for an exact input, swapping token0 for token1 if
+        `zero_for_one` is set, otherwise token1 for token0
+        """
+        reserves_in, reserves_out = self._reserves(zero_for_one)
+        amount_in_with_fee = amount_in * (self.fee.denominator - self.fee.numerator)
+        numerator = amount_in_with_fee * reserves_out
+        denominator = reserves_in * self.fee.denominator + amount_in_with_fee
+        return numerator // denominator
+
+    def swap(self, amount_in: int, zero_for_one: bool) -> int:
+        """
+        Applies an exact-input swap to the reserves and returns the output
+        """
+        amount_out = self.get_amount_out(amount_in, zero_for_one)
+        if zero_for_one:
+            self.reserve0 += amount_in
+            self.reserve1 -= amount_out
+        else:
+            self.reserve1 += amount_in
+            self.reserve0 -= amount_out
+        return amount_out
+
+    def get_amount_in(self, amount_out: int, zero_for_one: bool) -> int:
+        """
+        Calculates the input required for an exact output
+        """
+        reserves_in, reserves_out = self._reserves(zero_for_one)
+        assert amount_out < reserves_out, "Insufficient liquidity for the output"
+        numerator = reserves_in * amount_out * self.fee.denominator
+        denominator = (reserves_out - amount_out) * (
+            self.fee.denominator - self.fee.numerator
+        )
+        return numerator // denominator + 1
+
+
+@dataclass
+class V3TickInfo:
+    liquidity_gross: int = 0
+    liquidity_net: int = 0
+
+
+class V3PoolState(CheckpointMixin):
+    """
+    Offline state of a Uniswap V3-style pool: the current price, tick and in-range
+    liquidity plus the initialized ticks, updated from Swap, Mint and Burn events
+    """
+
+    def __init__(
+        self,
+        address: str,
+        fee: int,
+        tick_spacing: int,
+        sqrt_price_x96: int = 0,
+        tick: int = 0,
+        liquidity: int = 0,
+        ticks: Optional[Dict[int, V3TickInfo]] = None,
+        variant: ExchangeVariant = ExchangeVariant.UNISWAP_V3,
+    ) -> None:
+        self.address = address
+        # fee in hundredths of a bip, e.g. 3000 for 0.3%
+        self.fee = fee
+        self.tick_spacing = tick_spacing
+        self.sqrt_price_x96 = sqrt_price_x96
+        self.tick = tick
+        self.liquidity = liquidity
+        self.ticks: Dict[int, V3TickInfo] = ticks if ticks is not None else {}
+        # the fork of the V3 contracts, which decides how fees are charged
+        self.variant = variant
+        # slot0's feeProtocol, packed as the variant's pool does. For Uniswap, the
+        # denominator of the protocol's share of the fees in the low 4 bits for token0
+        # and the high 4 bits for token1 (0 if disabled)
+        self.fee_protocol = 0
+        self.block_number = None
+        # set while the tick map is shared with a checkpoint, so that it is copied
+        # before the next modification instead of at every checkpoint
+        self._ticks_shared = False
+
+    def __repr__(self) -> str:
+        return (
+            f"V3PoolState({self.address}, sqrt_price_x96={self.sqrt_price_x96}, "
+            f"tick={self.tick}, liquidity={self.liquidity}, ticks={len(self.ticks)})"
+        )
+
+    def copy(self) -> "V3PoolState":
+        new = copy.copy(self)
+        new.ticks = {
+            tick: V3TickInfo(info.liquidity_gross, info.liquidity_net)
+            for tick, info in self.ticks.items()
+        }
+        new._ticks_shared = False
+        return new
+
+    def branch(self) -> "V3PoolState":
+        new = copy.copy(self)
+        self._ticks_shared = new._ticks_shared = True
+        return new
+
+    def _checkpoint_state(self):
+        self._ticks_shared = True
+        return dict(vars(self))
+
+    def _restored_state(self, state):
+        return {**state, "_ticks_shared": True}
+
+    def _update_position(self, tick_lower: int, tick_upper: int, amount: int) -> None:
+        assert tick_lower < tick_upper, "tick_lower must be below tick_upper"
+        if self._ticks_shared:
+            self.ticks = {
+                tick: V3TickInfo(info.liquidity_gross, info.liquidity_net)
+                for tick, info in self.ticks.items()
+            }
+            self._ticks_shared = False
+        for tick, net in ((tick_lower, amount), (tick_upper, -amount)):
+            info = self.ticks.setdefault(tick, V3TickInfo())
+            info.liquidity_gross += amount
+            info.liquidity_net += net
+            assert info.liquidity_gross >= 0, f"Negative gross liquidity at tick {tick}"
+            if not info.liquidity_gross:
+                del self.ticks[tick]
+                _logger.debug("%s: tick %d uninitialized", self.address, tick)
+        if tick_lower <= self.tick < tick_upper:
+            self.liquidity += amount
+
+    def apply_event(self, event: PoolEvent) -> None:
+        if isinstance(event, V3SwapEvent):
+            self.sqrt_price_x96 = event.sqrt_price_x96
+            self.liquidity = event.liquidity
+            self.tick = event.tick
+        elif isinstance(event, V3MintEvent):
+            self._update_position(event.tick_lower, event.tick_upper, event.amount)
+        elif isinstance(event, V3BurnEvent):
+            self._update_position(event.tick_lower, event.tick_upper, -event.amount)
+        else:
+            raise TypeError(f"V3 pools cannot apply {type(event).__name__}")
+        self.block_number = event.block_number
+
+    def simulate_swap(
+        self,
+        amount_specified: int,
+        zero_for_one: bool,
+        sqrt_price_limit_x96: Optional[int] = None,
+    ) -> V3SwapResult:
+        """
+        Simulates a swap without modifying the pool. A positive `amount_specified` is
+        an exact input, negative an exact output
+        """
+        return simulate_v3_swap(
+            self, amount_specified, zero_for_one, sqrt_price_limit_x96
+        )
+
+    def get_amount_out(self, amount_in: int, zero_for_one: bool) -> int:
+        """
+        Calculates the output for an exact input, swapping token0 for token1 if
+        `zero_for_one` is set, otherwise token1 for token0
+        """
+        result = self.simulate_swap(amount_in, zero_for_one)
+        return -(result.amount1 if zero_for_one else result.amount0)
+
+    def get_amount_in(self, amount_out: int, zero_for_one: bool) -> int:
+        """
+        Calculates the input required for an exact output
+        """
+        result = self.simulate_swap(-amount_out, zero_for_one)
+        received = -(result.amount1 if zero_for_one else result.amount0)
+        assert received == amount_out, "Insufficient liquidity for the output"
+        return result.amount0 if zero_for_one else result.amount1
+
+    def swap(
+        self,
+        amount_specified: int,
+        zero_for_one: bool,
+        sqrt_price_limit_x96: Optional[int] = None,
+    ) -> V3SwapResult:
+        """
+        Applies a swap to the pool's price, tick and liquidity and returns the result
+        """
+        result = self.simulate_swap(
+            amount_specified, zero_for_one, sqrt_price_limit_x96
+        )
+        self.sqrt_price_x96 = result.sqrt_price_x96
+        self.tick = result.tick
+        self.liquidity = result.liquidity
+        return result
+
+
+def validate_v3_tick_map(pool_state: V3PoolState) -> List[str]:
+    """
+    Checks the initialized ticks of a V3 pool state for inconsistencies that would
+    make swap simulation wrong, usually caused by missed or misordered events.
+    Returns a description of each problem found (an empty list if the map is valid)
+    """
+    problems = []
+    liquidity = 0
+    for tick in sorted(pool_state.ticks):
+        info = pool_state.ticks[tick]
+        if not MIN_TICK <= tick <= MAX_TICK:
+            problems.append(f"tick {tick} is outside the tick range")
+        if tick % pool_state.tick_spacing:
+            problems.append(
+                f"tick {tick} is not a multiple of the tick spacing "
+                f"{pool_state.tick_spacing}"
+            )
+        if info.liquidity_gross <= 0:
+            problems.append(f"tick {tick} has no gross liquidity")
+        if abs(info.liquidity_net) > info.liquidity_gross:
+            problems.append(f"tick {tick} has a net liquidity above its gross")
+        liquidity += info.liquidity_net
+        if liquidity < 0:
+            problems.append(f"liquidity becomes negative above tick {tick}")
+
+    if liquidity != 0:
+        problems.append(f"net liquidity of all ticks sums to {liquidity}, not zero")
+
+    active_liquidity = sum(
+        info.liquidity_net
+        for tick, info in pool_state.ticks.items()
+        if tick <= pool_state.tick
+    )
+    if active_liquidity != pool_state.liquidity:
+        problems.append(
+            f"in-range liquidity {pool_state.liquidity} does not match the "
+            f"{active_liquidity} implied by the ticks at or below "
+            f"tick {pool_state.tick}"
+        )
+    return problems
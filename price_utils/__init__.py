@@ -0,0 +1 @@
+from .price_utils import *
\ No newline at end of file
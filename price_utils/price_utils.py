@@ -0,0 +1,117 @@
+import math
+from fractions import Fraction
+from typing import List, Sequence, Tuple, Union
+from ..pool_state import V2PoolState, V3PoolState
+from ..constants import BPS_DENOMINATOR, MAX_SQRT_RATIO, MIN_SQRT_RATIO
+from ..uniswap_v3 import V3_FEE_DENOMINATOR
+
+__all__ = [
+    "spot_price",
+    "price_impact",
+    "amount_out_min",
+    "amount_in_max",
+    "sqrt_price_limit_from_slippage",
+    "sqrt_price_limits",
+]
+
+
+def spot_price(
+    pool_state: Union[V2PoolState, V3PoolState], zero_for_one: bool
+) -> Fraction:
+    """
+    Returns the marginal price of the swap direction, excluding fees: units of the
+    output token per unit of the input token
+    """
+    if isinstance(pool_state, V2PoolState):
+        price = Fraction(pool_state.reserve1, pool_state.reserve0)
+    elif isinstance(pool_state, V3PoolState):
+        price = Fraction(pool_state.sqrt_price_x96 ** 2, 2 ** 192)
+    else:
+        raise TypeError(f"Unsupported pool state {type(pool_state).__name__}")
+    return price if zero_for_one else 1 / price
+
+
+def _fee(pool_state: Union[V2PoolState, V3PoolState]) -> Fraction:
+    if isinstance(pool_state, V2PoolState):
+        return pool_state.fee
+    return Fraction(pool_state.fee, V3_FEE_DENOMINATOR)
+
+
+def price_impact(
+    pool_state: Union[V2PoolState, V3PoolState], amount_in: int, zero_for_one: bool
+) -> Fraction:
+    """
+    Returns the price impact of an exact-input swap as a fraction (e.g. 1/100 for
+    1%): the shortfall of the output versus trading the post-fee input at the spot
+    price. The LP fee is excluded, so a negligible trade has an impact close to zero
+    """
+    assert amount_in > 0, "Input amount must be positive"
+    amount_out = pool_state.get_amount_out(amount_in, zero_for_one)
+    ideal_out = amount_in * (1 - _fee(pool_state)) * spot_price(
+        pool_state, zero_for_one
+    )
+    return 1 - amount_out / ideal_out
+
+
+def amount_out_min(amount_out: int, slippage_bps: int) -> int:
+    """
+    Returns the minimum output to accept for a quoted output and a slippage tolerance
+    in basis points, rounded down
+    """
+    assert 0 <= slippage_bps <= BPS_DENOMINATOR, "Slippage must be in [0, 10000] bps"
+    return amount_out * (BPS_DENOMINATOR - slippage_bps) // BPS_DENOMINATOR
+
+
+def amount_in_max(amount_in: int, slippage_bps: int) -> int:
+    """
+    Returns the maximum input to allow for a quoted input and a slippage tolerance in
+    basis points, rounded up
+    """
+    assert slippage_bps >= 0, "Slippage must be non-negative"
+    return -(-amount_in * (BPS_DENOMINATOR + slippage_bps) // BPS_DENOMINATOR)
+
+
+def sqrt_price_limit_from_slippage(
+    current_sqrt_price: int, bps: int, zero_for_one: bool
+) -> int:
+    """
+    Returns the sqrtPriceLimitX96 that stops a V3 swap once the pool price has moved
+    by `bps` basis points: down for zero_for_one swaps, up otherwise. The limit is
+    rounded towards the current price and clamped to the valid range
+    """
+    assert 0 <= bps < BPS_DENOMINATOR, "Slippage must be in [0, 10000) bps"
+    squared = current_sqrt_price ** 2
+    if zero_for_one:
+        limit_squared = squared * (BPS_DENOMINATOR - bps)
+        limit = math.isqrt(-(-limit_squared // BPS_DENOMINATOR))
+        if limit * limit * BPS_DENOMINATOR < limit_squared:
+            limit += 1
+        return max(limit, MIN_SQRT_RATIO + 1)
+    limit = math.isqrt(squared * (BPS_DENOMINATOR + bps) // BPS_DENOMINATOR)
+    return min(limit, MAX_SQRT_RATIO - 1)
+
+
+def sqrt_price_limits(
+    pools: Sequence[V3PoolState],
+    slippage_bps: Union[int, Sequence[int]],
+) -> List[Tuple[int, int]]:
+    """
+    Computes the slippage-protected sqrtPriceLimitX96 of both swap directions for a
+    list of V3-style pools, as (zero_for_one limit, one_for_zero limit) in the order
+    of the pools. `slippage_bps` is a tolerance shared by all pools or one per pool
+    """
+    if isinstance(slippage_bps, int):
+        slippage_bps = [slippage_bps] * len(pools)
+    assert len(slippage_bps) == len(pools), "One slippage tolerance per pool required"
+
+    limits = []
+    for pool_state, bps in zip(pools, slippage_bps):
+        if not hasattr(pool_state, "sqrt_price_x96"):
+            raise TypeError(f"Unsupported pool state {type(pool_state).__name__}")
+        limits.append(
+            (
+                sqrt_price_limit_from_slippage(pool_state.sqrt_price_x96, bps, True),
+                sqrt_price_limit_from_slippage(pool_state.sqrt_price_x96, bps, False),
+            )
+        )
+    return limits
@@ -0,0 +1 @@
+from .proofs import *
\ No newline at end of file
@@ -0,0 +1,262 @@
+from typing import Dict, List, Optional, Tuple, Union
+from eth_utils import keccak, to_checksum_address
+from ..exceptions import ProofVerificationError
+from ..rlp import rlp_decode, rlp_decode_int, rlp_encode
+
+__all__ = [
+    "EMPTY_TRIE_ROOT",
+    "EMPTY_CODE_HASH",
+    "NodeCache",
+    "verify_mpt_proof",
+    "verify_account_proof",
+    "verify_storage_proof",
+    "verify_get_proof_response",
+    "verify_get_proof_responses",
+]
+
+# root hash of a trie with no entries: keccak256(rlp(b""))
+EMPTY_TRIE_ROOT = keccak(rlp_encode(b""))
+EMPTY_CODE_HASH = keccak(b"")
+
+# decoded trie nodes shared by the proofs of a batch, keyed by their encoding
+NodeCache = Dict[bytes, Tuple[bytes, list]]
+
+
+def _to_bytes(value: Union[bytes, str]) -> bytes:
+    if isinstance(value, str):
+        value = value[2:] if value.startswith("0x") else value
+        if len(value) % 2:
+            value = "0" + value
+        return bytes.fromhex(value)
+    return bytes(value)
+
+
+def _to_int(value: Union[int, str]) -> int:
+    return value if isinstance(value, int) else int(value, 16)
+
+
+def _nibbles(data: bytes) -> List[int]:
+    nibbles = []
+    for byte in data:
+        nibbles.append(byte >> 4)
+        nibbles.append(byte & 0x0F)
+    return nibbles
+
+
+def _decode_hex_prefix(encoded_path: bytes):
+    """
+    Decodes a compact (hex-prefix) encoded path into (nibbles, is_leaf)
+    """
+    nibbles = _nibbles(encoded_path)
+    flag = nibbles[0]
+    if flag > 3:
+        raise ProofVerificationError(f"Invalid hex-prefix flag {flag}")
+    is_leaf = bool(flag & 2)
+    return nibbles[1:] if flag & 1 else nibbles[2:], is_leaf
+
+
+def verify_mpt_proof(
+    root: Union[bytes, str],
+    key: Union[bytes, str],
+    proof: List[Union[bytes, str]],
+    node_cache: Optional[NodeCache] = None,
+) -> Optional[bytes]:
+    """
+    Walks a Merkle-Patricia proof from `root` along keccak256(key), checking the hash of
+    every node on the path. Returns the RLP-encoded value stored at the key, or None if
+    the proof shows the key is absent.
+
+    Raises ProofVerificationError if the proof is malformed or does not match the root.
+    Proofs sharing a `node_cache` hash and decode each distinct node only once
+    """
+
+    root = _to_bytes(root)
+    proof = [_to_bytes(node) for node in proof]
+    path = _nibbles(keccak(_to_bytes(key)))
+
+    if not proof:
+        if root == EMPTY_TRIE_ROOT:
+            return None
+        raise ProofVerificationError("Empty proof for a non-empty trie")
+
+    expected_hash = root
+    proof_index = 0
+    position = 0
+    node = None
+    value = None
+
+    while True:
+        if node is None:
+            if proof_index == len(proof):
+                raise ProofVerificationError("Proof ended before reaching the key")
+            encoded_node = proof[proof_index]
+            proof_index += 1
+            cached = None if node_cache is None else node_cache.get(encoded_node)
+            if cached is None:
+                cached = keccak(encoded_node), rlp_decode(encoded_node)
+                if node_cache is not None:
+                    node_cache[encoded_node] = cached
+            node_hash, node = cached
+            if node_hash != expected_hash:
+                raise ProofVerificationError(
+                    f"Hash mismatch at proof node {proof_index - 1}"
+                )
+
+        if len(node) == 17:
+            if position == len(path):
+                value = node[16] or None
+                break
+            child = node[path[position]]
+            position += 1
+        elif len(node) == 2:
+            node_path, is_leaf = _decode_hex_prefix(node[0])
+            if path[position : position + len(node_path)] != node_path:
+                # the key diverges from this node's path, so it is not in the trie
+                break
+            position += len(node_path)
+            if is_leaf:
+                if position == len(path):
+                    value = node[1]
+                break
+            child = node[1]
+        else:
+            raise ProofVerificationError(f"Invalid trie node with {len(node)} items")
+
+        if isinstance(child, list):
+            # nodes shorter than 32 bytes are embedded in their parent
+            node = child
+        elif child == b"":
+            break
+        elif len(child) == 32:
+            expected_hash = child
+            node = None
+        else:
+            raise ProofVerificationError(
+                f"Invalid child reference of {len(child)} bytes"
+            )
+
+    if proof_index != len(proof):
+        raise ProofVerificationError(
+            f"Proof has {len(proof) - proof_index} unused nodes"
+        )
+
+    return value
+
+
+def verify_account_proof(
+    state_root: Union[bytes, str],
+    address: str,
+    account_proof: List[Union[bytes, str]],
+    node_cache: Optional[NodeCache] = None,
+) -> Optional[Dict]:
+    """
+    Verifies an account proof against a state root. Returns the proven account
+    (nonce, balance, storageHash, codeHash) or None if the account does not exist
+    """
+
+    encoded_account = verify_mpt_proof(
+        state_root, _to_bytes(address), account_proof, node_cache
+    )
+    if encoded_account is None:
+        return None
+
+    nonce, balance, storage_hash, code_hash = rlp_decode(encoded_account)
+    return {
+        "nonce": rlp_decode_int(nonce),
+        "balance": rlp_decode_int(balance),
+        "storageHash": bytes(storage_hash),
+        "codeHash": bytes(code_hash),
+    }
+
+
+def verify_storage_proof(
+    storage_root: Union[bytes, str],
+    slot: Union[int, bytes, str],
+    storage_proof: List[Union[bytes, str]],
+    node_cache: Optional[NodeCache] = None,
+) -> int:
+    """
+    Verifies a storage proof against an account's storage root and returns the proven
+    value of the slot (zero if the slot is unset)
+    """
+
+    if isinstance(slot, int):
+        slot = slot.to_bytes(32, "big")
+    else:
+        slot = _to_bytes(slot).rjust(32, b"\x00")
+
+    encoded_value = verify_mpt_proof(storage_root, slot, storage_proof, node_cache)
+    if encoded_value is None:
+        return 0
+    return rlp_decode_int(rlp_decode(encoded_value))
+
+
+def verify_get_proof_response(
+    state_root: Union[bytes, str],
+    response: Dict,
+    node_cache: Optional[NodeCache] = None,
+) -> Dict:
+    """
+    Verifies an eth_getProof response against a trusted state root (e.g. from a block
+    header obtained separately). Every account field and storage value claimed by the
+    response is checked against the proofs.
+
+    Returns a dict with the checksummed address, verified account fields and a mapping
+    of storage slot -> value. Raises ProofVerificationError if any claim does not match
+    """
+
+    address = to_checksum_address(response["address"])
+    account = verify_account_proof(
+        state_root, address, response["accountProof"], node_cache
+    )
+
+    if account is None:
+        account = {
+            "nonce": 0,
+            "balance": 0,
+            "storageHash": EMPTY_TRIE_ROOT,
+            "codeHash": EMPTY_CODE_HASH,
+        }
+
+    claimed = {
+        "nonce": _to_int(response["nonce"]),
+        "balance": _to_int(response["balance"]),
+        "storageHash": _to_bytes(response["storageHash"]),
+        "codeHash": _to_bytes(response["codeHash"]),
+    }
+    for field, value in claimed.items():
+        if account[field] != value:
+            raise ProofVerificationError(
+                f"{address}: proven {field} does not match the response"
+            )
+
+    storage = {}
+    for storage_proof in response.get("storageProof", []):
+        slot = _to_int(storage_proof["key"])
+        value = verify_storage_proof(
+            account["storageHash"], slot, storage_proof["proof"], node_cache
+        )
+        if value != _to_int(storage_proof["value"]):
+            raise ProofVerificationError(
+                f"{address}: proven value of slot {hex(slot)} differs"
+            )
+        storage[slot] = value
+
+    return {"address": address, **account, "storage": storage}
+
+
+def verify_get_proof_responses(
+    state_root: Union[bytes, str],
+    responses: List[Dict],
+) -> List[Dict]:
+    """
+    Verifies several eth_getProof responses taken at the same block. The account
+    proofs of one block share their upper trie nodes (and storage proofs of the same
+    contract their storage nodes), so each distinct node is hashed and decoded once
+    for the whole batch
+    """
+    node_cache: NodeCache = {}
+    return [
+        verify_get_proof_response(state_root, response, node_cache)
+        for response in responses
+    ]
@@ -0,0 +1,4 @@
+from .http_provider import *
+from .ipc_provider import *
+from .ws_provider import *
+from .fallback_provider import *
\ No newline at end of file
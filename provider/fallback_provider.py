@@ -0,0 +1,223 @@
+import threading
+import time
+from dataclasses import dataclass
+from typing import Any, Callable, List, Optional, Sequence, Tuple, Union
+import requests
+from ..exceptions import RpcResponseError
+from ..log import get_logger
+from ..metrics import increment_counter
+from ..rpc.retry import RetryPolicy
+from .http_provider import HttpProvider, JsonRpcMethods
+
+__all__ = ["EndpointStats", "FallbackProvider"]
+
+_logger = get_logger("provider")
+
+_AUTH_STATUS_CODES = (401, 403)
+
+
+@dataclass(frozen=True)
+class EndpointStats:
+    url: str
+    requests: int
+    errors: int
+    # moving averages of the request latency in seconds and of the failure rate
+    latency: Optional[float]
+    error_rate: float
+    consecutive_failures: int
+    # monotonic time until which the endpoint is skipped, None if it is not
+    quarantined_until: Optional[float]
+
+
+class _Endpoint:
+    def __init__(self, provider: HttpProvider) -> None:
+        self.provider = provider
+        self.requests = 0
+        self.errors = 0
+        self.latency: Optional[float] = None
+        self.error_rate = 0.0
+        self.consecutive_failures = 0
+        self.quarantined_until: Optional[float] = None
+
+    def score(self) -> float:
+        # untried endpoints sort first, so that every endpoint gets measured, and
+        # endpoints that never answered sort last
+        if self.latency is None:
+            return float("inf") if self.errors else 0.0
+        return self.latency / (1 - min(self.error_rate, 0.99))
+
+
+class FallbackProvider(JsonRpcMethods):
+    """
+    JSON-RPC provider spreading requests over several HTTP endpoints. Each request
+    goes to the healthy endpoint with the best latency (a moving average, penalized
+    by the endpoint's recent error rate) and fails over to the next one on transport
+    errors, retryable or authentication HTTP statuses, and retryable JSON-RPC errors.
+    Other errors, such as reverts or a 400 for a malformed request, are raised without
+    failing over.
+
+    An endpoint failing `quarantine_after` times in a row is skipped for
+    `quarantine_time` seconds, then tried again. If every endpoint is quarantined,
+    they are still tried in order of quarantine expiry rather than failing outright.
+
+    Endpoints given as URLs are created as HttpProviders without retries, since
+    failing over is faster than retrying the same endpoint. Per-endpoint statistics
+    are returned by `stats`
+    """
+
+    def __init__(
+        self,
+        endpoints: Sequence[Union[str, HttpProvider]],
+        quarantine_after: int = 3,
+        quarantine_time: float = 30.0,
+        smoothing: float = 0.2,
+        clock: Callable[[], float] = time.monotonic,
+        **provider_kwargs: Any,
+    ) -> None:
+        assert endpoints, "At least one endpoint is required"
+        assert quarantine_after >= 1, "The quarantine threshold must be at least 1"
+        assert 0 < smoothing <= 1, "The smoothing factor must be in (0, 1]"
+        provider_kwargs.setdefault("retry_policy", RetryPolicy(max_retries=0))
+        self._endpoints = [
+            _Endpoint(
+                HttpProvider(endpoint, **provider_kwargs)
+                if isinstance(endpoint, str)
+                else endpoint
+            )
+            for endpoint in endpoints
+        ]
+        self.quarantine_after = quarantine_after
+        self.quarantine_time = quarantine_time
+        self.smoothing = smoothing
+        self._clock = clock
+        self._lock = threading.Lock()
+
+    def __str__(self) -> str:
+        return ", ".join(str(endpoint.provider) for endpoint in self._endpoints)
+
+    def close(self) -> None:
+        for endpoint in self._endpoints:
+            endpoint.provider.close()
+
+    def _ranked(self) -> List[_Endpoint]:
+        now = self._clock()
+        with self._lock:
+            healthy = [
+                endpoint
+                for endpoint in self._endpoints
+                if endpoint.quarantined_until is None
+                or endpoint.quarantined_until <= now
+            ]
+            quarantined = [
+                endpoint for endpoint in self._endpoints if endpoint not in healthy
+            ]
+            healthy.sort(key=_Endpoint.score)
+            quarantined.sort(key=lambda endpoint: endpoint.quarantined_until)
+        return healthy + quarantined
+
+    def _record(self, endpoint: _Endpoint, latency: float, failed: bool) -> None:
+        with self._lock:
+            endpoint.requests += 1
+            endpoint.error_rate += self.smoothing * (failed - endpoint.error_rate)
+            if failed:
+                endpoint.errors += 1
+                endpoint.consecutive_failures += 1
+                if endpoint.consecutive_failures >= self.quarantine_after:
+                    endpoint.quarantined_until = self._clock() + self.quarantine_time
+                    increment_counter("rpc_fallback_quarantines")
+                    _logger.warning(
+                        "FallbackProvider: quarantining %s for %.0fs after %d failures",
+                        endpoint.provider,
+                        self.quarantine_time,
+                        endpoint.consecutive_failures,
+                    )
+                return
+            endpoint.consecutive_failures = 0
+            endpoint.quarantined_until = None
+            endpoint.latency = (
+                latency
+                if endpoint.latency is None
+                else endpoint.latency + self.smoothing * (latency - endpoint.latency)
+            )
+
+    @staticmethod
+    def _endpoint_failure(provider: HttpProvider, error: Exception) -> bool:
+        if isinstance(error, RpcResponseError):
+            return provider.retry_policy.retry_rpc_error(error.code)
+        if isinstance(error, requests.exceptions.HTTPError):
+            if error.response is None:
+                return True
+            # a rejected API key is specific to the endpoint, but other client errors
+            # such as a malformed request would fail on every endpoint
+            status_code = error.response.status_code
+            return status_code in _AUTH_STATUS_CODES or (
+                provider.retry_policy.retry_status(status_code)
+            )
+        return isinstance(
+            error,
+            (requests.exceptions.ConnectionError, requests.exceptions.Timeout),
+        )
+
+    def _dispatch(self, function: Callable[[HttpProvider], object]):
+        last_error = None
+        for endpoint in self._ranked():
+            start = self._clock()
+            try:
+                result = function(endpoint.provider)
+            except Exception as e:
+                if not self._endpoint_failure(endpoint.provider, e):
+                    # the endpoint answered, the request itself failed
+                    self._record(endpoint, self._clock() - start, False)
+                    raise
+                self._record(endpoint, self._clock() - start, True)
+                increment_counter("rpc_fallback_failovers")
+                _logger.info(
+                    "FallbackProvider: request to %s failed (%s)", endpoint.provider, e
+                )
+                last_error = e
+                continue
+            self._record(endpoint, self._clock() - start, False)
+            return result
+        raise last_error
+
+    def request(self, method: str, params: Optional[List] = None) -> Any:
+        """
+        Sends a JSON-RPC request to the best available endpoint and returns its result
+        """
+        return self._dispatch(lambda provider: provider.request(method, params))
+
+    def batch_request(
+        self,
+        calls: List[Tuple[str, List]],
+        raise_on_error: bool = True,
+    ) -> List:
+        """
+        Sends a batch of (method, params) calls to the best available endpoint
+        """
+        return self._dispatch(
+            lambda provider: provider.batch_request(calls, raise_on_error)
+        )
+
+    def stats(self) -> List[EndpointStats]:
+        """
+        Returns a snapshot of every endpoint's statistics, in configuration order
+        """
+        now = self._clock()
+        with self._lock:
+            return [
+                EndpointStats(
+                    url=str(endpoint.provider),
+                    requests=endpoint.requests,
+                    errors=endpoint.errors,
+                    latency=endpoint.latency,
+                    error_rate=endpoint.error_rate,
+                    consecutive_failures=endpoint.consecutive_failures,
+                    quarantined_until=(
+                        endpoint.quarantined_until
+                        if endpoint.quarantined_until is not None
+                        and endpoint.quarantined_until > now
+                        else None
+                    ),
+                )
+                for endpoint in self._endpoints
+            ]
@@ -0,0 +1,378 @@
+import asyncio
+import functools
+import json
+import threading
+import time
+from concurrent.futures import FIRST_COMPLETED, ThreadPoolExecutor, wait
+from typing import Any, Callable, Dict, List, Optional, Tuple, Union
+import requests
+from requests.adapters import HTTPAdapter
+from ..abi import decode_revert
+from ..log import get_logger
+from ..metrics import increment_counter, timed_metric
+from ..exceptions import EvmRevertError, RpcResponseError
+from ..rpc import (
+    Log,
+    Receipt,
+    encode_state_overrides,
+    parse_logs,
+    parse_receipts,
+    parse_rpc_batch_response,
+    parse_rpc_response,
+)
+from ..rpc.rate_limiter import RateLimiter
+from ..rpc.retry import RetryPolicy
+
+__all__ = ["JsonRpcMethods", "HttpProvider"]
+
+_logger = get_logger("provider")
+
+
+def _block_identifier(block: Union[int, str]) -> str:
+    return hex(block) if isinstance(block, int) else block
+
+
+def _revert_error(error: RpcResponseError) -> Optional[EvmRevertError]:
+    """
+    Converts the error of a reverted eth_call to an EvmRevertError. Nodes report
+    reverts with code 3 and the payload in `data`, or with an "execution reverted"
+    message when there is no payload. Other errors return None
+    """
+    if error.code != 3 and "execution reverted" not in str(error.message).lower():
+        return None
+    data = b""
+    if isinstance(error.data, str):
+        try:
+            data = bytes.fromhex(
+                error.data[2:] if error.data.startswith("0x") else error.data
+            )
+        except ValueError:
+            pass
+    revert = decode_revert(data)
+    message = "execution reverted"
+    if revert.kind != "empty":
+        message += f": {revert}"
+    return EvmRevertError(message, data)
+
+
+class JsonRpcMethods:
+    """
+    Typed wrappers of common JSON-RPC methods, for providers implementing `request`
+    (and `batch_request` for the awaitable batch)
+    """
+
+    def chain_id(self) -> int:
+        return int(self.request("eth_chainId"), 16)
+
+    def block_number(self) -> int:
+        return int(self.request("eth_blockNumber"), 16)
+
+    def get_balance(self, address: str, block: Union[int, str] = "latest") -> int:
+        return int(
+            self.request("eth_getBalance", [address, _block_identifier(block)]), 16
+        )
+
+    def get_transaction_count(
+        self, address: str, block: Union[int, str] = "latest"
+    ) -> int:
+        return int(
+            self.request(
+                "eth_getTransactionCount", [address, _block_identifier(block)]
+            ),
+            16,
+        )
+
+    def get_storage_at(
+        self, address: str, slot: int, block: Union[int, str] = "latest"
+    ) -> int:
+        return int(
+            self.request(
+                "eth_getStorageAt", [address, hex(slot), _block_identifier(block)]
+            ),
+            16,
+        )
+
+    def call(
+        self,
+        to: str,
+        data: Union[bytes, str],
+        block: Union[int, str] = "latest",
+        state_overrides: Optional[Dict] = None,
+        sender: Optional[str] = None,
+    ) -> bytes:
+        """
+        Executes eth_call and returns the raw return data. State overrides are given in
+        the format accepted by encode_state_overrides. A revert raises EvmRevertError
+        with the revert payload, see decode_revert for custom errors
+        """
+        transaction = {
+            "to": to,
+            "data": data if isinstance(data, str) else "0x" + bytes(data).hex(),
+        }
+        if sender:
+            transaction["from"] = sender
+        params = [transaction, _block_identifier(block)]
+        if state_overrides:
+            params.append(encode_state_overrides(state_overrides))
+        try:
+            result = self.request("eth_call", params)
+        except RpcResponseError as e:
+            revert = _revert_error(e)
+            if revert is None:
+                raise
+            raise revert from e
+        return bytes.fromhex(result[2:])
+
+    def get_logs(self, log_filter: Dict) -> List[Log]:
+        log_filter = dict(log_filter)
+        for key in ("fromBlock", "toBlock"):
+            if key in log_filter:
+                log_filter[key] = _block_identifier(log_filter[key])
+        return parse_logs(self.request("eth_getLogs", [log_filter]))
+
+    def get_block_receipts(self, block: Union[int, str] = "latest") -> List[Receipt]:
+        return parse_receipts(
+            self.request("eth_getBlockReceipts", [_block_identifier(block)])
+        )
+
+    async def _run_async(self, function, *args, **kwargs):
+        loop = asyncio.get_running_loop()
+        return await loop.run_in_executor(
+            None, functools.partial(function, *args, **kwargs)
+        )
+
+    async def request_async(self, method: str, params: Optional[List] = None) -> Any:
+        """
+        Awaitable version of request, run in the event loop's default executor
+        """
+        return await self._run_async(self.request, method, params)
+
+    async def batch_request_async(
+        self,
+        calls: List[Tuple[str, List]],
+        raise_on_error: bool = True,
+    ) -> List:
+        return await self._run_async(self.batch_request, calls, raise_on_error)
+
+    async def call_async(
+        self,
+        to: str,
+        data: Union[bytes, str],
+        block: Union[int, str] = "latest",
+        state_overrides: Optional[Dict] = None,
+        sender: Optional[str] = None,
+    ) -> bytes:
+        return await self._run_async(
+            self.call, to, data, block, state_overrides, sender
+        )
+
+    async def get_logs_async(self, log_filter: Dict) -> List[Log]:
+        return await self._run_async(self.get_logs, log_filter)
+
+    async def get_block_receipts_async(
+        self, block: Union[int, str] = "latest"
+    ) -> List[Receipt]:
+        return await self._run_async(self.get_block_receipts, block)
+
+
+class HttpProvider(JsonRpcMethods):
+    """
+    JSON-RPC provider over HTTP(S), holding a pool of keep-alive connections to a single
+    endpoint. Requests that fail at the transport level, or with a retryable HTTP
+    status or JSON-RPC error code, are retried following `retry_policy` (by default
+    an exponential backoff from `retry_backoff`, up to `max_retries` times). Other
+    JSON-RPC errors are raised as RpcResponseError, and errors returned in place by
+    batch_request(raise_on_error=False) are never retried.
+
+    With a `rate_limiter`, every request waits for its share of the endpoint's quota,
+    counting each call of a batch and each retry
+    """
+
+    def __init__(
+        self,
+        url: str,
+        timeout: float = 10.0,
+        max_retries: int = 3,
+        retry_backoff: float = 0.25,
+        pool_size: int = 10,
+        headers: Optional[Dict[str, str]] = None,
+        rate_limiter: Optional[RateLimiter] = None,
+        retry_policy: Optional[RetryPolicy] = None,
+    ) -> None:
+        self.url = url
+        self.timeout = timeout
+        self.retry_policy = retry_policy or RetryPolicy(
+            max_retries=max_retries, backoff=retry_backoff
+        )
+        self.rate_limiter = rate_limiter
+
+        self._next_id = 1
+        self._id_lock = threading.Lock()
+        self._session = requests.Session()
+        adapter = HTTPAdapter(pool_connections=pool_size, pool_maxsize=pool_size)
+        self._session.mount("http://", adapter)
+        self._session.mount("https://", adapter)
+        self._session.headers.update({"Content-Type": "application/json"})
+        if headers:
+            self._session.headers.update(headers)
+
+        self._hedge_session = None
+        self._hedge_executor = None
+        if self.retry_policy.hedge_url is not None:
+            self._hedge_session = requests.Session()
+            self._hedge_session.headers.update(self._session.headers)
+            self._hedge_executor = ThreadPoolExecutor(
+                max_workers=2 * pool_size, thread_name_prefix=f"HttpProvider({url})"
+            )
+
+    def __str__(self) -> str:
+        return self.url
+
+    def close(self) -> None:
+        self._session.close()
+        if self._hedge_session is not None:
+            self._hedge_session.close()
+            self._hedge_executor.shutdown(wait=False)
+
+    def _reserve_ids(self, count: int) -> int:
+        """
+        Reserves a contiguous block of request ids and returns the first one
+        """
+        with self._id_lock:
+            first_id = self._next_id
+            self._next_id += count
+        return first_id
+
+    def _post_to(self, session: requests.Session, url: str, body: bytes) -> bytes:
+        increment_counter("rpc_http_requests")
+        with timed_metric("rpc_http_request_seconds"):
+            response = session.post(url, data=body, timeout=self.timeout)
+        if self.retry_policy.retry_status(response.status_code):
+            raise requests.exceptions.HTTPError(
+                f"HTTP {response.status_code}", response=response
+            )
+        response.raise_for_status()
+        return response.content
+
+    def _post_hedged(self, body: bytes) -> bytes:
+        """
+        Posts to the endpoint and, if it has not answered within the hedging delay,
+        to the hedge endpoint too, returning the first successful response
+        """
+        policy = self.retry_policy
+        primary = self._hedge_executor.submit(
+            self._post_to, self._session, self.url, body
+        )
+        done, _ = wait([primary], timeout=policy.hedge_after)
+        if done:
+            return primary.result()
+
+        increment_counter("rpc_http_hedged_requests")
+        hedge = self._hedge_executor.submit(
+            self._post_to, self._hedge_session, policy.hedge_url, body
+        )
+        pending = {primary, hedge}
+        while True:
+            done, pending = wait(pending, return_when=FIRST_COMPLETED)
+            for future in done:
+                if future.exception() is None:
+                    for other in pending:
+                        other.cancel()
+                    return future.result()
+            if not pending:
+                # both failed, report the primary endpoint's error
+                return primary.result()
+
+    def _post(
+        self,
+        body: bytes,
+        cost: int = 1,
+        parse: Optional[Callable[[bytes], object]] = None,
+    ):
+        """
+        Posts a request, retrying as the retry policy allows, and returns the parsed
+        response (the raw content without `parse`)
+        """
+        policy = self.retry_policy
+        attempt = 0
+        while True:
+            if self.rate_limiter is not None:
+                self.rate_limiter.acquire(cost)
+            try:
+                if self._hedge_executor is not None:
+                    content = self._post_hedged(body)
+                else:
+                    content = self._post_to(self._session, self.url, body)
+                return parse(content) if parse is not None else content
+            except (
+                requests.exceptions.ConnectionError,
+                requests.exceptions.Timeout,
+                requests.exceptions.HTTPError,
+                RpcResponseError,
+            ) as e:
+                if isinstance(e, RpcResponseError):
+                    retryable = policy.retry_rpc_error(e.code)
+                elif isinstance(e, requests.exceptions.HTTPError):
+                    retryable = e.response is not None and policy.retry_status(
+                        e.response.status_code
+                    )
+                else:
+                    retryable = True
+                if not retryable or attempt >= policy.max_retries:
+                    raise
+                attempt += 1
+                increment_counter("rpc_http_retries")
+                _logger.info(
+                    "HttpProvider: request to %s failed (%s), retry %d of %d",
+                    self.url,
+                    e,
+                    attempt,
+                    policy.max_retries,
+                )
+                time.sleep(policy.delay(attempt))
+
+    def request(self, method: str, params: Optional[List] = None) -> Any:
+        """
+        Sends a single JSON-RPC request and returns the raw (JSON-decoded) result
+        """
+        body = json.dumps(
+            {
+                "jsonrpc": "2.0",
+                "id": self._reserve_ids(1),
+                "method": method,
+                "params": params or [],
+            }
+        ).encode()
+        return self._post(body, parse=parse_rpc_response)
+
+    def batch_request(
+        self,
+        calls: List[Tuple[str, List]],
+        raise_on_error: bool = True,
+    ) -> List:
+        """
+        Sends several (method, params) calls in one HTTP request and returns their
+        results in the same order as the calls
+        """
+        if not calls:
+            return []
+        first_id = self._reserve_ids(len(calls))
+        ids = list(range(first_id, first_id + len(calls)))
+        body = json.dumps(
+            [
+                {
+                    "jsonrpc": "2.0",
+                    "id": request_id,
+                    "method": method,
+                    "params": params or [],
+                }
+                for request_id, (method, params) in zip(ids, calls)
+            ]
+        ).encode()
+        return self._post(
+            body,
+            len(calls),
+            functools.partial(
+                parse_rpc_batch_response, raise_on_error=raise_on_error, ids=ids
+            ),
+        )
@@ -0,0 +1,258 @@
+import asyncio
+import codecs
+import functools
+import json
+import queue
+import socket
+import threading
+from collections import OrderedDict
+from concurrent.futures import Future
+from typing import Any, Callable, Dict, List, Optional, Tuple
+from ..exceptions import RpcResponseError
+from ..log import get_logger
+from ..rpc import parse_transaction
+
+__all__ = ["IpcProvider"]
+
+_logger = get_logger("provider")
+
+
+class IpcProvider:
+    """
+    JSON-RPC provider over a geth/reth IPC socket. A background thread reads the socket
+    continuously, resolving request responses and routing subscription notifications
+    to per-subscription queues, so pending transactions can be consumed as soon as the
+    node announces them
+    """
+
+    MAX_EARLY_NOTIFICATIONS = 1_000
+    MAX_EARLY_SUBSCRIPTIONS = 16
+
+    def __init__(
+        self,
+        path: str,
+        timeout: float = 10.0,
+    ) -> None:
+        self.path = path
+        self.timeout = timeout
+
+        self._socket = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+        self._socket.connect(path)
+        self._write_lock = threading.Lock()
+        self._state_lock = threading.Lock()
+        self._next_id = 1
+        self._pending_requests: Dict[int, Future] = {}
+        self._subscriptions: Dict[str, Tuple[queue.Queue, Callable]] = {}
+        # notifications that arrive before eth_subscribe has returned their id. Ids
+        # that never get registered (or arrive after unsubscribing) are evicted
+        # oldest first, so the buffer stays bounded
+        self._early_notifications: "OrderedDict[str, List]" = OrderedDict()
+        self._closed = False
+
+        self._reader = threading.Thread(
+            target=self._read_loop, name=f"IpcProvider({path})", daemon=True
+        )
+        self._reader.start()
+
+    def __str__(self) -> str:
+        return self.path
+
+    def close(self) -> None:
+        self._closed = True
+        try:
+            self._socket.shutdown(socket.SHUT_RDWR)
+        except OSError:
+            pass
+        self._socket.close()
+
+    def _read_loop(self) -> None:
+        decoder = json.JSONDecoder()
+        utf8_decoder = codecs.getincrementaldecoder("utf-8")(errors="replace")
+        buffer = ""
+        while not self._closed:
+            try:
+                chunk = self._socket.recv(1 << 16)
+            except OSError:
+                break
+            if not chunk:
+                break
+            buffer += utf8_decoder.decode(chunk)
+
+            # messages are concatenated JSON objects, possibly split across reads
+            while buffer:
+                buffer = buffer.lstrip()
+                try:
+                    message, end = decoder.raw_decode(buffer)
+                except ValueError as e:
+                    newline = buffer.find("\n")
+                    if newline == -1:
+                        # incomplete, wait for the rest of the message
+                        break
+                    # nodes write compact JSON, which cannot contain a raw newline, so
+                    # a message that does not parse up to one is malformed
+                    _logger.warning(
+                        "IpcProvider: discarding malformed message from %s: %s",
+                        self.path,
+                        e,
+                    )
+                    buffer = buffer[newline + 1 :]
+                    continue
+                buffer = buffer[end:]
+                try:
+                    self._dispatch(message)
+                except Exception:
+                    # a bad message must not stop the reader, every later request
+                    # would wait for its response in vain
+                    _logger.exception(
+                        "IpcProvider: failed to handle message from %s", self.path
+                    )
+
+        if not self._closed:
+            _logger.warning("IpcProvider: socket %s closed by the node", self.path)
+
+        # fail any requests still waiting on the closed socket
+        with self._state_lock:
+            pending, self._pending_requests = self._pending_requests, {}
+        for future in pending.values():
+            future.set_exception(ConnectionError(f"IPC socket {self.path} closed"))
+
+    def _dispatch(self, message: Dict) -> None:
+        if message.get("method") == "eth_subscription":
+            params = message["params"]
+            with self._state_lock:
+                subscription = self._subscriptions.get(params["subscription"])
+                if subscription is None:
+                    self._buffer_early(params["subscription"], params["result"])
+                    return
+            self._enqueue(subscription, params["result"])
+            return
+
+        with self._state_lock:
+            future = self._pending_requests.pop(message.get("id"), None)
+        if future is None:
+            return
+        if "error" in message:
+            error = message["error"]
+            future.set_exception(
+                RpcResponseError(
+                    error.get("code"), error.get("message"), error.get("data")
+                )
+            )
+        else:
+            future.set_result(message.get("result"))
+
+    def _buffer_early(self, subscription_id: str, result) -> None:
+        # called with _state_lock held
+        early = self._early_notifications.get(subscription_id)
+        if early is None:
+            if len(self._early_notifications) >= self.MAX_EARLY_SUBSCRIPTIONS:
+                evicted, _ = self._early_notifications.popitem(last=False)
+                _logger.debug(
+                    "IpcProvider: dropped notifications for unknown subscription %s",
+                    evicted,
+                )
+            early = self._early_notifications[subscription_id] = []
+        if len(early) < self.MAX_EARLY_NOTIFICATIONS:
+            early.append(result)
+
+    @staticmethod
+    def _enqueue(subscription: Tuple[queue.Queue, Callable], result) -> None:
+        subscription_queue, decode = subscription
+        try:
+            item = decode(result)
+        except Exception:
+            _logger.exception("IpcProvider: failed to decode notification %s", result)
+            return
+        try:
+            subscription_queue.put_nowait(item)
+        except queue.Full:
+            # the consumer is falling behind, so drop the oldest entry
+            try:
+                subscription_queue.get_nowait()
+            except queue.Empty:
+                pass
+            _logger.debug("IpcProvider: subscription queue full, dropped oldest")
+            subscription_queue.put_nowait(item)
+
+    def request(self, method: str, params: Optional[List] = None) -> Any:
+        """
+        Sends a JSON-RPC request and waits for its result
+        """
+        future = Future()
+        with self._state_lock:
+            request_id = self._next_id
+            self._next_id += 1
+            self._pending_requests[request_id] = future
+
+        body = json.dumps(
+            {
+                "jsonrpc": "2.0",
+                "id": request_id,
+                "method": method,
+                "params": params or [],
+            }
+        ).encode()
+        with self._write_lock:
+            self._socket.sendall(body)
+
+        try:
+            return future.result(timeout=self.timeout)
+        finally:
+            with self._state_lock:
+                self._pending_requests.pop(request_id, None)
+
+    async def request_async(self, method: str, params: Optional[List] = None) -> Any:
+        """
+        Awaitable version of request, run in the event loop's default executor
+        """
+        loop = asyncio.get_running_loop()
+        return await loop.run_in_executor(
+            None, functools.partial(self.request, method, params)
+        )
+
+    def subscribe(
+        self,
+        params: List,
+        decode: Optional[Callable] = None,
+        max_queue_size: int = 0,
+    ) -> Tuple[str, queue.Queue]:
+        """
+        Opens an eth_subscribe subscription and returns its id and a queue receiving
+        each notification, optionally transformed by `decode`. With a bounded queue,
+        the oldest entries are discarded when the consumer falls behind
+        """
+        subscription_queue = queue.Queue(maxsize=max_queue_size)
+        decode = decode or (lambda result: result)
+
+        subscription_id = self.request("eth_subscribe", params)
+        subscription = (subscription_queue, decode)
+        with self._state_lock:
+            self._subscriptions[subscription_id] = subscription
+            # drain under the lock, so later notifications cannot be queued first
+            for result in self._early_notifications.pop(subscription_id, []):
+                self._enqueue(subscription, result)
+        return subscription_id, subscription_queue
+
+    def subscribe_pending_transactions(
+        self,
+        max_queue_size: int = 10_000,
+    ) -> Tuple[str, queue.Queue]:
+        """
+        Subscribes to full pending transaction bodies. Each queue entry is a parsed
+        Transaction. Nodes that only announce hashes deliver the hash as bytes instead
+        """
+
+        def _decode(result):
+            if isinstance(result, dict):
+                return parse_transaction(result)
+            return bytes.fromhex(result[2:])
+
+        return self.subscribe(
+            ["newPendingTransactions", True], _decode, max_queue_size=max_queue_size
+        )
+
+    def unsubscribe(self, subscription_id: str) -> bool:
+        with self._state_lock:
+            self._subscriptions.pop(subscription_id, None)
+            self._early_notifications.pop(subscription_id, None)
+        return self.request("eth_unsubscribe", [subscription_id])
@@ -0,0 +1,352 @@
+import asyncio
+import json
+from collections import OrderedDict
+from typing import Any, Callable, Dict, Hashable, List, Optional
+from ..exceptions import RpcResponseError
+from ..log import get_logger
+from ..rpc import parse_block_header, parse_log, parse_transaction
+from ..rpc.rate_limiter import RateLimiter
+
+__all__ = ["WsSubscription", "WsProvider"]
+
+_logger = get_logger("provider")
+
+# number of delivered item identities remembered per subscription for de-duplication
+_SEEN_LIMIT = 10_000
+
+
+class WsSubscription:
+    """
+    An eth_subscribe subscription that survives reconnections. Decoded notifications
+    are consumed with `async for`
+    """
+
+    def __init__(
+        self,
+        provider: "WsProvider",
+        params: List,
+        decode: Callable,
+        backfill: Optional[Callable] = None,
+        max_queue_size: int = 0,
+    ) -> None:
+        self.provider = provider
+        self.params = params
+        self.server_id: Optional[str] = None
+        self._decode = decode
+        self._backfill = backfill
+        self._queue = asyncio.Queue(maxsize=max_queue_size)
+        # highest block delivered, the point to backfill from after a reconnect
+        self.last_seen: Optional[int] = None
+        # identities of recently delivered items, e.g. (number, hash) for headers,
+        # which de-duplicate the items delivered twice around a reconnect
+        self._seen: "OrderedDict[Hashable, None]" = OrderedDict()
+        # live notifications held back while a backfill is running
+        self._held: Optional[List] = None
+
+    def __aiter__(self) -> "WsSubscription":
+        return self
+
+    async def __anext__(self) -> Any:
+        return await self._queue.get()
+
+    def _deliver(self, item, key=None, height=None) -> None:
+        if key is not None:
+            if key in self._seen:
+                return
+            self._seen[key] = None
+            if len(self._seen) > _SEEN_LIMIT:
+                self._seen.popitem(last=False)
+        if height is not None and (self.last_seen is None or height > self.last_seen):
+            self.last_seen = height
+        if self._queue.full():
+            # the consumer is falling behind, so drop the oldest entry
+            self._queue.get_nowait()
+            _logger.debug("WsSubscription %s: queue full, dropped oldest", self.params)
+        self._queue.put_nowait(item)
+
+    def _forget(self, key: Hashable) -> None:
+        self._seen.pop(key, None)
+
+    def _notify(self, result) -> None:
+        self._deliver(*self._decode(result))
+
+    def _receive(self, result) -> None:
+        if self._held is not None:
+            self._held.append(result)
+        else:
+            self._notify(result)
+
+    async def _run_backfill(self) -> None:
+        # live notifications arriving meanwhile are delivered after the backfilled
+        # items, so they stay in order and cannot advance last_seen past the range
+        # being backfilled
+        if self._held is None:
+            self._held = []
+        try:
+            await self._backfill(self)
+        finally:
+            held, self._held = self._held, None
+            for result in held:
+                self._notify(result)
+
+    async def unsubscribe(self) -> None:
+        await self.provider.unsubscribe(self)
+
+
+class WsProvider:
+    """
+    JSON-RPC provider over a websocket, maintaining eth_subscribe subscriptions across
+    disconnects. After a reconnect every subscription is re-established, and blocks or
+    logs announced while disconnected are backfilled with regular requests, so
+    consumers see a gap-free stream. Live notifications arriving during a backfill are
+    held back until it completes. Items already delivered, identified by block hash
+    rather than height so that reorged blocks and logs still come through, are dropped
+    to avoid duplicates at the reconnection boundary. Requests, including backfills,
+    wait for a `rate_limiter` if one is given
+    """
+
+    def __init__(
+        self,
+        url: str,
+        timeout: float = 10.0,
+        reconnect_delay: float = 0.5,
+        max_reconnect_delay: float = 30.0,
+        rate_limiter: Optional[RateLimiter] = None,
+    ) -> None:
+        self.url = url
+        self.timeout = timeout
+        self.reconnect_delay = reconnect_delay
+        self.max_reconnect_delay = max_reconnect_delay
+        self.rate_limiter = rate_limiter
+
+        self._connection = None
+        self._connected = asyncio.Event()
+        self._next_id = 1
+        self._pending_requests: Dict[int, asyncio.Future] = {}
+        self._subscriptions: List[WsSubscription] = []
+        self._by_server_id: Dict[str, WsSubscription] = {}
+        self._runner: Optional[asyncio.Task] = None
+        self._closed = False
+
+    def __str__(self) -> str:
+        return self.url
+
+    async def connect(self) -> None:
+        """
+        Starts the connection task and waits for the first connection
+        """
+        if self._runner is None:
+            self._runner = asyncio.ensure_future(self._run())
+        await asyncio.wait_for(self._connected.wait(), self.timeout)
+
+    async def close(self) -> None:
+        self._closed = True
+        if self._connection is not None:
+            await self._connection.close()
+        if self._runner is not None:
+            self._runner.cancel()
+            try:
+                await self._runner
+            except asyncio.CancelledError:
+                pass
+
+    async def _run(self) -> None:
+        import websockets
+
+        delay = self.reconnect_delay
+        first_connection = True
+        while not self._closed:
+            try:
+                async with websockets.connect(self.url, max_size=None) as connection:
+                    self._connection = connection
+                    reader = asyncio.ensure_future(self._read(connection))
+                    try:
+                        self._connected.set()
+                        await self._resubscribe(backfill=not first_connection)
+                        first_connection = False
+                        delay = self.reconnect_delay
+                        await reader
+                    finally:
+                        # a failed resubscription must not leave the reader behind
+                        if not reader.done():
+                            reader.cancel()
+                            try:
+                                await reader
+                            except asyncio.CancelledError:
+                                pass
+            except asyncio.CancelledError:
+                raise
+            except Exception as e:
+                _logger.warning("WsProvider: connection to %s lost: %s", self.url, e)
+            finally:
+                self._connected.clear()
+                self._connection = None
+                self._by_server_id.clear()
+                for future in self._pending_requests.values():
+                    if not future.done():
+                        future.set_exception(ConnectionError("Websocket disconnected"))
+                self._pending_requests.clear()
+
+            if not self._closed:
+                await asyncio.sleep(delay)
+                delay = min(2 * delay, self.max_reconnect_delay)
+
+    async def _read(self, connection) -> None:
+        async for raw_message in connection:
+            message = json.loads(raw_message)
+            if message.get("method") == "eth_subscription":
+                params = message["params"]
+                subscription = self._by_server_id.get(params["subscription"])
+                if subscription is not None:
+                    subscription._receive(params["result"])
+                continue
+
+            future = self._pending_requests.pop(message.get("id"), None)
+            if future is None or future.done():
+                continue
+            if "error" in message:
+                error = message["error"]
+                future.set_exception(
+                    RpcResponseError(
+                        error.get("code"), error.get("message"), error.get("data")
+                    )
+                )
+            else:
+                future.set_result(message.get("result"))
+
+    async def _resubscribe(self, backfill: bool) -> None:
+        for subscription in self._subscriptions:
+            if backfill and subscription._backfill is not None:
+                subscription._held = []
+            await self._open(subscription)
+            if subscription._held is not None:
+                _logger.info("WsProvider: backfilling %s", subscription.params)
+                await subscription._run_backfill()
+
+    async def _open(self, subscription: WsSubscription) -> None:
+        subscription.server_id = await self.request(
+            "eth_subscribe", subscription.params
+        )
+        self._by_server_id[subscription.server_id] = subscription
+
+    async def request(self, method: str, params: Optional[List] = None) -> Any:
+        """
+        Sends a JSON-RPC request over the websocket and waits for its result
+        """
+        await asyncio.wait_for(self._connected.wait(), self.timeout)
+        if self.rate_limiter is not None:
+            await self.rate_limiter.acquire_async()
+
+        request_id = self._next_id
+        self._next_id += 1
+        future = asyncio.get_running_loop().create_future()
+        self._pending_requests[request_id] = future
+        await self._connection.send(
+            json.dumps(
+                {
+                    "jsonrpc": "2.0",
+                    "id": request_id,
+                    "method": method,
+                    "params": params or [],
+                }
+            )
+        )
+        try:
+            return await asyncio.wait_for(future, self.timeout)
+        finally:
+            self._pending_requests.pop(request_id, None)
+
+    async def _subscribe(self, subscription: WsSubscription) -> WsSubscription:
+        self._subscriptions.append(subscription)
+        if self._connected.is_set():
+            await self._open(subscription)
+        return subscription
+
+    async def unsubscribe(self, subscription: WsSubscription) -> None:
+        self._subscriptions.remove(subscription)
+        if subscription.server_id is not None:
+            self._by_server_id.pop(subscription.server_id, None)
+            if self._connected.is_set():
+                await self.request("eth_unsubscribe", [subscription.server_id])
+
+    async def subscribe_new_heads(self, max_queue_size: int = 0) -> WsSubscription:
+        """
+        Subscribes to new block headers, delivered as BlockHeader objects
+        """
+
+        def _decode(result):
+            header = parse_block_header(result)
+            return header, (header.number, header.hash), header.number
+
+        async def _backfill(subscription: WsSubscription) -> None:
+            if subscription.last_seen is None:
+                return
+            latest = int(await self.request("eth_blockNumber"), 16)
+            # starting at the last delivered height picks up a block replaced by a
+            # reorg while disconnected, the unchanged block is de-duplicated
+            for number in range(subscription.last_seen, latest + 1):
+                block = await self.request("eth_getBlockByNumber", [hex(number), False])
+                subscription._notify(block)
+
+        return await self._subscribe(
+            WsSubscription(self, ["newHeads"], _decode, _backfill, max_queue_size)
+        )
+
+    async def subscribe_logs(
+        self, log_filter: Dict, max_queue_size: int = 0
+    ) -> WsSubscription:
+        """
+        Subscribes to logs matching a filter ({"address": ..., "topics": [...]}),
+        delivered as Log objects. Logs removed by a reorg are always delivered, with
+        `removed` set
+        """
+
+        def _decode(result):
+            log = parse_log(result)
+            key = (log.block_hash, log.log_index)
+            if log.removed:
+                # the log is delivered again if the chain reorganizes back to its block
+                subscription._forget(key)
+                return log, None, None
+            return log, key, log.block_number
+
+        async def _backfill(subscription: WsSubscription) -> None:
+            if subscription.last_seen is None:
+                return
+            logs = await self.request(
+                "eth_getLogs",
+                [
+                    {
+                        **log_filter,
+                        "fromBlock": hex(subscription.last_seen),
+                        "toBlock": "latest",
+                    }
+                ],
+            )
+            for log in logs:
+                subscription._notify(log)
+
+        subscription = WsSubscription(
+            self, ["logs", log_filter], _decode, _backfill, max_queue_size
+        )
+        return await self._subscribe(subscription)
+
+    async def subscribe_pending_transactions(
+        self, full: bool = True, max_queue_size: int = 10_000
+    ) -> WsSubscription:
+        """
+        Subscribes to pending transactions, delivered as Transaction objects when
+        `full` is set (and supported by the node), otherwise as hashes
+        """
+
+        def _decode(result):
+            if isinstance(result, dict):
+                return parse_transaction(result), None, None
+            return bytes.fromhex(result[2:]), None, None
+
+        params = ["newPendingTransactions"]
+        if full:
+            params.append(True)
+        return await self._subscribe(
+            WsSubscription(self, params, _decode, None, max_queue_size)
+        )
@@ -0,0 +1,2 @@
+from .quoter import *
+from .fee_tiers import *
\ No newline at end of file
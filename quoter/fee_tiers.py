@@ -0,0 +1,57 @@
+from dataclasses import dataclass
+from typing import Dict, Optional
+from ..exceptions import SwapSimulationError
+
+__all__ = ["FeeTierQuote", "best_fee_tier"]
+
+
+@dataclass
+class FeeTierQuote:
+    fee: int
+    pool: object
+    # the output of an exact-input quote, or the input of an exact-output quote
+    amount: int
+    # the quote of every tier, None where the pool could not fill the swap
+    quotes: Dict[int, Optional[int]]
+
+
+def _quote_tier(pool, amount: int, zero_for_one: bool, exact_output: bool):
+    try:
+        result = pool.simulate_swap(-amount if exact_output else amount, zero_for_one)
+    except SwapSimulationError:
+        return None
+    amount0, amount1 = result.amount0, result.amount1
+    amount_in, amount_out = (
+        (amount0, -amount1) if zero_for_one else (amount1, -amount0)
+    )
+    # pools without enough liquidity fill the swap only partially
+    if exact_output:
+        return amount_in if amount_out == amount else None
+    return amount_out if amount_in == amount else None
+
+
+def best_fee_tier(
+    token_in: str,
+    token_out: str,
+    amount: int,
+    pools_by_tier: Dict[int, object],
+    exact_output: bool = False,
+) -> Optional[FeeTierQuote]:
+    """
+    Quotes a swap in every fee tier's V3 pool of a token pair (keyed by fee) and
+    returns the tier with the most output, or with `exact_output` the least input for
+    `amount` out. Tiers whose pool cannot fill the whole swap are skipped. Returns
+    None if no tier can
+    """
+    assert amount > 0, "Swap amount must be positive"
+    zero_for_one = int(token_in, 16) < int(token_out, 16)
+    quotes = {
+        fee: _quote_tier(pool, amount, zero_for_one, exact_output)
+        for fee, pool in pools_by_tier.items()
+    }
+    available = [fee for fee, quote in quotes.items() if quote is not None]
+    if not available:
+        return None
+    choose = min if exact_output else max
+    fee = choose(available, key=lambda fee: quotes[fee])
+    return FeeTierQuote(fee, pools_by_tier[fee], quotes[fee], quotes)
@@ -0,0 +1,70 @@
+from dataclasses import dataclass
+from typing import List, Sequence, Tuple, Union
+from ..metrics import increment_counter
+from ..pool_state import CurveCryptoPoolState, CurveStableswapPoolState
+
+__all__ = ["PathHop", "Path", "quote_path", "quote_path_exact_output"]
+
+
+@dataclass(frozen=True)
+class PathHop:
+    """
+    A single swap in a path. `token_in` and `token_out` are the pool's coin indices:
+    0 and 1 for two-token pools (V2, V3, Solidly), any coin index for Curve pools
+    """
+
+    pool: object
+    token_in: int
+    token_out: int
+
+
+Path = Sequence[Union[PathHop, Tuple[object, int, int]]]
+
+
+def _hops(path: Path) -> List[PathHop]:
+    hops = [hop if isinstance(hop, PathHop) else PathHop(*hop) for hop in path]
+    assert hops, "A path must contain at least one hop"
+    for hop in hops:
+        assert hop.token_in != hop.token_out, f"Hop through {hop.pool} is a no-op"
+    return hops
+
+
+_CURVE_POOL_TYPES = (CurveStableswapPoolState, CurveCryptoPoolState)
+
+
+def _amount_out(hop: PathHop, amount_in: int) -> int:
+    if isinstance(hop.pool, _CURVE_POOL_TYPES):
+        return hop.pool.get_dy(hop.token_in, hop.token_out, amount_in)
+    return hop.pool.get_amount_out(amount_in, hop.token_in == 0)
+
+
+def _amount_in(hop: PathHop, amount_out: int) -> int:
+    if isinstance(hop.pool, _CURVE_POOL_TYPES):
+        return hop.pool.get_dx(hop.token_in, hop.token_out, amount_out)
+    return hop.pool.get_amount_in(amount_out, hop.token_in == 0)
+
+
+def quote_path(path: Path, amount_in: int) -> List[int]:
+    """
+    Quotes an exact-input swap through a path of pool states of any supported type,
+    feeding each hop's output into the next. Returns the amount at every step, starting
+    with `amount_in` and ending with the final output (like getAmountsOut)
+    """
+    increment_counter("paths_quoted")
+    amounts = [amount_in]
+    for hop in _hops(path):
+        amounts.append(_amount_out(hop, amounts[-1]))
+    return amounts
+
+
+def quote_path_exact_output(path: Path, amount_out: int) -> List[int]:
+    """
+    Quotes an exact-output swap through a path, solving each hop backwards from the
+    last. Returns the amount at every step, starting with the required input and
+    ending with `amount_out` (like getAmountsIn)
+    """
+    increment_counter("paths_quoted")
+    amounts = [amount_out]
+    for hop in reversed(_hops(path)):
+        amounts.insert(0, _amount_in(hop, amounts[0]))
+    return amounts
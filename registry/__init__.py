@@ -0,0 +1,2 @@
+from .registry import *
+from .refresh import *
\ No newline at end of file
@@ -0,0 +1,136 @@
+from dataclasses import dataclass, field
+from typing import Dict, Iterable, List, Mapping, Set, Tuple, Union
+from ..abi import abi_encode, function_selector
+from ..pool_state import (
+    CurveCryptoPoolState,
+    CurveStableswapPoolState,
+    V3PoolState,
+)
+from ..storage import V3_LIQUIDITY_SLOT, V3_SLOT0_SLOT, slot_to_hex
+from ..uniswap_v3 import ExchangeVariant
+
+__all__ = [
+    "GET_RESERVES_SELECTOR",
+    "SLOT0_SELECTOR",
+    "LIQUIDITY_SELECTOR",
+    "BALANCES_SELECTOR",
+    "RefreshPlan",
+    "token_dependency_graph",
+    "build_refresh_plan",
+]
+
+GET_RESERVES_SELECTOR = function_selector("getReserves()")
+SLOT0_SELECTOR = function_selector("slot0()")
+LIQUIDITY_SELECTOR = function_selector("liquidity()")
+BALANCES_SELECTOR = function_selector("balances(uint256)")
+
+
+@dataclass
+class RefreshPlan:
+    """
+    The pools to re-verify after a block, with the reads that fetch their state:
+    storage slots for pools whose layout is known (Uniswap V3), and eth_calls for the
+    others
+    """
+
+    pools: List[str] = field(default_factory=list)
+    # (pool address, storage slot)
+    storage_reads: List[Tuple[str, int]] = field(default_factory=list)
+    # (pool address, calldata)
+    calls: List[Tuple[str, bytes]] = field(default_factory=list)
+
+    def requests(self, block: Union[int, str] = "latest") -> List[Tuple[str, List]]:
+        """
+        The plan's reads as (method, params) JSON-RPC calls, storage reads first
+        """
+        block_identifier = hex(block) if isinstance(block, int) else block
+        return [
+            ("eth_getStorageAt", [address, slot_to_hex(slot), block_identifier])
+            for address, slot in self.storage_reads
+        ] + [
+            ("eth_call", [{"to": to, "data": "0x" + data.hex()}, block_identifier])
+            for to, data in self.calls
+        ]
+
+    def batches(
+        self,
+        block: Union[int, str] = "latest",
+        batch_size: int = 100,
+    ) -> List[List[Tuple[str, List]]]:
+        """
+        The plan's requests split into batches for HttpProvider.batch_request
+        """
+        assert batch_size >= 1, "The batch size must be positive"
+        requests = self.requests(block)
+        return [
+            requests[start : start + batch_size]
+            for start in range(0, len(requests), batch_size)
+        ]
+
+
+def token_dependency_graph(
+    pool_tokens: Mapping[str, Iterable[str]],
+) -> Dict[str, Set[str]]:
+    """
+    Builds a dependency graph from the tokens of each pool: a token maps to the pools
+    holding it, and a pool to every pool sharing one of its tokens (itself included)
+    """
+    graph: Dict[str, Set[str]] = {}
+    for pool, tokens in pool_tokens.items():
+        for token in tokens:
+            graph.setdefault(token, set()).add(pool)
+    for pool, tokens in pool_tokens.items():
+        dependents = graph.setdefault(pool, set())
+        dependents.add(pool)
+        for token in tokens:
+            dependents.update(graph[token])
+    return graph
+
+
+def _reads(address: str, pool_state, plan: RefreshPlan) -> None:
+    if isinstance(pool_state, V3PoolState):
+        if getattr(pool_state, "variant", None) is ExchangeVariant.UNISWAP_V3:
+            plan.storage_reads.append((address, V3_SLOT0_SLOT))
+            plan.storage_reads.append((address, V3_LIQUIDITY_SLOT))
+        else:
+            # forks may change the storage layout, so read through the ABI
+            plan.calls.append((address, SLOT0_SELECTOR))
+            plan.calls.append((address, LIQUIDITY_SELECTOR))
+    elif isinstance(pool_state, (CurveStableswapPoolState, CurveCryptoPoolState)):
+        for i in range(len(pool_state.balances)):
+            plan.calls.append(
+                (address, BALANCES_SELECTOR + abi_encode(["uint256"], [i]))
+            )
+    elif hasattr(pool_state, "reserve0"):
+        plan.calls.append((address, GET_RESERVES_SELECTOR))
+    else:
+        raise TypeError(f"Unsupported pool state {type(pool_state).__name__}")
+
+
+def build_refresh_plan(
+    changed_addresses: Iterable[str],
+    dependency_graph: Mapping[str, Iterable[str]],
+    pools: Mapping[str, object],
+) -> RefreshPlan:
+    """
+    Computes the pools to re-verify after a block that touched `changed_addresses`
+    (pools, tokens or any other contract), and the reads needed to refresh them.
+
+    A changed address is re-verified if it is a known pool, along with the pools the
+    dependency graph lists for it (e.g. from token_dependency_graph). Dependencies are
+    followed one level only, and addresses not in `pools` are skipped
+    """
+    selected: Set[str] = set()
+    for address in changed_addresses:
+        if address in pools:
+            selected.add(address)
+        selected.update(
+            dependent
+            for dependent in dependency_graph.get(address, ())
+            if dependent in pools
+        )
+
+    plan = RefreshPlan(pools=sorted(selected))
+    for address in plan.pools:
+        _reads(address, pools[address], plan)
+    return plan
@@ -0,0 +1,149 @@
+import math
+from typing import Any, Dict, Iterable, Iterator, List, Optional
+from ..journal import StateJournal
+from ..pool_state import PoolEvent
+
+__all__ = ["pool_liquidity", "PoolRegistry"]
+
+
+def pool_liquidity(pool_state: Any) -> int:
+    """
+    A liquidity measure comparable across pool types: the in-range liquidity of
+    V3-style pools, sqrt(reserve0 * reserve1) for V2-style pairs (the same quantity for
+    a full-range position), and the smallest balance normalized to 18 decimals for
+    Curve pools
+    """
+    if hasattr(pool_state, "liquidity"):
+        return pool_state.liquidity
+    if hasattr(pool_state, "reserve0"):
+        return math.isqrt(pool_state.reserve0 * pool_state.reserve1)
+    if hasattr(pool_state, "balances"):
+        return min(
+            balance * 10 ** (18 - decimals)
+            for balance, decimals in zip(pool_state.balances, pool_state.decimals)
+        )
+    raise TypeError(f"Unsupported pool state {type(pool_state).__name__}")
+
+
+class PoolRegistry:
+    """
+    The set of pool states tracked by a bot, keyed by address and updated only from
+    chain events. Every update goes through a StateJournal, so after a reorg the
+    whole registry can be rolled back to the last common block with one call, instead
+    of re-fetching state for every affected pool.
+
+    Pool objects are restored in place on rollback, so references held by strategies
+    (arbitrage paths, quoters) stay valid
+    """
+
+    def __init__(
+        self,
+        pools: Iterable = (),
+        journal_path: Optional[str] = None,
+        checkpoint_interval: int = 100,
+    ) -> None:
+        self._journal = StateJournal(pools, journal_path, checkpoint_interval)
+
+    @classmethod
+    def from_journal(cls, path: str, checkpoint_interval: int = 100) -> "PoolRegistry":
+        """
+        Rebuilds a registry from a journal file written by a previous run
+        """
+        registry = cls.__new__(cls)
+        registry._journal = StateJournal.load(path, checkpoint_interval)
+        return registry
+
+    @property
+    def journal(self) -> StateJournal:
+        return self._journal
+
+    @property
+    def last_block(self) -> Optional[int]:
+        return self._journal.last_block
+
+    def __getitem__(self, address: str) -> Any:
+        return self._journal.pools[address]
+
+    def __contains__(self, address: str) -> bool:
+        return address in self._journal.pools
+
+    def __iter__(self) -> Iterator[str]:
+        return iter(self._journal.pools)
+
+    def __len__(self) -> int:
+        return len(self._journal.pools)
+
+    def get(self, address: str, default: Any = None) -> Any:
+        return self._journal.pools.get(address, default)
+
+    @property
+    def pools(self) -> Dict:
+        return self._journal.pools
+
+    def apply_event(self, event: PoolEvent) -> None:
+        self._journal.append(event)
+
+    def apply_events(self, events: Iterable[PoolEvent]) -> None:
+        self._journal.extend(events)
+
+    def rollback_to_block(self, block_number: int) -> List[str]:
+        """
+        Reverts every pool to its state at the end of `block_number`, discarding the
+        journaled events of later blocks. Returns the addresses of the pools that were
+        changed by the discarded events
+        """
+        return self._journal.rewind(block_number)
+
+    def last_update(self, address: str) -> Optional[int]:
+        """
+        The block of the last event applied to a pool, or None if it has not been
+        updated since it was loaded
+        """
+        return self._journal.pools[address].block_number
+
+    def stale_pools(
+        self, max_age_blocks: int, block_number: Optional[int] = None
+    ) -> List[str]:
+        """
+        Returns the addresses of the pools not updated within `max_age_blocks` of
+        `block_number` (by default the last journaled block), including pools never
+        updated since they were loaded
+        """
+        assert max_age_blocks >= 0, "The maximum age must be non-negative"
+        if block_number is None:
+            block_number = self.last_block
+        return [
+            address
+            for address, pool_state in self._journal.pools.items()
+            if pool_state.block_number is None
+            or (
+                block_number is not None
+                and block_number - pool_state.block_number > max_age_blocks
+            )
+        ]
+
+    def dust_pools(self, min_liquidity: int) -> List[str]:
+        """
+        Returns the addresses of the pools holding less than `min_liquidity`, as
+        measured by pool_liquidity
+        """
+        return [
+            address
+            for address, pool_state in self._journal.pools.items()
+            if pool_liquidity(pool_state) < min_liquidity
+        ]
+
+    def active_pools(
+        self,
+        max_age_blocks: Optional[int] = None,
+        min_liquidity: int = 0,
+        block_number: Optional[int] = None,
+    ) -> List[str]:
+        """
+        Returns the addresses of the pools that are neither stale nor dust, for
+        pathfinding to skip abandoned pools. Either filter is disabled by its default
+        """
+        excluded = set(self.dust_pools(min_liquidity)) if min_liquidity else set()
+        if max_age_blocks is not None:
+            excluded.update(self.stale_pools(max_age_blocks, block_number))
+        return [address for address in self._journal.pools if address not in excluded]
@@ -0,0 +1 @@
+from .relay import *
\ No newline at end of file
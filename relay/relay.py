@@ -0,0 +1,210 @@
+import json
+from dataclasses import dataclass, field
+from typing import Dict, List, Optional, Union
+from eth_utils import keccak
+from ..rpc.responses import _loads
+from ..signing import address_from_private_key, sign_digest, signature_to_bytes
+
+__all__ = [
+    "MevShareLog",
+    "MevShareTx",
+    "MevShareEvent",
+    "decode_mev_share_event",
+    "BundleOptions",
+    "build_bundle_payload",
+    "flashbots_signature",
+]
+
+
+@dataclass
+class MevShareLog:
+    """
+    A log hint. Matchmakers may share only some topics (the others are omitted) and
+    may withhold `data` entirely, in which case it is None
+    """
+
+    address: str
+    topics: List[bytes]
+    data: Optional[bytes] = None
+
+
+@dataclass
+class MevShareTx:
+    """
+    A transaction hint. Each field is only present if the sender chose to share it
+    """
+
+    to: Optional[str] = None
+    function_selector: Optional[bytes] = None
+    call_data: Optional[bytes] = None
+
+
+@dataclass
+class MevShareEvent:
+    # transaction hash, or bundle hash for bundles
+    hash: bytes
+    logs: List[MevShareLog] = field(default_factory=list)
+    txs: List[MevShareTx] = field(default_factory=list)
+    mev_gas_price: Optional[int] = None
+    gas_used: Optional[int] = None
+
+
+def _hex_bytes(value: Optional[str]) -> Optional[bytes]:
+    if value is None:
+        return None
+    return bytes.fromhex(value[2:] if value.startswith("0x") else value)
+
+
+def _hex_int(value: Optional[str]) -> Optional[int]:
+    return int(value, 16) if value is not None else None
+
+
+def decode_mev_share_event(data: Union[bytes, str]) -> Optional[MevShareEvent]:
+    """
+    Parses a MEV-Share event stream payload. Either the JSON object itself or a raw
+    server-sent event line ("data: {...}") is accepted. Returns None for SSE lines
+    that carry no event (comments, keep-alives and blank lines)
+    """
+    if isinstance(data, (bytes, bytearray, memoryview)):
+        data = bytes(data).decode()
+    data = data.strip()
+    if data.startswith("data:"):
+        data = data[len("data:") :].strip()
+    if not data.startswith("{"):
+        return None
+
+    event = _loads(data)
+    return MevShareEvent(
+        hash=_hex_bytes(event["hash"]),
+        logs=[
+            MevShareLog(
+                address=log["address"],
+                topics=[_hex_bytes(topic) for topic in log.get("topics") or []],
+                data=_hex_bytes(log.get("data")),
+            )
+            for log in event.get("logs") or []
+        ],
+        txs=[
+            MevShareTx(
+                to=tx.get("to"),
+                function_selector=_hex_bytes(tx.get("functionSelector")),
+                call_data=_hex_bytes(tx.get("callData")),
+            )
+            for tx in event.get("txs") or []
+        ],
+        mev_gas_price=_hex_int(event.get("mevGasPrice")),
+        gas_used=_hex_int(event.get("gasUsed")),
+    )
+
+
+@dataclass
+class BundleOptions:
+    """
+    Optional bundle parameters. The first group applies to eth_sendBundle, the second
+    to mev_sendBundle (v0.1), where `refund` entries are {"bodyIdx", "percent"},
+    `refund_config` entries are {"address", "percent"} and `can_revert` lists the
+    indices of transactions allowed to revert
+    """
+
+    min_timestamp: Optional[int] = None
+    max_timestamp: Optional[int] = None
+    reverting_tx_hashes: List[bytes] = field(default_factory=list)
+    replacement_uuid: Optional[str] = None
+    builders: List[str] = field(default_factory=list)
+
+    max_block: Optional[int] = None
+    can_revert: List[int] = field(default_factory=list)
+    refund: List[Dict] = field(default_factory=list)
+    refund_config: List[Dict] = field(default_factory=list)
+    hints: List[str] = field(default_factory=list)
+
+
+def _hex(value: Union[bytes, str]) -> str:
+    return value if isinstance(value, str) else "0x" + bytes(value).hex()
+
+
+def _mev_share_body_item(item: Union[bytes, str], can_revert: bool) -> Dict:
+    raw = _hex_bytes(item) if isinstance(item, str) else bytes(item)
+    if len(raw) == 32:
+        # a transaction hash, referencing a transaction shared through MEV-Share
+        return {"hash": _hex(raw)}
+    return {"tx": _hex(raw), "canRevert": can_revert}
+
+
+def build_bundle_payload(
+    txs: List[Union[bytes, str]],
+    block_number: int,
+    options: Optional[BundleOptions] = None,
+    method: str = "eth_sendBundle",
+    request_id: int = 1,
+) -> bytes:
+    """
+    Builds the JSON-RPC request body submitting signed raw transactions as a bundle
+    targeting `block_number`, for either eth_sendBundle or mev_sendBundle. For
+    mev_sendBundle, 32-byte entries are treated as hashes of transactions from the
+    MEV-Share stream. The returned bytes are exactly what flashbots_signature signs
+    """
+    assert method in (
+        "eth_sendBundle",
+        "mev_sendBundle",
+    ), f"Unsupported bundle method {method}"
+    options = options or BundleOptions()
+
+    if method == "eth_sendBundle":
+        params = {"txs": [_hex(tx) for tx in txs], "blockNumber": hex(block_number)}
+        if options.min_timestamp is not None:
+            params["minTimestamp"] = options.min_timestamp
+        if options.max_timestamp is not None:
+            params["maxTimestamp"] = options.max_timestamp
+        if options.reverting_tx_hashes:
+            params["revertingTxHashes"] = [
+                _hex(tx_hash) for tx_hash in options.reverting_tx_hashes
+            ]
+        if options.replacement_uuid is not None:
+            params["replacementUuid"] = options.replacement_uuid
+        if options.builders:
+            params["builders"] = list(options.builders)
+    else:
+        inclusion = {"block": hex(block_number)}
+        if options.max_block is not None:
+            inclusion["maxBlock"] = hex(options.max_block)
+        params = {
+            "version": "v0.1",
+            "inclusion": inclusion,
+            "body": [
+                _mev_share_body_item(tx, i in options.can_revert)
+                for i, tx in enumerate(txs)
+            ],
+        }
+        validity = {}
+        if options.refund:
+            validity["refund"] = list(options.refund)
+        if options.refund_config:
+            validity["refundConfig"] = list(options.refund_config)
+        if validity:
+            params["validity"] = validity
+        privacy = {}
+        if options.hints:
+            privacy["hints"] = list(options.hints)
+        if options.builders:
+            privacy["builders"] = list(options.builders)
+        if privacy:
+            params["privacy"] = privacy
+
+    return json.dumps(
+        {"jsonrpc": "2.0", "id": request_id, "method": method, "params": [params]},
+        separators=(",", ":"),
+    ).encode()
+
+
+def flashbots_signature(private_key: Union[bytes, str, int], body: bytes) -> str:
+    """
+    Returns the X-Flashbots-Signature header value for a request body: the signer
+    address and an EIP-191 signature of the hex-encoded keccak hash of the body
+    """
+    message = ("0x" + keccak(body).hex()).encode()
+    digest = keccak(
+        b"\x19Ethereum Signed Message:\n" + str(len(message)).encode() + message
+    )
+    signature = signature_to_bytes(*sign_digest(private_key, digest))
+    return f"{address_from_private_key(private_key)}:0x{signature.hex()}"
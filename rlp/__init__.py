@@ -0,0 +1 @@
+from .rlp import *
\ No newline at end of file
@@ -0,0 +1,124 @@
+from typing import Any, List, Tuple, Union
+from ..exceptions import RlpError
+
+__all__ = ["RlpItem", "rlp_encode", "rlp_decode", "rlp_decode_int"]
+
+# An RLP item is a byte string or a (possibly nested) list of items. Encoding also
+# accepts the Python types the `rlp` package infers sedes for: non-negative ints
+# (minimal big-endian), bools, and str (UTF-8)
+RlpItem = Union[bytes, List["RlpItem"]]
+
+
+def _encode_length(length: int, offset: int) -> bytes:
+    if length < 56:
+        return bytes([offset + length])
+    length_bytes = length.to_bytes((length.bit_length() + 7) // 8, "big")
+    return bytes([offset + 55 + len(length_bytes)]) + length_bytes
+
+
+def rlp_encode(item: Any) -> bytes:
+    """
+    Encodes a byte string, int, bool, str, or nested list/tuple of these
+    """
+    if isinstance(item, (list, tuple)):
+        payload = b"".join(rlp_encode(element) for element in item)
+        return _encode_length(len(payload), 0xC0) + payload
+
+    if isinstance(item, bool):
+        item = b"\x01" if item else b""
+    elif isinstance(item, int):
+        if item < 0:
+            raise RlpError(f"Cannot RLP-encode negative integer {item}")
+        item = item.to_bytes((item.bit_length() + 7) // 8, "big")
+    elif isinstance(item, str):
+        item = item.encode()
+    elif isinstance(item, (bytes, bytearray, memoryview)):
+        item = bytes(item)
+    else:
+        raise RlpError(f"Cannot RLP-encode object of type {type(item).__name__}")
+
+    if len(item) == 1 and item[0] < 0x80:
+        return item
+    return _encode_length(len(item), 0x80) + item
+
+
+def _decode_length(data: bytes, position: int, short_offset: int) -> Tuple[int, int]:
+    """
+    Returns the (payload start, payload length) for the item prefix at `position`
+    """
+    prefix = data[position]
+    if prefix - short_offset <= 55:
+        return position + 1, prefix - short_offset
+
+    length_of_length = prefix - short_offset - 55
+    start = position + 1 + length_of_length
+    if start > len(data):
+        raise RlpError("RLP length prefix runs past the end of the input")
+    length_bytes = data[position + 1 : start]
+    if length_bytes[0] == 0:
+        raise RlpError("RLP length prefix has leading zero bytes")
+    length = int.from_bytes(length_bytes, "big")
+    if length < 56:
+        raise RlpError("RLP long-form length used for a short payload")
+    return start, length
+
+
+def _decode_item(data: bytes, position: int) -> Tuple[RlpItem, int]:
+    """
+    Decodes the item starting at `position`, returning it with the position of the
+    next item
+    """
+    prefix = data[position]
+
+    if prefix < 0x80:
+        return data[position : position + 1], position + 1
+
+    if prefix < 0xC0:
+        start, length = _decode_length(data, position, 0x80)
+        end = start + length
+        if end > len(data):
+            raise RlpError("RLP string runs past the end of the input")
+        if length == 1 and data[start] < 0x80:
+            raise RlpError("RLP single byte below 0x80 must not be prefixed")
+        return data[start:end], end
+
+    start, length = _decode_length(data, position, 0xC0)
+    end = start + length
+    if end > len(data):
+        raise RlpError("RLP list runs past the end of the input")
+    items = []
+    position = start
+    while position < end:
+        item, position = _decode_item(data, position)
+        items.append(item)
+    if position != end:
+        raise RlpError("RLP list payload does not match its declared length")
+    return items, end
+
+
+def rlp_decode(data: Union[bytes, str]) -> RlpItem:
+    """
+    Decodes canonical RLP into byte strings and lists, matching the `rlp` package's
+    sedes-less output. Non-canonical encodings and trailing bytes are rejected
+    """
+    if isinstance(data, str):
+        data = bytes.fromhex(data[2:] if data.startswith("0x") else data)
+    data = bytes(data)
+    if not data:
+        raise RlpError("Cannot RLP-decode an empty input")
+
+    item, end = _decode_item(data, 0)
+    if end != len(data):
+        raise RlpError(f"RLP input has {len(data) - end} trailing bytes")
+    return item
+
+
+def rlp_decode_int(item: bytes) -> int:
+    """
+    Interprets a decoded byte string as a big-endian integer, rejecting leading zeros
+    """
+    if isinstance(item, list):
+        raise RlpError("Expected an RLP string, found a list")
+    if item and item[0] == 0:
+        raise RlpError("RLP integer has leading zero bytes")
+    return int.from_bytes(item, "big")
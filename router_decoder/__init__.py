@@ -0,0 +1 @@
+from .router_decoder import *
\ No newline at end of file
@@ -0,0 +1,343 @@
+from dataclasses import dataclass, field
+from typing import Callable, Dict, List, Optional, Tuple, Union
+from eth_utils import to_checksum_address
+from ..abi import abi_decode, function_selector
+from ..exceptions import AbiError
+
+__all__ = ["NATIVE_TOKEN_ADDRESS", "DecodedSwap", "decode_router_calldata"]
+
+# placeholder used by aggregators for the native token
+NATIVE_TOKEN_ADDRESS = "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE"
+
+
+@dataclass
+class DecodedSwap:
+    """
+    A swap decoded from router or aggregator calldata. For exact-input swaps
+    `amount_out` is the minimum accepted output, for exact-output swaps `amount_in` is
+    the maximum accepted input. Tokens that are not present in the calldata (e.g.
+    1inch uniswapV3Swap) are None, and `pools` lists the pool or exchange addresses
+    encoded in the calldata as routing hints
+    """
+
+    protocol: str
+    function: str
+    token_in: Optional[str]
+    token_out: Optional[str]
+    amount_in: int
+    amount_out: int
+    exact_input: bool = True
+    recipient: Optional[str] = None
+    path: List[str] = field(default_factory=list)
+    pools: List[str] = field(default_factory=list)
+
+
+def _address(value: Union[str, int]) -> str:
+    if isinstance(value, int):
+        return to_checksum_address((value % 2 ** 160).to_bytes(20, "big"))
+    return to_checksum_address(value)
+
+
+def _split_types(signature: str) -> List[str]:
+    """
+    Splits the argument list of a signature at its top-level commas, keeping tuples
+    """
+    arguments = signature[signature.index("(") + 1 : -1]
+    types, depth, start = [], 0, 0
+    for i, character in enumerate(arguments):
+        if character == "(":
+            depth += 1
+        elif character == ")":
+            depth -= 1
+        elif character == "," and depth == 0:
+            types.append(arguments[start:i])
+            start = i + 1
+    if arguments:
+        types.append(arguments[start:])
+    return types
+
+
+def _v3_path_tokens(path: bytes) -> List[str]:
+    # packed token (20 bytes) | fee (3 bytes) | token | ...
+    return [_address("0x" + path[i : i + 20].hex()) for i in range(0, len(path), 23)]
+
+
+def _v2_exact_in(function: str, args: Tuple, value: int) -> DecodedSwap:
+    if len(args) == 4:
+        # swapExactETHForTokens: the input is the transaction value
+        amount_in, (amount_out_min, path, recipient, _) = value, args
+    else:
+        amount_in, amount_out_min, path, recipient, _ = args
+    path = [_address(token) for token in path]
+    return DecodedSwap(
+        protocol="uniswap_v2",
+        function=function,
+        token_in=path[0],
+        token_out=path[-1],
+        amount_in=amount_in,
+        amount_out=amount_out_min,
+        recipient=_address(recipient),
+        path=path,
+    )
+
+
+def _v2_exact_out(function: str, args: Tuple, value: int) -> DecodedSwap:
+    if len(args) == 4:
+        # swapETHForExactTokens: the maximum input is the transaction value
+        amount_in_max, (amount_out, path, recipient, _) = value, args
+    else:
+        amount_out, amount_in_max, path, recipient, _ = args
+    path = [_address(token) for token in path]
+    return DecodedSwap(
+        protocol="uniswap_v2",
+        function=function,
+        token_in=path[0],
+        token_out=path[-1],
+        amount_in=amount_in_max,
+        amount_out=amount_out,
+        exact_input=False,
+        recipient=_address(recipient),
+        path=path,
+    )
+
+
+def _v3_exact_input_single(function: str, args: Tuple, value: int) -> DecodedSwap:
+    token_in, token_out, _, recipient, _, amount_in, amount_out_min, _ = args[0]
+    return DecodedSwap(
+        protocol="uniswap_v3",
+        function=function,
+        token_in=_address(token_in),
+        token_out=_address(token_out),
+        amount_in=amount_in,
+        amount_out=amount_out_min,
+        recipient=_address(recipient),
+        path=[_address(token_in), _address(token_out)],
+    )
+
+
+def _v3_exact_output_single(function: str, args: Tuple, value: int) -> DecodedSwap:
+    token_in, token_out, _, recipient, _, amount_out, amount_in_max, _ = args[0]
+    return DecodedSwap(
+        protocol="uniswap_v3",
+        function=function,
+        token_in=_address(token_in),
+        token_out=_address(token_out),
+        amount_in=amount_in_max,
+        amount_out=amount_out,
+        exact_input=False,
+        recipient=_address(recipient),
+        path=[_address(token_in), _address(token_out)],
+    )
+
+
+def _v3_exact_input(function: str, args: Tuple, value: int) -> DecodedSwap:
+    path, recipient, _, amount_in, amount_out_min = args[0]
+    tokens = _v3_path_tokens(path)
+    return DecodedSwap(
+        protocol="uniswap_v3",
+        function=function,
+        token_in=tokens[0],
+        token_out=tokens[-1],
+        amount_in=amount_in,
+        amount_out=amount_out_min,
+        recipient=_address(recipient),
+        path=tokens,
+    )
+
+
+def _v3_exact_output(function: str, args: Tuple, value: int) -> DecodedSwap:
+    path, recipient, _, amount_out, amount_in_max = args[0]
+    # exact output paths are encoded from the output token backwards
+    tokens = list(reversed(_v3_path_tokens(path)))
+    return DecodedSwap(
+        protocol="uniswap_v3",
+        function=function,
+        token_in=tokens[0],
+        token_out=tokens[-1],
+        amount_in=amount_in_max,
+        amount_out=amount_out,
+        exact_input=False,
+        recipient=_address(recipient),
+        path=tokens,
+    )
+
+
+def _oneinch_swap(function: str, args: Tuple, value: int) -> DecodedSwap:
+    executor, description, _, _ = args
+    src_token, dst_token, _, dst_receiver, amount, min_return, _ = description
+    return DecodedSwap(
+        protocol="1inch",
+        function=function,
+        token_in=_address(src_token),
+        token_out=_address(dst_token),
+        amount_in=amount,
+        amount_out=min_return,
+        recipient=_address(dst_receiver),
+        pools=[_address(executor)],
+    )
+
+
+def _oneinch_uniswap_v3_swap(function: str, args: Tuple, value: int) -> DecodedSwap:
+    amount, min_return, pools = args
+    return DecodedSwap(
+        protocol="1inch",
+        function=function,
+        token_in=None,
+        token_out=None,
+        amount_in=amount,
+        amount_out=min_return,
+        # pool addresses are packed in the low 160 bits, flags in the high bits
+        pools=[_address(pool) for pool in pools],
+    )
+
+
+def _oneinch_unoswap(function: str, args: Tuple, value: int) -> DecodedSwap:
+    src_token, amount, min_return, pools = args
+    return DecodedSwap(
+        protocol="1inch",
+        function=function,
+        token_in=_address(src_token),
+        token_out=None,
+        amount_in=amount,
+        amount_out=min_return,
+        pools=[_address(pool) for pool in pools],
+    )
+
+
+def _zeroex_transform_erc20(function: str, args: Tuple, value: int) -> DecodedSwap:
+    input_token, output_token, input_amount, min_output_amount, _ = args
+    return DecodedSwap(
+        protocol="0x",
+        function=function,
+        token_in=_address(input_token),
+        token_out=_address(output_token),
+        amount_in=input_amount,
+        amount_out=min_output_amount,
+    )
+
+
+def _zeroex_sell_to_uniswap(function: str, args: Tuple, value: int) -> DecodedSwap:
+    tokens, sell_amount, min_buy_amount, _ = args
+    tokens = [_address(token) for token in tokens]
+    return DecodedSwap(
+        protocol="0x",
+        function=function,
+        token_in=tokens[0],
+        token_out=tokens[-1],
+        amount_in=sell_amount,
+        amount_out=min_buy_amount,
+        path=tokens,
+    )
+
+
+def _paraswap_simple_swap(function: str, args: Tuple, value: int) -> DecodedSwap:
+    data = args[0]
+    from_token, to_token, from_amount, to_amount, _, callees = data[:6]
+    beneficiary = data[9]
+    return DecodedSwap(
+        protocol="paraswap",
+        function=function,
+        token_in=_address(from_token),
+        token_out=_address(to_token),
+        amount_in=from_amount,
+        amount_out=to_amount,
+        recipient=_address(beneficiary),
+        pools=[_address(callee) for callee in callees],
+    )
+
+
+_V3_SINGLE = "(address,address,uint24,address,uint256,uint256,uint256,uint160)"
+_ONEINCH_DESCRIPTION = "(address,address,address,address,uint256,uint256,uint256)"
+_PARASWAP_SIMPLE_DATA = (
+    "(address,address,uint256,uint256,uint256,address[],bytes,uint256[],uint256[],"
+    "address,address,uint256,bytes,uint256,bytes16)"
+)
+
+_SIGNATURES = (
+    # Uniswap V2 router and forks
+    (
+        "swapExactTokensForTokens(uint256,uint256,address[],address,uint256)",
+        _v2_exact_in,
+    ),
+    (
+        "swapExactTokensForETH(uint256,uint256,address[],address,uint256)",
+        _v2_exact_in,
+    ),
+    ("swapExactETHForTokens(uint256,address[],address,uint256)", _v2_exact_in),
+    (
+        "swapExactTokensForTokensSupportingFeeOnTransferTokens"
+        "(uint256,uint256,address[],address,uint256)",
+        _v2_exact_in,
+    ),
+    (
+        "swapExactTokensForETHSupportingFeeOnTransferTokens"
+        "(uint256,uint256,address[],address,uint256)",
+        _v2_exact_in,
+    ),
+    (
+        "swapExactETHForTokensSupportingFeeOnTransferTokens"
+        "(uint256,address[],address,uint256)",
+        _v2_exact_in,
+    ),
+    (
+        "swapTokensForExactTokens(uint256,uint256,address[],address,uint256)",
+        _v2_exact_out,
+    ),
+    (
+        "swapTokensForExactETH(uint256,uint256,address[],address,uint256)",
+        _v2_exact_out,
+    ),
+    ("swapETHForExactTokens(uint256,address[],address,uint256)", _v2_exact_out),
+    # Uniswap V3 SwapRouter
+    (f"exactInputSingle({_V3_SINGLE})", _v3_exact_input_single),
+    (f"exactOutputSingle({_V3_SINGLE})", _v3_exact_output_single),
+    ("exactInput((bytes,address,uint256,uint256,uint256))", _v3_exact_input),
+    ("exactOutput((bytes,address,uint256,uint256,uint256))", _v3_exact_output),
+    # 1inch AggregationRouterV5
+    (f"swap(address,{_ONEINCH_DESCRIPTION},bytes,bytes)", _oneinch_swap),
+    ("uniswapV3Swap(uint256,uint256,uint256[])", _oneinch_uniswap_v3_swap),
+    ("unoswap(address,uint256,uint256,uint256[])", _oneinch_unoswap),
+    # 0x ExchangeProxy
+    (
+        "transformERC20(address,address,uint256,uint256,(uint32,bytes)[])",
+        _zeroex_transform_erc20,
+    ),
+    ("sellToUniswap(address[],uint256,uint256,bool)", _zeroex_sell_to_uniswap),
+    # Paraswap AugustusSwapper V5
+    (f"simpleSwap({_PARASWAP_SIMPLE_DATA})", _paraswap_simple_swap),
+)
+
+_DECODERS: Dict[bytes, Tuple[str, List[str], Callable]] = {
+    function_selector(signature): (
+        signature[: signature.index("(")],
+        _split_types(signature),
+        decoder,
+    )
+    for signature, decoder in _SIGNATURES
+}
+
+
+def decode_router_calldata(
+    calldata: Union[bytes, str], value: int = 0
+) -> Optional[DecodedSwap]:
+    """
+    Decodes a swap sent to the Uniswap V2/V3 routers or to the 1inch, 0x or Paraswap
+    aggregators. `value` is the transaction value, which is the input amount of swaps
+    paying with native ETH. Returns None for calldata with an unknown selector, and
+    raises AbiError if a known selector carries malformed arguments
+    """
+    if isinstance(calldata, str):
+        calldata = bytes.fromhex(
+            calldata[2:] if calldata.startswith("0x") else calldata
+        )
+    calldata = bytes(calldata)
+
+    entry = _DECODERS.get(calldata[:4])
+    if entry is None:
+        return None
+    function, types, decoder = entry
+    args = abi_decode(types, calldata[4:])
+    try:
+        return decoder(function, args, value)
+    except (IndexError, ValueError) as e:
+        raise AbiError(f"Malformed {function} arguments: {e}") from e
@@ -0,0 +1,2 @@
+from .router_encoder import *
+from .v4 import *
\ No newline at end of file
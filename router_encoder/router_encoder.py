@@ -0,0 +1,275 @@
+import time
+from typing import List, Optional, Sequence, Tuple
+from ..abi import abi_encode, function_selector
+from .v4 import V4Actions
+
+__all__ = [
+    "V3_SWAP_EXACT_IN",
+    "V3_SWAP_EXACT_OUT",
+    "SWEEP",
+    "TRANSFER",
+    "PAY_PORTION",
+    "V2_SWAP_EXACT_IN",
+    "V2_SWAP_EXACT_OUT",
+    "WRAP_ETH",
+    "UNWRAP_WETH",
+    "V4_SWAP",
+    "ALLOW_REVERT_FLAG",
+    "MSG_SENDER",
+    "ADDRESS_THIS",
+    "CONTRACT_BALANCE",
+    "EXECUTE_SELECTOR",
+    "EXECUTE_NO_DEADLINE_SELECTOR",
+    "encode_v3_path",
+    "UniversalRouterCommands",
+]
+
+# Universal Router command types, see Commands.sol
+V3_SWAP_EXACT_IN = 0x00
+V3_SWAP_EXACT_OUT = 0x01
+SWEEP = 0x04
+TRANSFER = 0x05
+PAY_PORTION = 0x06
+V2_SWAP_EXACT_IN = 0x08
+V2_SWAP_EXACT_OUT = 0x09
+WRAP_ETH = 0x0B
+UNWRAP_WETH = 0x0C
+V4_SWAP = 0x10
+# set on a command byte to let the rest of the commands run if it reverts
+ALLOW_REVERT_FLAG = 0x80
+
+# special recipients and amounts understood by the router, see Constants.sol
+MSG_SENDER = "0x0000000000000000000000000000000000000001"
+ADDRESS_THIS = "0x0000000000000000000000000000000000000002"
+# uses the router's entire balance of the token
+CONTRACT_BALANCE = 2 ** 255
+
+EXECUTE_SELECTOR = function_selector("execute(bytes,bytes[],uint256)")
+EXECUTE_NO_DEADLINE_SELECTOR = function_selector("execute(bytes,bytes[])")
+
+
+def encode_v3_path(
+    tokens: Sequence[str],
+    fees: Sequence[int],
+    exact_output: bool = False,
+) -> bytes:
+    """
+    Packs a V3 swap path (token | fee | token | ...) from the tokens in swap order and
+    the fee of each pool between them. Exact output swaps are executed from the
+    output backwards, so with `exact_output` the path is encoded in reverse
+    """
+    assert len(tokens) >= 2, "A path needs at least two tokens"
+    assert len(fees) == len(tokens) - 1, "Expected one fee for each pool in the path"
+    if exact_output:
+        tokens, fees = list(reversed(tokens)), list(reversed(fees))
+    path = bytes.fromhex(tokens[0][2:])
+    for fee, token in zip(fees, tokens[1:]):
+        path += fee.to_bytes(3, "big") + bytes.fromhex(token[2:])
+    return path
+
+
+class UniversalRouterCommands:
+    """
+    Builds the command sequence of a Universal Router execute() call. Each method
+    appends one command and returns the builder, so calls can be chained. Recipients
+    default to the caller (MSG_SENDER); pass ADDRESS_THIS to keep the output in the
+    router for a later command, and CONTRACT_BALANCE as an input amount to spend
+    everything the router holds
+    """
+
+    def __init__(self) -> None:
+        self.commands: List[int] = []
+        self.inputs: List[bytes] = []
+
+    def _add(
+        self,
+        command: int,
+        types: Sequence[str],
+        values: Sequence,
+        allow_revert: bool = False,
+    ) -> "UniversalRouterCommands":
+        self.commands.append(command | (ALLOW_REVERT_FLAG if allow_revert else 0))
+        self.inputs.append(abi_encode(types, values))
+        return self
+
+    def v2_swap_exact_in(
+        self,
+        amount_in: int,
+        amount_out_min: int,
+        path: Sequence[str],
+        recipient: str = MSG_SENDER,
+        payer_is_user: bool = True,
+        allow_revert: bool = False,
+    ) -> "UniversalRouterCommands":
+        return self._add(
+            V2_SWAP_EXACT_IN,
+            ["address", "uint256", "uint256", "address[]", "bool"],
+            [recipient, amount_in, amount_out_min, list(path), payer_is_user],
+            allow_revert,
+        )
+
+    def v2_swap_exact_out(
+        self,
+        amount_out: int,
+        amount_in_max: int,
+        path: Sequence[str],
+        recipient: str = MSG_SENDER,
+        payer_is_user: bool = True,
+        allow_revert: bool = False,
+    ) -> "UniversalRouterCommands":
+        """
+        Appends a V2 exact output swap. Unlike V3, the path is given in swap order
+        """
+        return self._add(
+            V2_SWAP_EXACT_OUT,
+            ["address", "uint256", "uint256", "address[]", "bool"],
+            [recipient, amount_out, amount_in_max, list(path), payer_is_user],
+            allow_revert,
+        )
+
+    def v3_swap_exact_in(
+        self,
+        amount_in: int,
+        amount_out_min: int,
+        tokens: Sequence[str],
+        fees: Sequence[int],
+        recipient: str = MSG_SENDER,
+        payer_is_user: bool = True,
+        allow_revert: bool = False,
+    ) -> "UniversalRouterCommands":
+        return self._add(
+            V3_SWAP_EXACT_IN,
+            ["address", "uint256", "uint256", "bytes", "bool"],
+            [
+                recipient,
+                amount_in,
+                amount_out_min,
+                encode_v3_path(tokens, fees),
+                payer_is_user,
+            ],
+            allow_revert,
+        )
+
+    def v3_swap_exact_out(
+        self,
+        amount_out: int,
+        amount_in_max: int,
+        tokens: Sequence[str],
+        fees: Sequence[int],
+        recipient: str = MSG_SENDER,
+        payer_is_user: bool = True,
+        allow_revert: bool = False,
+    ) -> "UniversalRouterCommands":
+        """
+        Appends a V3 exact output swap. The tokens and fees are given in swap order
+        (input token first) and reversed for encoding
+        """
+        return self._add(
+            V3_SWAP_EXACT_OUT,
+            ["address", "uint256", "uint256", "bytes", "bool"],
+            [
+                recipient,
+                amount_out,
+                amount_in_max,
+                encode_v3_path(tokens, fees, exact_output=True),
+                payer_is_user,
+            ],
+            allow_revert,
+        )
+
+    def v4_swap(
+        self,
+        actions: V4Actions,
+        allow_revert: bool = False,
+    ) -> "UniversalRouterCommands":
+        """
+        Appends a V4 swap, executing the given V4Actions inside a PoolManager unlock
+        """
+        self.commands.append(V4_SWAP | (ALLOW_REVERT_FLAG if allow_revert else 0))
+        self.inputs.append(actions.encode())
+        return self
+
+    def wrap_eth(
+        self,
+        amount: int,
+        recipient: str = ADDRESS_THIS,
+    ) -> "UniversalRouterCommands":
+        """
+        Wraps `amount` of the ETH held by the router (CONTRACT_BALANCE for all of it),
+        by default keeping the WETH in the router for a following swap
+        """
+        return self._add(WRAP_ETH, ["address", "uint256"], [recipient, amount])
+
+    def unwrap_weth(
+        self,
+        amount_min: int,
+        recipient: str = MSG_SENDER,
+    ) -> "UniversalRouterCommands":
+        """
+        Unwraps all the WETH held by the router, reverting if it is less than
+        `amount_min`
+        """
+        return self._add(UNWRAP_WETH, ["address", "uint256"], [recipient, amount_min])
+
+    def sweep(
+        self,
+        token: str,
+        amount_min: int = 0,
+        recipient: str = MSG_SENDER,
+    ) -> "UniversalRouterCommands":
+        """
+        Sends the router's entire balance of a token, reverting if it is less than
+        `amount_min`. The zero address sweeps ETH
+        """
+        return self._add(
+            SWEEP, ["address", "address", "uint256"], [token, recipient, amount_min]
+        )
+
+    def transfer(
+        self,
+        token: str,
+        recipient: str,
+        amount: int,
+    ) -> "UniversalRouterCommands":
+        return self._add(
+            TRANSFER, ["address", "address", "uint256"], [token, recipient, amount]
+        )
+
+    def pay_portion(
+        self,
+        token: str,
+        recipient: str,
+        bips: int,
+    ) -> "UniversalRouterCommands":
+        assert 0 <= bips <= 10_000, f"Portion of {bips} bips is out of range"
+        return self._add(
+            PAY_PORTION, ["address", "address", "uint256"], [token, recipient, bips]
+        )
+
+    def encode(self) -> Tuple[bytes, List[bytes]]:
+        """
+        Returns the packed command bytes and the list of encoded inputs
+        """
+        return bytes(self.commands), list(self.inputs)
+
+    def calldata(
+        self,
+        deadline: Optional[int] = None,
+        ttl: Optional[int] = None,
+    ) -> bytes:
+        """
+        Returns the execute() calldata. The deadline is a unix timestamp, or is set
+        `ttl` seconds from now. Without either, the overload without a deadline is used
+        """
+        assert self.commands, "No commands to execute"
+        assert deadline is None or ttl is None, "Give either a deadline or a ttl"
+        commands, inputs = self.encode()
+        if ttl is not None:
+            deadline = int(time.time()) + ttl
+        if deadline is None:
+            return EXECUTE_NO_DEADLINE_SELECTOR + abi_encode(
+                ["bytes", "bytes[]"], [commands, inputs]
+            )
+        return EXECUTE_SELECTOR + abi_encode(
+            ["bytes", "bytes[]", "uint256"], [commands, inputs, deadline]
+        )
@@ -0,0 +1,177 @@
+from dataclasses import dataclass
+from enum import IntEnum
+from typing import List, Tuple
+from ..abi import abi_encode
+
+__all__ = ["OPEN_DELTA", "V4Action", "V4PoolKey", "V4Actions"]
+
+# amount placeholder for settling or taking the full open delta of a currency
+OPEN_DELTA = 0
+
+_POOL_KEY = "(address,address,uint24,int24,address)"
+
+
+class V4Action(IntEnum):
+    """
+    V4Router and PositionManager action types, see Actions.sol
+    """
+
+    SWAP_EXACT_IN_SINGLE = 0x06
+    SWAP_EXACT_IN = 0x07
+    SWAP_EXACT_OUT_SINGLE = 0x08
+    SWAP_EXACT_OUT = 0x09
+    SETTLE = 0x0B
+    SETTLE_ALL = 0x0C
+    SETTLE_PAIR = 0x0D
+    TAKE = 0x0E
+    TAKE_ALL = 0x0F
+    TAKE_PORTION = 0x10
+    TAKE_PAIR = 0x11
+
+
+@dataclass(frozen=True)
+class V4PoolKey:
+    """
+    Identifies a V4 pool. currency0 must sort below currency1, with the zero address
+    used for native ETH
+    """
+
+    currency0: str
+    currency1: str
+    fee: int
+    tick_spacing: int
+    hooks: str = "0x0000000000000000000000000000000000000000"
+
+    def __post_init__(self) -> None:
+        assert int(self.currency0, 16) < int(
+            self.currency1, 16
+        ), "currency0 must sort below currency1"
+
+    def as_tuple(self) -> Tuple:
+        return (
+            self.currency0,
+            self.currency1,
+            self.fee,
+            self.tick_spacing,
+            self.hooks,
+        )
+
+
+class V4Actions:
+    """
+    Builds the action sequence executed by the V4Router inside a PoolManager unlock.
+    Each method appends one action and returns the builder. A swap leaves open deltas
+    that must be closed by settling the input currency and taking the output, e.g.
+    swap_exact_in_single(...).settle_all(currency_in, max).take_all(currency_out, min)
+    """
+
+    def __init__(self) -> None:
+        self.actions: List[int] = []
+        self.params: List[bytes] = []
+
+    def _add(self, action: V4Action, types: List[str], values: List) -> "V4Actions":
+        self.actions.append(action)
+        self.params.append(abi_encode(types, values))
+        return self
+
+    def swap_exact_in_single(
+        self,
+        pool_key: V4PoolKey,
+        zero_for_one: bool,
+        amount_in: int,
+        amount_out_min: int,
+        hook_data: bytes = b"",
+    ) -> "V4Actions":
+        return self._add(
+            V4Action.SWAP_EXACT_IN_SINGLE,
+            [f"({_POOL_KEY},bool,uint128,uint128,bytes)"],
+            [
+                (
+                    pool_key.as_tuple(),
+                    zero_for_one,
+                    amount_in,
+                    amount_out_min,
+                    hook_data,
+                )
+            ],
+        )
+
+    def swap_exact_out_single(
+        self,
+        pool_key: V4PoolKey,
+        zero_for_one: bool,
+        amount_out: int,
+        amount_in_max: int,
+        hook_data: bytes = b"",
+    ) -> "V4Actions":
+        return self._add(
+            V4Action.SWAP_EXACT_OUT_SINGLE,
+            [f"({_POOL_KEY},bool,uint128,uint128,bytes)"],
+            [
+                (
+                    pool_key.as_tuple(),
+                    zero_for_one,
+                    amount_out,
+                    amount_in_max,
+                    hook_data,
+                )
+            ],
+        )
+
+    def settle(
+        self,
+        currency: str,
+        amount: int = OPEN_DELTA,
+        payer_is_user: bool = True,
+    ) -> "V4Actions":
+        """
+        Pays `amount` of a currency to the PoolManager (OPEN_DELTA for the full debt),
+        from the caller or, without `payer_is_user`, from the router's balance
+        """
+        return self._add(
+            V4Action.SETTLE,
+            ["address", "uint256", "bool"],
+            [currency, amount, payer_is_user],
+        )
+
+    def settle_all(self, currency: str, amount_max: int) -> "V4Actions":
+        """
+        Pays the full debt of a currency from the caller, reverting above `amount_max`
+        """
+        return self._add(
+            V4Action.SETTLE_ALL, ["address", "uint256"], [currency, amount_max]
+        )
+
+    def take(
+        self,
+        currency: str,
+        recipient: str,
+        amount: int = OPEN_DELTA,
+    ) -> "V4Actions":
+        """
+        Withdraws `amount` of a currency owed by the PoolManager (OPEN_DELTA for all of
+        it) to the recipient
+        """
+        return self._add(
+            V4Action.TAKE,
+            ["address", "address", "uint256"],
+            [currency, recipient, amount],
+        )
+
+    def take_all(self, currency: str, amount_min: int) -> "V4Actions":
+        """
+        Withdraws everything owed in a currency to the caller, reverting below
+        `amount_min`
+        """
+        return self._add(
+            V4Action.TAKE_ALL, ["address", "uint256"], [currency, amount_min]
+        )
+
+    def encode(self) -> bytes:
+        """
+        Returns the unlock payload, abi.encode(bytes actions, bytes[] params), as
+        passed to PoolManager.unlock by the V4Router and carried by the Universal
+        Router's V4_SWAP command
+        """
+        assert self.actions, "No actions to encode"
+        return abi_encode(["bytes", "bytes[]"], [bytes(self.actions), self.params])
@@ -0,0 +1,6 @@
+from .responses import *
+from .state_overrides import *
+from .call_batch import *
+from .receipt_columns import *
+from .rate_limiter import *
+from .retry import *
\ No newline at end of file
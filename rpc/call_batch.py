@@ -0,0 +1,74 @@
+import json
+from typing import Dict, List, Optional, Sequence, Tuple, Union
+from .responses import _hex_bytes, parse_rpc_batch_response
+from .state_overrides import encode_state_overrides
+
+__all__ = ["build_call_batch", "parse_call_batch_response"]
+
+
+def build_call_batch(
+    calls: List[Tuple[str, Union[bytes, str]]],
+    block: Union[int, str] = "latest",
+    state_overrides: Optional[Dict] = None,
+    first_id: int = 1,
+) -> bytes:
+    """
+    Builds a JSON-RPC batch request body with one eth_call per (to, calldata) pair,
+    all executed against the same block. Request ids are assigned contiguously from
+    `first_id`, the ids to pass to parse_call_batch_response. State overrides, given
+    in the format accepted by encode_state_overrides, are applied to every call
+    """
+
+    block_identifier = hex(block) if isinstance(block, int) else block
+    encoded_overrides = (
+        encode_state_overrides(state_overrides) if state_overrides else None
+    )
+
+    requests = []
+    for i, (to, calldata) in enumerate(calls):
+        params = [
+            {
+                "to": to,
+                "data": (
+                    calldata
+                    if isinstance(calldata, str)
+                    else "0x" + bytes(calldata).hex()
+                ),
+            },
+            block_identifier,
+        ]
+        if encoded_overrides is not None:
+            params.append(encoded_overrides)
+        requests.append(
+            {
+                "jsonrpc": "2.0",
+                "id": first_id + i,
+                "method": "eth_call",
+                "params": params,
+            }
+        )
+
+    return json.dumps(requests, separators=(",", ":")).encode()
+
+
+def parse_call_batch_response(
+    data: Union[bytes, str, List],
+    raise_on_error: bool = True,
+    ids: Optional[Sequence[int]] = None,
+) -> List:
+    """
+    Parses the response to a batch built by build_call_batch and returns the raw
+    return data of each call. Responses are matched to the calls by id, so pass the
+    batch's ids, e.g. range(first_id, first_id + len(calls)), to have a missing or
+    unexpected response raise RpcResponseError; without them the results are
+    ordered by id.
+
+    With `raise_on_error=False`, calls that failed (including reverts, whose revert
+    data is kept in the error's `data` attribute) are returned in place as
+    RpcResponseError instances
+    """
+
+    return [
+        result if isinstance(result, Exception) else _hex_bytes(result)
+        for result in parse_rpc_batch_response(data, raise_on_error, ids)
+    ]
@@ -0,0 +1,114 @@
+import asyncio
+import threading
+import time
+from typing import Callable, Dict, Optional
+
+__all__ = ["RateLimiter"]
+
+_limiters: Dict[str, "RateLimiter"] = {}
+_limiters_lock = threading.Lock()
+
+
+class RateLimiter:
+    """
+    Thread-safe token bucket admitting `rate` requests per second on average, with
+    bursts of up to `burst` requests. One limiter can be shared by every provider and
+    subsystem using an endpoint, so their combined traffic stays within its quota
+    """
+
+    def __init__(
+        self,
+        rate: float,
+        burst: Optional[int] = None,
+        clock: Callable[[], float] = time.monotonic,
+    ) -> None:
+        assert rate > 0, "The rate must be positive"
+        self.rate = rate
+        self.burst = burst if burst is not None else max(1, int(rate))
+        assert self.burst >= 1, "The burst size must be at least 1"
+        self._clock = clock
+        self._tokens = float(self.burst)
+        self._updated = clock()
+        self._lock = threading.Lock()
+
+    def __repr__(self) -> str:
+        return f"RateLimiter(rate={self.rate}, burst={self.burst})"
+
+    @classmethod
+    def for_endpoint(
+        cls, url: str, rate: float, burst: Optional[int] = None
+    ) -> "RateLimiter":
+        """
+        Returns the limiter shared by all users of an endpoint, creating it with the
+        given rate on first use. Later calls return the existing limiter unchanged
+        """
+        with _limiters_lock:
+            limiter = _limiters.get(url)
+            if limiter is None:
+                limiter = _limiters[url] = cls(rate, burst)
+            return limiter
+
+    def _refill(self) -> None:
+        now = self._clock()
+        self._tokens = min(
+            float(self.burst), self._tokens + (now - self._updated) * self.rate
+        )
+        self._updated = now
+
+    def _reserve(
+        self, tokens: int, max_delay: Optional[float] = None
+    ) -> Optional[float]:
+        """
+        Takes `tokens` from the bucket, possibly going into debt, and returns the time
+        to wait before the reservation is covered. Nothing is taken, and None is
+        returned, if that would take longer than `max_delay`
+        """
+        with self._lock:
+            self._refill()
+            delay = max(0.0, (tokens - self._tokens) / self.rate)
+            if max_delay is not None and delay > max_delay:
+                return None
+            self._tokens -= tokens
+            return delay
+
+    @property
+    def available(self) -> float:
+        with self._lock:
+            self._refill()
+            return max(0.0, self._tokens)
+
+    def try_acquire(self, tokens: int = 1) -> bool:
+        """
+        Takes `tokens` if they are available right away, without blocking
+        """
+        assert 1 <= tokens <= self.burst, f"Cannot acquire {tokens} tokens at once"
+        with self._lock:
+            self._refill()
+            if self._tokens < tokens:
+                return False
+            self._tokens -= tokens
+            return True
+
+    def acquire(self, tokens: int = 1, timeout: Optional[float] = None) -> bool:
+        """
+        Blocks until `tokens` are available and takes them. Requests larger than the
+        burst size wait for the bucket to refill past empty. Returns False without
+        taking any if they would not be available within `timeout` seconds
+        """
+        assert tokens >= 1, f"Cannot acquire {tokens} tokens"
+        # waiters queue up by reserving in order, so none of them is starved
+        delay = self._reserve(tokens, timeout)
+        if delay is None:
+            return False
+        if delay:
+            time.sleep(delay)
+        return True
+
+    async def acquire_async(self, tokens: int = 1) -> None:
+        """
+        Awaitable version of acquire, waiting without blocking the event loop
+        """
+        assert tokens >= 1, f"Cannot acquire {tokens} tokens"
+        delay = self._reserve(tokens)
+        if delay:
+            await asyncio.sleep(delay)
@@ -0,0 +1,97 @@
+from dataclasses import dataclass
+from typing import Dict, List, Union
+import numpy
+from .responses import _hex_bytes, _load, parse_rpc_response
+
+__all__ = ["ReceiptColumns", "parse_block_receipts"]
+
+
+@dataclass
+class ReceiptColumns:
+    """
+    Receipts of a block stored column-wise, one array entry per receipt. Logs are
+    flattened into their own columns, with `log_receipt_index` giving the position of
+    the owning receipt
+    """
+
+    transaction_hash: numpy.ndarray
+    transaction_index: numpy.ndarray
+    status: numpy.ndarray
+    gas_used: numpy.ndarray
+    cumulative_gas_used: numpy.ndarray
+    effective_gas_price: numpy.ndarray
+    sender: numpy.ndarray
+    to: numpy.ndarray
+
+    log_receipt_index: numpy.ndarray
+    log_index: numpy.ndarray
+    log_address: numpy.ndarray
+    log_topics: numpy.ndarray
+    log_data: numpy.ndarray
+
+    def __len__(self) -> int:
+        return len(self.transaction_hash)
+
+
+def parse_block_receipts(data: Union[bytes, str, Dict, List]) -> ReceiptColumns:
+    """
+    Parses the result of eth_getBlockReceipts (a bare result list or a full response)
+    directly into columnar arrays. Numeric columns are uint64 (status is uint8),
+    while hashes, addresses and log payloads are object arrays of bytes and str
+    """
+
+    receipts = _load(data)
+    if isinstance(receipts, dict):
+        receipts = parse_rpc_response(receipts)
+
+    logs = [
+        (receipt_index, log)
+        for receipt_index, receipt in enumerate(receipts)
+        for log in receipt["logs"]
+    ]
+
+    def _column(values, dtype) -> numpy.ndarray:
+        if dtype is object:
+            # filled element-wise, so that equal-length lists (log topics) are not
+            # broadcast into a second dimension
+            column = numpy.empty(len(values), dtype=object)
+            for i, value in enumerate(values):
+                column[i] = value
+            return column
+        return numpy.array(values, dtype=dtype)
+
+    return ReceiptColumns(
+        transaction_hash=_column(
+            [_hex_bytes(receipt["transactionHash"]) for receipt in receipts], object
+        ),
+        transaction_index=_column(
+            [int(receipt["transactionIndex"], 16) for receipt in receipts],
+            numpy.uint64,
+        ),
+        status=_column(
+            [int(receipt.get("status", "0x1"), 16) for receipt in receipts],
+            numpy.uint8,
+        ),
+        gas_used=_column(
+            [int(receipt["gasUsed"], 16) for receipt in receipts], numpy.uint64
+        ),
+        cumulative_gas_used=_column(
+            [int(receipt["cumulativeGasUsed"], 16) for receipt in receipts],
+            numpy.uint64,
+        ),
+        effective_gas_price=_column(
+            [int(receipt.get("effectiveGasPrice", "0x0"), 16) for receipt in receipts],
+            numpy.uint64,
+        ),
+        sender=_column([receipt["from"] for receipt in receipts], object),
+        to=_column([receipt.get("to") for receipt in receipts], object),
+        log_receipt_index=_column(
+            [receipt_index for receipt_index, _ in logs], numpy.uint64
+        ),
+        log_index=_column([int(log["logIndex"], 16) for _, log in logs], numpy.uint64),
+        log_address=_column([log["address"] for _, log in logs], object),
+        log_topics=_column(
+            [[_hex_bytes(topic) for topic in log["topics"]] for _, log in logs], object
+        ),
+        log_data=_column([_hex_bytes(log["data"]) for _, log in logs], object),
+    )
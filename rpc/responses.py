@@ -0,0 +1,296 @@
+import json
+from dataclasses import dataclass, field
+from typing import Any, Dict, List, Optional, Sequence, Union
+from ..exceptions import RpcResponseError
+
+__all__ = [
+    "Log",
+    "BlockHeader",
+    "Transaction",
+    "Receipt",
+    "parse_rpc_response",
+    "parse_rpc_batch_response",
+    "parse_log",
+    "parse_logs",
+    "parse_block_header",
+    "parse_transaction",
+    "parse_receipt",
+    "parse_receipts",
+    "parse_txpool_content",
+]
+
+try:
+    # orjson parses large responses several times faster than the standard library
+    import orjson
+
+    _loads = orjson.loads
+except ImportError:
+    _loads = json.loads
+
+# JSON-RPC "internal error", for batch responses that cannot be matched to requests
+_BATCH_MISMATCH_CODE = -32603
+
+
+@dataclass
+class Log:
+    address: str
+    topics: List[bytes]
+    data: bytes
+    block_number: Optional[int]
+    block_hash: Optional[bytes]
+    transaction_hash: Optional[bytes]
+    transaction_index: Optional[int]
+    log_index: Optional[int]
+    removed: bool = False
+
+
+@dataclass
+class BlockHeader:
+    number: int
+    hash: bytes
+    parent_hash: bytes
+    timestamp: int
+    gas_limit: int
+    gas_used: int
+    base_fee_per_gas: Optional[int]
+    logs_bloom: bytes
+    miner: str
+
+
+@dataclass
+class Transaction:
+    hash: bytes
+    sender: str
+    to: Optional[str]
+    nonce: int
+    value: int
+    gas: int
+    input: bytes
+    type: int = 0
+    gas_price: Optional[int] = None
+    max_fee_per_gas: Optional[int] = None
+    max_priority_fee_per_gas: Optional[int] = None
+    chain_id: Optional[int] = None
+    block_number: Optional[int] = None
+    access_list: List[Dict] = field(default_factory=list)
+
+
+@dataclass
+class Receipt:
+    transaction_hash: bytes
+    transaction_index: int
+    block_number: int
+    status: int
+    gas_used: int
+    cumulative_gas_used: int
+    effective_gas_price: int
+    sender: str
+    to: Optional[str]
+    contract_address: Optional[str]
+    logs: List[Log]
+    type: int = 0
+
+
+def _hex_bytes(value: Optional[str]) -> Optional[bytes]:
+    if value is None:
+        return None
+    value = value[2:] if value.startswith("0x") else value
+    if len(value) % 2:
+        value = "0" + value
+    return bytes.fromhex(value)
+
+
+def _hex_int(value: Optional[str]) -> Optional[int]:
+    if value is None:
+        return None
+    return int(value, 16)
+
+
+def _load(data: Union[bytes, str, Dict, List]):
+    if isinstance(data, (bytes, bytearray, memoryview, str)):
+        return _loads(bytes(data) if not isinstance(data, str) else data)
+    return data
+
+
+def parse_rpc_response(data: Union[bytes, str, Dict]) -> Any:
+    """
+    Parses a single JSON-RPC response and returns its result, raising RpcResponseError
+    if the endpoint returned an error
+    """
+
+    response = _load(data)
+    if "error" in response:
+        error = response["error"]
+        raise RpcResponseError(
+            error.get("code"), error.get("message"), error.get("data")
+        )
+    return response["result"]
+
+
+def parse_rpc_batch_response(
+    data: Union[bytes, str, List],
+    raise_on_error: bool = True,
+    ids: Optional[Sequence[int]] = None,
+) -> List:
+    """
+    Parses a batched JSON-RPC response. Endpoints may answer a batch in any order, so
+    responses are matched by id: results are returned in the order of `ids`, the ids
+    of the requests sent, or ordered by id if they are not given.
+
+    Raises RpcResponseError if a response has no id or a duplicate one, or if the ids
+    differ from `ids`, rather than assign a result to the wrong request. With
+    `raise_on_error=False`, failed requests are returned in place as
+    RpcResponseError instances instead of aborting the whole batch
+    """
+
+    responses = _load(data)
+    if isinstance(responses, dict):
+        # some endpoints answer a malformed batch with a single error object
+        parse_rpc_response(responses)
+        responses = [responses]
+
+    by_id: Dict[int, Dict] = {}
+    for response in responses:
+        response_id = response.get("id")
+        if response_id is None:
+            raise RpcResponseError(
+                _BATCH_MISMATCH_CODE, "Batch response without an id", response
+            )
+        if response_id in by_id:
+            raise RpcResponseError(
+                _BATCH_MISMATCH_CODE, f"Duplicate batch response id {response_id}"
+            )
+        by_id[response_id] = response
+
+    if ids is None:
+        ids = sorted(by_id)
+    else:
+        missing = [request_id for request_id in ids if request_id not in by_id]
+        unexpected = sorted(set(by_id) - set(ids))
+        if missing or unexpected:
+            raise RpcResponseError(
+                _BATCH_MISMATCH_CODE,
+                f"Batch response ids do not match the requests: missing {missing}, "
+                f"unexpected {unexpected}",
+            )
+
+    results = []
+    for request_id in ids:
+        try:
+            results.append(parse_rpc_response(by_id[request_id]))
+        except RpcResponseError as e:
+            if raise_on_error:
+                raise
+            results.append(e)
+    return results
+
+
+def parse_log(log: Dict) -> Log:
+    return Log(
+        address=log["address"],
+        topics=[_hex_bytes(topic) for topic in log["topics"]],
+        data=_hex_bytes(log["data"]),
+        block_number=_hex_int(log.get("blockNumber")),
+        block_hash=_hex_bytes(log.get("blockHash")),
+        transaction_hash=_hex_bytes(log.get("transactionHash")),
+        transaction_index=_hex_int(log.get("transactionIndex")),
+        log_index=_hex_int(log.get("logIndex")),
+        removed=log.get("removed", False),
+    )
+
+
+def parse_logs(data: Union[bytes, str, Dict, List]) -> List[Log]:
+    """
+    Parses the result of eth_getLogs (a bare result list or a full response)
+    """
+
+    logs = _load(data)
+    if isinstance(logs, dict):
+        logs = parse_rpc_response(logs)
+    return [parse_log(log) for log in logs]
+
+
+def parse_block_header(header: Dict) -> BlockHeader:
+    """
+    Parses a newHeads notification or the header fields of an eth_getBlockByNumber
+    result
+    """
+    return BlockHeader(
+        number=_hex_int(header["number"]),
+        hash=_hex_bytes(header["hash"]),
+        parent_hash=_hex_bytes(header["parentHash"]),
+        timestamp=_hex_int(header["timestamp"]),
+        gas_limit=_hex_int(header["gasLimit"]),
+        gas_used=_hex_int(header["gasUsed"]),
+        base_fee_per_gas=_hex_int(header.get("baseFeePerGas")),
+        logs_bloom=_hex_bytes(header["logsBloom"]),
+        miner=header["miner"],
+    )
+
+
+def parse_transaction(transaction: Dict) -> Transaction:
+    return Transaction(
+        hash=_hex_bytes(transaction["hash"]),
+        sender=transaction["from"],
+        to=transaction.get("to"),
+        nonce=_hex_int(transaction["nonce"]),
+        value=_hex_int(transaction["value"]),
+        gas=_hex_int(transaction["gas"]),
+        input=_hex_bytes(transaction.get("input", transaction.get("data", "0x"))),
+        type=_hex_int(transaction.get("type", "0x0")),
+        gas_price=_hex_int(transaction.get("gasPrice")),
+        max_fee_per_gas=_hex_int(transaction.get("maxFeePerGas")),
+        max_priority_fee_per_gas=_hex_int(transaction.get("maxPriorityFeePerGas")),
+        chain_id=_hex_int(transaction.get("chainId")),
+        block_number=_hex_int(transaction.get("blockNumber")),
+        access_list=transaction.get("accessList", []),
+    )
+
+
+def parse_receipt(receipt: Dict) -> Receipt:
+    return Receipt(
+        transaction_hash=_hex_bytes(receipt["transactionHash"]),
+        transaction_index=_hex_int(receipt["transactionIndex"]),
+        block_number=_hex_int(receipt["blockNumber"]),
+        status=_hex_int(receipt.get("status", "0x1")),
+        gas_used=_hex_int(receipt["gasUsed"]),
+        cumulative_gas_used=_hex_int(receipt["cumulativeGasUsed"]),
+        effective_gas_price=_hex_int(receipt.get("effectiveGasPrice", "0x0")),
+        sender=receipt["from"],
+        to=receipt.get("to"),
+        contract_address=receipt.get("contractAddress"),
+        logs=[parse_log(log) for log in receipt["logs"]],
+        type=_hex_int(receipt.get("type", "0x0")),
+    )
+
+
+def parse_receipts(data: Union[bytes, str, Dict, List]) -> List[Receipt]:
+    """
+    Parses the result of eth_getBlockReceipts (a bare result list or a full response)
+    """
+
+    receipts = _load(data)
+    if isinstance(receipts, dict):
+        receipts = parse_rpc_response(receipts)
+    return [parse_receipt(receipt) for receipt in receipts]
+
+
+def parse_txpool_content(data: Union[bytes, str, Dict]) -> Dict[str, List[Transaction]]:
+    """
+    Parses the result of txpool_content into lists of pending and queued transactions,
+    each sorted by sender and nonce
+    """
+
+    content = _load(data)
+    if "result" in content or "error" in content:
+        content = parse_rpc_response(content)
+
+    parsed = {}
+    for pool in ("pending", "queued"):
+        transactions = []
+        for sender in sorted(content.get(pool, {})):
+            by_nonce = content[pool][sender]
+            for nonce in sorted(by_nonce, key=int):
+                transactions.append(parse_transaction(by_nonce[nonce]))
+        parsed[pool] = transactions
+    return parsed
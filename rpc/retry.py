@@ -0,0 +1,56 @@
+import random
+from dataclasses import dataclass
+from typing import Optional, Tuple
+
+__all__ = ["RETRY_STATUS_CODES", "RETRY_RPC_CODES", "RetryPolicy"]
+
+# HTTP status codes that indicate a transient failure worth retrying
+RETRY_STATUS_CODES = (429, 500, 502, 503, 504)
+# JSON-RPC error codes that indicate a transient failure: "limit exceeded" (EIP-1474)
+RETRY_RPC_CODES = (-32005,)
+
+
+@dataclass(frozen=True)
+class RetryPolicy:
+    """
+    How a provider retries failed requests. Transport errors and the listed HTTP
+    status and JSON-RPC error codes are retried up to `max_retries` times, waiting
+    `backoff * multiplier ** (attempt - 1)` seconds (at most `max_backoff`) before
+    each retry, randomly shortened by up to the `jitter` fraction so that clients
+    failing together do not retry together.
+
+    With a `hedge_url`, a request still unanswered after `hedge_after` seconds is
+    also sent to that endpoint, and the first response is used
+    """
+
+    max_retries: int = 3
+    backoff: float = 0.25
+    multiplier: float = 2.0
+    max_backoff: float = 10.0
+    jitter: float = 0.5
+    retry_status_codes: Tuple[int, ...] = RETRY_STATUS_CODES
+    retry_rpc_codes: Tuple[int, ...] = RETRY_RPC_CODES
+    hedge_url: Optional[str] = None
+    hedge_after: float = 1.0
+
+    def __post_init__(self) -> None:
+        assert self.max_retries >= 0, "The number of retries must be non-negative"
+        assert self.backoff >= 0 and self.max_backoff >= 0, "Invalid backoff"
+        assert self.multiplier >= 1, "The backoff multiplier must be at least 1"
+        assert 0 <= self.jitter <= 1, "The jitter must be a fraction in [0, 1]"
+        assert self.hedge_after >= 0, "The hedging delay must be non-negative"
+
+    def delay(self, attempt: int) -> float:
+        """
+        Seconds to wait before retry number `attempt` (starting at 1)
+        """
+        delay = min(self.backoff * self.multiplier ** (attempt - 1), self.max_backoff)
+        if self.jitter:
+            delay *= 1 - self.jitter * random.random()
+        return delay
+
+    def retry_status(self, status_code: int) -> bool:
+        return status_code in self.retry_status_codes
+
+    def retry_rpc_error(self, code: Optional[int]) -> bool:
+        return code in self.retry_rpc_codes
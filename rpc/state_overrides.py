@@ -0,0 +1,139 @@
+from typing import Dict, Optional, Union
+from eth_utils import keccak, to_checksum_address
+from ..storage import mapping_slot
+
+__all__ = [
+    "KNOWN_BALANCE_SLOTS",
+    "encode_state_overrides",
+    "erc20_balance_slot",
+    "erc20_balance_override",
+]
+
+# Storage slot of the `balanceOf` mapping for common mainnet tokens
+KNOWN_BALANCE_SLOTS = {
+    # WETH9
+    "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2": 3,
+    # USDC (FiatTokenV2)
+    "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48": 9,
+    # USDT
+    "0xdAC17F958D2ee523a2206206994597C13D831ec7": 2,
+    # DAI
+    "0x6B175474E89094C44Da98b954EedeAC495271d0F": 2,
+    # WBTC
+    "0x2260FAC5E5542a773Aa44fBCfeDf7C193bc2C599": 0,
+}
+
+
+def _to_bytes(value: Union[bytes, str, int], length: Optional[int] = None) -> bytes:
+    if isinstance(value, int):
+        return value.to_bytes(length or 32, "big")
+    if isinstance(value, str):
+        value = value[2:] if value.startswith("0x") else value
+        if len(value) % 2:
+            value = "0" + value
+        value = bytes.fromhex(value)
+    value = bytes(value)
+    if length is not None:
+        assert len(value) <= length, f"Value is longer than {length} bytes"
+        value = value.rjust(length, b"\x00")
+    return value
+
+
+def _quantity(value: int) -> str:
+    """
+    Formats an int as a JSON-RPC quantity (no leading zeros, "0x0" for zero)
+    """
+    assert value >= 0, "Quantities must be non-negative"
+    return hex(value)
+
+
+def _word(value: Union[bytes, str, int]) -> str:
+    """
+    Formats a storage key or value as a 32-byte hex string
+    """
+    return "0x" + _to_bytes(value, 32).hex()
+
+
+def encode_state_overrides(overrides: Dict[str, Dict]) -> Dict[str, Dict]:
+    """
+    Builds the state override object accepted as the third parameter of eth_call
+    (geth, reth, erigon and anvil share this format).
+
+    `overrides` maps an address to a dict with any of these keys:
+        - "balance": int (wei)
+        - "nonce": int
+        - "code": bytes or hex str
+        - "state": {slot: value}, replacing the account's entire storage
+        - "stateDiff": {slot: value}, replacing only the given slots
+
+    Slots and values may be ints, bytes or hex strings. They are left-padded to 32
+    bytes, while balance and nonce are formatted as minimal hex quantities
+    """
+
+    encoded = {}
+
+    for address, account in overrides.items():
+        assert not (
+            "state" in account and "stateDiff" in account
+        ), f"{address}: 'state' and 'stateDiff' are mutually exclusive"
+
+        encoded_account = {}
+        for key, value in account.items():
+            if key in ("balance", "nonce"):
+                encoded_account[key] = _quantity(value)
+            elif key == "code":
+                encoded_account[key] = "0x" + _to_bytes(value).hex()
+            elif key in ("state", "stateDiff"):
+                encoded_account[key] = {
+                    _word(slot): _word(slot_value) for slot, slot_value in value.items()
+                }
+            else:
+                raise ValueError(f"{address}: unknown override field '{key}'")
+
+        encoded[to_checksum_address(address)] = encoded_account
+
+    return encoded
+
+
+def erc20_balance_slot(
+    holder: str,
+    balance_slot: int,
+    vyper: bool = False,
+) -> int:
+    """
+    Computes the storage key holding `holder`'s balance for a token whose `balanceOf`
+    mapping lives at `balance_slot`.
+    Solidity hashes key ++ slot, while Vyper (< 0.3.8) hashes slot ++ key
+    """
+    if not vyper:
+        return mapping_slot(holder, balance_slot)
+    return int.from_bytes(
+        keccak(_to_bytes(balance_slot, 32) + _to_bytes(holder, 32)), "big"
+    )
+
+
+def erc20_balance_override(
+    token: str,
+    holder: str,
+    amount: int,
+    balance_slot: Optional[int] = None,
+    vyper: bool = False,
+) -> Dict[str, Dict]:
+    """
+    Returns a state override setting `holder`'s balance of `token` to `amount`.
+    If the balance slot is not given, it is looked up in KNOWN_BALANCE_SLOTS.
+    Overrides for several tokens can be merged with dict.update before encoding
+    """
+
+    if balance_slot is None:
+        token = to_checksum_address(token)
+        assert (
+            token in KNOWN_BALANCE_SLOTS
+        ), f"Balance slot for {token} is unknown and must be provided"
+        balance_slot = KNOWN_BALANCE_SLOTS[token]
+
+    return {
+        token: {
+            "stateDiff": {erc20_balance_slot(holder, balance_slot, vyper): amount}
+        }
+    }
@@ -0,0 +1 @@
+from .security import *
\ No newline at end of file
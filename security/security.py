@@ -0,0 +1,313 @@
+from dataclasses import dataclass, field
+from typing import Dict, List, Optional, Set, Tuple, Union
+from ..abi import function_selector
+
+__all__ = [
+    "extract_selectors",
+    "RiskFinding",
+    "TokenRiskReport",
+    "score_token_bytecode",
+]
+
+# opcodes used by the analysis
+_STOP = 0x00
+_EQ = 0x14
+_ISZERO = 0x15
+_XOR = 0x18
+_ORIGIN = 0x32
+_TIMESTAMP = 0x42
+_NUMBER = 0x43
+_JUMP = 0x56
+_JUMPI = 0x57
+_JUMPDEST = 0x5B
+_PUSH1 = 0x60
+_PUSH2 = 0x61
+_PUSH32 = 0x7F
+_DUP1 = 0x80
+_DUP16 = 0x8F
+_CALL = 0xF1
+_CALLCODE = 0xF2
+_RETURN = 0xF3
+_DELEGATECALL = 0xF4
+_REVERT = 0xFD
+_INVALID = 0xFE
+_SELFDESTRUCT = 0xFF
+
+_JUMPS = (_JUMP, _JUMPI)
+_TERMINATING = {_STOP, _JUMP, _RETURN, _REVERT, _INVALID, _SELFDESTRUCT}
+
+_Instruction = Tuple[int, int, Optional[int]]
+
+
+def _to_bytes(code: Union[bytes, str]) -> bytes:
+    if isinstance(code, str):
+        return bytes.fromhex(code[2:] if code.startswith("0x") else code)
+    return bytes(code)
+
+
+def _instructions(code: bytes) -> List[_Instruction]:
+    """
+    Disassembles bytecode into (pc, opcode, pushed value) tuples, skipping over push
+    data and the trailing metadata. The pushed value is None for other opcodes
+    """
+    code = _strip_metadata(code)
+    instructions = []
+    pc = 0
+    while pc < len(code):
+        opcode = code[pc]
+        if _PUSH1 <= opcode <= _PUSH32:
+            size = opcode - _PUSH1 + 1
+            # push data running past the end of the code is zero-padded
+            data = code[pc + 1 : pc + 1 + size].ljust(size, b"\x00")
+            instructions.append((pc, opcode, int.from_bytes(data, "big")))
+            pc += 1 + size
+        else:
+            instructions.append((pc, opcode, None))
+            pc += 1
+    return instructions
+
+
+def _strip_metadata(code: bytes) -> bytes:
+    """
+    Removes the CBOR metadata appended by solc and vyper, whose length is given by the
+    last two bytes, so that it is not disassembled as code
+    """
+    if len(code) < 2:
+        return code
+    length = int.from_bytes(code[-2:], "big")
+    if length + 2 <= len(code) and code[-2 - length] & 0xE0 == 0xA0:
+        # the metadata is a CBOR map (major type 5)
+        return code[: -2 - length]
+    return code
+
+
+def _dispatcher_entries(instructions: List[_Instruction]) -> Dict[bytes, int]:
+    """
+    Maps the selectors of a contract's dispatcher to their entry points. Three
+    comparison sequences are recognized, with the selector pushed in up to 4 bytes
+    (compilers shorten selectors with leading zero bytes):
+
+    - `PUSH4 selector [DUPn] EQ PUSH dest JUMPI` (solc), jumping to the function
+    - `PUSH4 selector [DUPn] XOR PUSH next JUMPI` (vyper), falling through to it
+    - `PUSH4 selector [DUPn] EQ ISZERO PUSH next JUMPI`, falling through to it
+
+    Comparisons against the split points of solc's binary search (GT, LT) are not
+    matched
+    """
+    entries = {}
+    count = len(instructions)
+    for i, (_, opcode, value) in enumerate(instructions):
+        if not _PUSH1 <= opcode <= _PUSH1 + 3:
+            continue
+        j = i + 1
+        if j < count and _DUP1 <= instructions[j][1] <= _DUP16:
+            j += 1
+        if j + 2 >= count:
+            continue
+        compare = instructions[j][1]
+        if compare == _EQ and instructions[j + 1][1] == _ISZERO:
+            compare, j = _XOR, j + 1
+            if j + 2 >= count:
+                continue
+        if compare not in (_EQ, _XOR) or instructions[j + 1][2] is None:
+            continue
+        if instructions[j + 2][1] != _JUMPI:
+            continue
+        if compare == _EQ:
+            entry = instructions[j + 1][2]
+        elif j + 3 < count:
+            # the jump skips to the next comparison when the selector differs
+            entry = instructions[j + 3][0]
+        else:
+            continue
+        entries.setdefault(value.to_bytes(4, "big"), entry)
+    return entries
+
+
+def extract_selectors(code: Union[bytes, str]) -> List[bytes]:
+    """
+    Returns the sorted 4-byte selectors that a contract's dispatcher responds to,
+    found by matching the selector comparisons emitted by solc and vyper. Functions
+    reached only through a fallback (e.g. on proxies, or in hand-written contracts
+    with custom dispatch) are not found
+    """
+    return sorted(_dispatcher_entries(_instructions(_to_bytes(code))))
+
+
+def _reachable_opcodes(instructions: List[_Instruction], start: int) -> Set[int]:
+    """
+    Returns the opcodes reachable from the jump destination `start`. Jump targets are
+    over-approximated: besides pushes directly followed by a jump, any PUSH2 value
+    that is a JUMPDEST is treated as a successor, which covers the return addresses
+    pushed for internal function calls
+    """
+    index_of = {pc: i for i, (pc, _, _) in enumerate(instructions)}
+    jumpdests = {pc for pc, opcode, _ in instructions if opcode == _JUMPDEST}
+    opcodes = set()
+    visited = set()
+    pending = [start]
+    while pending:
+        pc = pending.pop()
+        if pc in visited or pc not in index_of:
+            continue
+        visited.add(pc)
+        for i in range(index_of[pc], len(instructions)):
+            block_pc, opcode, value = instructions[i]
+            if opcode == _JUMPDEST and block_pc != pc:
+                # falls through into the next block
+                pending.append(block_pc)
+                break
+            opcodes.add(opcode)
+            if value is not None and value in jumpdests:
+                followed_by_jump = (
+                    i + 1 < len(instructions) and instructions[i + 1][1] in _JUMPS
+                )
+                if opcode == _PUSH2 or followed_by_jump:
+                    pending.append(value)
+            if opcode in _TERMINATING:
+                break
+    return opcodes
+
+
+def _selectors(*signatures: str) -> Dict[bytes, str]:
+    return {function_selector(signature): signature for signature in signatures}
+
+
+# functions letting the owner stop selected holders from selling
+_BLACKLIST_SELECTORS = _selectors(
+    "blacklist(address)",
+    "addToBlacklist(address)",
+    "blacklistAddress(address,bool)",
+    "setBlacklist(address,bool)",
+    "isBlacklisted(address)",
+    "addBot(address)",
+    "addBots(address[])",
+    "setBots(address[])",
+    "setBot(address,bool)",
+    "blockBots(address[])",
+)
+# functions changing the transfer tax after launch
+_FEE_SWITCH_SELECTORS = _selectors(
+    "setFee(uint256)",
+    "setFees(uint256,uint256)",
+    "setTaxFeePercent(uint256)",
+    "setBuyFee(uint256)",
+    "setSellFee(uint256)",
+    "setTaxes(uint256,uint256)",
+    "updateFees(uint256,uint256)",
+    "setSwapAndLiquifyEnabled(bool)",
+)
+_LIMIT_SELECTORS = _selectors(
+    "setMaxTxAmount(uint256)",
+    "setMaxTxPercent(uint256)",
+    "setMaxWalletSize(uint256)",
+    "setMaxWallet(uint256)",
+    "removeLimits()",
+)
+_TRADING_SWITCH_SELECTORS = _selectors(
+    "openTrading()",
+    "enableTrading()",
+    "setTrading(bool)",
+    "setTradingEnabled(bool)",
+    "tradingStatus(bool)",
+)
+_MINT_SELECTORS = _selectors("mint(address,uint256)", "mint(uint256)")
+_PAUSE_SELECTORS = _selectors("pause()")
+
+_TRANSFER_SELECTORS = _selectors(
+    "transfer(address,uint256)", "transferFrom(address,address,uint256)"
+)
+
+# (finding, weight, selectors, description)
+_SELECTOR_CHECKS = (
+    ("blacklist", 40, _BLACKLIST_SELECTORS, "owner can block addresses"),
+    ("fee_switch", 25, _FEE_SWITCH_SELECTORS, "owner can change transfer taxes"),
+    ("trading_switch", 20, _TRADING_SWITCH_SELECTORS, "owner controls trading"),
+    ("owner_mint", 20, _MINT_SELECTORS, "supply can be minted"),
+    ("pausable", 20, _PAUSE_SELECTORS, "transfers can be paused"),
+    ("limits", 10, _LIMIT_SELECTORS, "owner sets transaction limits"),
+)
+# (finding, weight, opcodes, description) for opcodes anywhere in the contract
+_OPCODE_CHECKS = (
+    ("selfdestruct", 30, {_SELFDESTRUCT}, "contract can self-destruct"),
+    ("delegatecall", 15, {_DELEGATECALL, _CALLCODE}, "code can be replaced"),
+)
+# (finding, weight, opcodes, description) for opcodes reachable from transfers
+_TRANSFER_PATH_CHECKS = (
+    ("transfer_tx_origin", 30, {_ORIGIN}, "transfers check tx.origin"),
+    (
+        "transfer_block_dependent",
+        15,
+        {_TIMESTAMP, _NUMBER},
+        "transfers depend on the block time or number",
+    ),
+    ("transfer_external_call", 10, {_CALL}, "transfers make external calls"),
+)
+
+
+@dataclass
+class RiskFinding:
+    name: str
+    weight: int
+    description: str
+    # the matched function signatures, for selector-based findings
+    signatures: List[str] = field(default_factory=list)
+
+
+@dataclass
+class TokenRiskReport:
+    # sum of the finding weights, capped at 100
+    score: int
+    findings: List[RiskFinding]
+    # dispatcher selectors found in the bytecode
+    selectors: List[bytes]
+
+    def has(self, name: str) -> bool:
+        return any(finding.name == name for finding in self.findings)
+
+
+def score_token_bytecode(code: Union[bytes, str]) -> TokenRiskReport:
+    """
+    Scores the runtime bytecode of a token for patterns common in honeypots and
+    owner-controlled tokens: privileged functions (blacklists, fee and trading
+    switches, minting, pausing), dangerous opcodes, and transfer paths that depend on
+    tx.origin, the block time or external calls.
+
+    This is a heuristic pre-filter for discovered tokens, not a verdict: many
+    legitimate tokens are pausable or upgradeable, and a honeypot can hide its logic
+    behind unfamiliar function names. Proxies are scored on their own bytecode, not on
+    the implementation's
+    """
+    instructions = _instructions(_to_bytes(code))
+    entries = _dispatcher_entries(instructions)
+    opcodes = {opcode for _, opcode, _ in instructions}
+    findings = []
+
+    for name, weight, selectors, description in _SELECTOR_CHECKS:
+        matched = [selectors[selector] for selector in entries if selector in selectors]
+        if matched:
+            findings.append(RiskFinding(name, weight, description, sorted(matched)))
+
+    for name, weight, risky_opcodes, description in _OPCODE_CHECKS:
+        if opcodes & risky_opcodes:
+            findings.append(RiskFinding(name, weight, description))
+
+    transfer_entries = [
+        entry for selector, entry in entries.items() if selector in _TRANSFER_SELECTORS
+    ]
+    if not transfer_entries:
+        findings.append(
+            RiskFinding("no_transfer", 10, "no transfer function in the dispatcher")
+        )
+    transfer_opcodes = set()
+    for entry in transfer_entries:
+        transfer_opcodes |= _reachable_opcodes(instructions, entry)
+    for name, weight, risky_opcodes, description in _TRANSFER_PATH_CHECKS:
+        if transfer_opcodes & risky_opcodes:
+            findings.append(RiskFinding(name, weight, description))
+
+    return TokenRiskReport(
+        score=min(100, sum(finding.weight for finding in findings)),
+        findings=findings,
+        selectors=sorted(entries),
+    )
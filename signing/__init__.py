@@ -0,0 +1 @@
+from .signing import *
\ No newline at end of file
@@ -0,0 +1,315 @@
+import multiprocessing
+from dataclasses import dataclass
+from typing import TYPE_CHECKING, Iterable, List, Optional, Tuple, Union
+from eth_utils import keccak, to_checksum_address
+from ..address_utils import check_address_array
+
+if TYPE_CHECKING:
+    import coincurve
+    import numpy
+
+__all__ = [
+    "SECP256K1_N",
+    "public_key_from_private_key",
+    "address_from_public_key",
+    "address_from_private_key",
+    "addresses_from_public_keys",
+    "addresses_from_private_keys",
+    "Signature",
+    "sign_hash",
+    "sign_digest",
+    "signature_to_bytes",
+    "recover_address",
+    "normalize_v",
+    "signature_from_bytes",
+    "to_compact_signature",
+    "from_compact_signature",
+    "verify_signatures",
+]
+
+# order of the secp256k1 group
+SECP256K1_N = 0xFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141
+
+
+def _to_bytes(value: Union[bytes, str]) -> bytes:
+    if isinstance(value, str):
+        return bytes.fromhex(value[2:] if value.startswith("0x") else value)
+    return bytes(value)
+
+
+def _coincurve():
+    try:
+        import coincurve
+    except ImportError as e:
+        raise ImportError(
+            "Signing and key derivation require the optional coincurve dependency "
+            "(pip install coincurve)"
+        ) from e
+    return coincurve
+
+
+def _load_public_key(public_key: bytes) -> "coincurve.PublicKey":
+    """
+    Parses a 33-byte compressed, 64-byte raw, or 65-byte uncompressed (0x04 prefixed)
+    public key. libsecp256k1 rejects points that are not on the curve
+    """
+    if len(public_key) == 64:
+        public_key = b"\x04" + public_key
+    if len(public_key) == 65:
+        # libsecp256k1 also parses the 0x06/0x07 hybrid encodings, which are not
+        # valid Ethereum public keys
+        if public_key[0] != 4:
+            raise ValueError("Uncompressed public keys must begin with 0x04")
+    elif len(public_key) == 33:
+        if public_key[0] not in (2, 3):
+            raise ValueError("Compressed public keys must begin with 0x02 or 0x03")
+    else:
+        raise ValueError(f"Invalid public key length: {len(public_key)} bytes")
+    return _coincurve().PublicKey(public_key)
+
+
+def _address(public_key: "coincurve.PublicKey") -> str:
+    return to_checksum_address(keccak(public_key.format(compressed=False)[1:])[-20:])
+
+
+def _load_private_key(
+    private_key: Union[bytes, str, int]
+) -> "coincurve.PrivateKey":
+    if isinstance(private_key, int):
+        if not 0 < private_key < SECP256K1_N:
+            raise ValueError("Private key is outside the valid secp256k1 range")
+        return _coincurve().PrivateKey(private_key.to_bytes(32, "big"))
+
+    key_bytes = _to_bytes(private_key)
+    if len(key_bytes) != 32:
+        raise ValueError(f"Expected a 32-byte private key, found {len(key_bytes)}")
+    if not 0 < int.from_bytes(key_bytes, "big") < SECP256K1_N:
+        raise ValueError("Private key is outside the valid secp256k1 range")
+    return _coincurve().PrivateKey(key_bytes)
+
+
+def public_key_from_private_key(private_key: Union[bytes, str, int]) -> bytes:
+    """
+    Returns the 64-byte raw (x || y) public key for a private key
+    """
+    return _load_private_key(private_key).public_key.format(compressed=False)[1:]
+
+
+def address_from_public_key(public_key: Union[bytes, str]) -> str:
+    """
+    Derives the checksummed address for a public key.
+    Compressed (33 byte), raw (64 byte) and uncompressed (65 byte) keys are accepted
+    """
+    return _address(_load_public_key(_to_bytes(public_key)))
+
+
+def address_from_private_key(private_key: Union[bytes, str, int]) -> str:
+    """
+    Derives the checksummed address for a private key
+    """
+    return _address(_load_private_key(private_key).public_key)
+
+
+def addresses_from_public_keys(
+    public_keys: List[Union[bytes, str]], out: Optional["numpy.ndarray"] = None
+) -> Union[List[str], "numpy.ndarray"]:
+    """
+    Derives the addresses for a batch of public keys, as a list of str or written
+    into a numpy S42 array `out` (see to_checksum_addresses)
+    """
+    if out is None:
+        return [address_from_public_key(public_key) for public_key in public_keys]
+    check_address_array(out)
+    for i, public_key in enumerate(public_keys):
+        out[i] = address_from_public_key(public_key).encode()
+    return out
+
+
+def addresses_from_private_keys(
+    private_keys: List[Union[bytes, str, int]], out: Optional["numpy.ndarray"] = None
+) -> Union[List[str], "numpy.ndarray"]:
+    """
+    Derives the addresses for a batch of private keys, as a list of str or written
+    into a numpy S42 array `out` (see to_checksum_addresses)
+    """
+    if out is None:
+        return [address_from_private_key(private_key) for private_key in private_keys]
+    check_address_array(out)
+    for i, private_key in enumerate(private_keys):
+        out[i] = address_from_private_key(private_key).encode()
+    return out
+
+
+@dataclass(frozen=True)
+class Signature:
+    r: int
+    s: int
+    # parity of the y coordinate of the nonce point, the recovery id of ecrecover
+    y_parity: int
+
+    @property
+    def v(self) -> int:
+        return 27 + self.y_parity
+
+    def to_bytes(self) -> bytes:
+        """
+        Returns the 65-byte r || s || v form, with v in {27, 28}
+        """
+        return signature_to_bytes(self.v, self.r, self.s)
+
+
+def sign_hash(
+    digest: Union[bytes, str], private_key: Union[bytes, str, int]
+) -> Signature:
+    """
+    Signs a 32-byte message hash through libsecp256k1, whose nonces are the
+    deterministic RFC 6979 ones, so the same input always yields the same signature.
+    s is normalized to the lower half of the curve order (EIP-2), with y_parity
+    adjusted to match
+    """
+    key = _load_private_key(private_key)
+    digest = _to_bytes(digest)
+    if len(digest) != 32:
+        raise ValueError(f"Expected a 32-byte digest, found {len(digest)}")
+
+    # r || s || recovery id
+    signature = key.sign_recoverable(digest, hasher=None)
+    r = int.from_bytes(signature[:32], "big")
+    s = int.from_bytes(signature[32:64], "big")
+    recovery_id = signature[64]
+    # libsecp256k1 already signs with a low s, this keeps the guarantee explicit
+    if s > SECP256K1_N // 2:
+        s = SECP256K1_N - s
+        recovery_id ^= 1
+    # ecrecover cannot represent the (negligibly likely) case of a nonce point with
+    # x >= N, and libsecp256k1 offers no way to move to the next RFC 6979 nonce
+    if recovery_id > 1:
+        raise ValueError("The signature's recovery id cannot be represented as v")
+    return Signature(r, s, recovery_id)
+
+
+def sign_digest(
+    private_key: Union[bytes, str, int], digest: Union[bytes, str]
+) -> Tuple[int, int, int]:
+    """
+    Signs a 32-byte message digest, returning (v, r, s) with v in {27, 28}. See
+    sign_hash, which this wraps
+    """
+    signature = sign_hash(digest, private_key)
+    return signature.v, signature.r, signature.s
+
+
+def signature_to_bytes(v: int, r: int, s: int) -> bytes:
+    """
+    Packs a signature into the 65-byte r || s || v form
+    """
+    return r.to_bytes(32, "big") + s.to_bytes(32, "big") + bytes([v])
+
+
+def recover_address(digest: Union[bytes, str], v: int, r: int, s: int) -> str:
+    """
+    Recovers the checksummed signer address of a digest, equivalent to ecrecover
+    """
+    digest = _to_bytes(digest)
+    if len(digest) != 32:
+        raise ValueError(f"Expected a 32-byte digest, found {len(digest)}")
+    if v not in (0, 1, 27, 28):
+        raise ValueError(f"Invalid recovery id {v}")
+    if not (0 < r < SECP256K1_N and 0 < s < SECP256K1_N):
+        raise ValueError("Signature is out of range")
+
+    recovery_id = v - 27 if v >= 27 else v
+    # raises ValueError if no public key recovers from the signature
+    public_key = _coincurve().PublicKey.from_signature_and_message(
+        r.to_bytes(32, "big") + s.to_bytes(32, "big") + bytes([recovery_id]),
+        digest,
+        hasher=None,
+    )
+    return _address(public_key)
+
+
+def normalize_v(v: int) -> int:
+    """
+    Converts a recovery id given as 0/1, 27/28 or EIP-155 (chain_id * 2 + 35/36) to
+    27/28
+    """
+    if v in (0, 1):
+        return 27 + v
+    if v in (27, 28):
+        return v
+    if v >= 35:
+        return 27 + (v - 35) % 2
+    raise ValueError(f"Invalid recovery id {v}")
+
+
+def signature_from_bytes(signature: Union[bytes, str]) -> Tuple[int, int, int]:
+    """
+    Unpacks a 65-byte r || s || v signature or a 64-byte EIP-2098 compact signature
+    into (v, r, s), with v normalized to 27/28
+    """
+    signature = _to_bytes(signature)
+    if len(signature) == 65:
+        return (
+            normalize_v(signature[64]),
+            int.from_bytes(signature[:32], "big"),
+            int.from_bytes(signature[32:64], "big"),
+        )
+    if len(signature) == 64:
+        y_parity_and_s = int.from_bytes(signature[32:], "big")
+        return (
+            27 + (y_parity_and_s >> 255),
+            int.from_bytes(signature[:32], "big"),
+            y_parity_and_s & (2 ** 255 - 1),
+        )
+    raise ValueError(f"Invalid signature length: {len(signature)} bytes")
+
+
+def to_compact_signature(signature: Union[bytes, str]) -> bytes:
+    """
+    Converts a 65-byte signature to the 64-byte EIP-2098 form r || (yParity << 255 | s).
+    Only low-s signatures can be represented
+    """
+    v, r, s = signature_from_bytes(signature)
+    if s > SECP256K1_N // 2:
+        raise ValueError("EIP-2098 requires a low-s signature")
+    return r.to_bytes(32, "big") + (((v - 27) << 255) | s).to_bytes(32, "big")
+
+
+def from_compact_signature(compact: Union[bytes, str]) -> bytes:
+    """
+    Expands a 64-byte EIP-2098 signature to the 65-byte r || s || v form
+    """
+    compact = _to_bytes(compact)
+    if len(compact) != 64:
+        raise ValueError(f"Expected a 64-byte signature, found {len(compact)}")
+    return signature_to_bytes(*signature_from_bytes(compact))
+
+
+def _verify_signature(item: Tuple[Union[bytes, str], Union[bytes, str], str]) -> bool:
+    digest, signature, expected_signer = item
+    try:
+        signer = recover_address(digest, *signature_from_bytes(signature))
+    except ValueError:
+        return False
+    return signer.lower() == expected_signer.lower()
+
+
+def verify_signatures(
+    items: Iterable[Tuple[Union[bytes, str], Union[bytes, str], str]],
+    workers: int = 1,
+    chunk_size: int = 64,
+) -> List[bool]:
+    """
+    Verifies many (digest, signature, expected signer) tuples, returning whether each
+    signature was made by its expected signer. Signatures may be 65-byte or EIP-2098
+    compact, and malformed signatures verify as False instead of raising.
+
+    Recovery is CPU-bound, so with `workers` > 1 the items are verified in that many
+    processes, `chunk_size` at a time
+    """
+    assert workers >= 1, "At least one worker is required"
+    items = list(items)
+    if workers == 1 or len(items) <= chunk_size:
+        return [_verify_signature(item) for item in items]
+    with multiprocessing.Pool(processes=workers) as pool:
+        return pool.map(_verify_signature, items, chunksize=chunk_size)
@@ -0,0 +1,2 @@
+from .snapshot import *
+from .snapshot_file import *
\ No newline at end of file
@@ -0,0 +1,87 @@
+from dataclasses import dataclass, field
+from typing import Any, Dict, Iterable, List, Tuple
+
+__all__ = ["PoolDiff", "SnapshotDiff", "take_snapshot", "diff_pools", "diff_snapshots"]
+
+# bookkeeping attributes that do not describe pool state
+_IGNORED_FIELDS = ("block_number", "ticks")
+
+
+@dataclass
+class PoolDiff:
+    pool: str
+    # field name -> (old value, new value)
+    changed_fields: Dict[str, Tuple[Any, Any]] = field(default_factory=dict)
+    ticks_added: List[int] = field(default_factory=list)
+    ticks_removed: List[int] = field(default_factory=list)
+    # ticks initialized in both snapshots, but with different liquidity
+    ticks_changed: List[int] = field(default_factory=list)
+
+    def __bool__(self) -> bool:
+        return bool(
+            self.changed_fields
+            or self.ticks_added
+            or self.ticks_removed
+            or self.ticks_changed
+        )
+
+
+@dataclass
+class SnapshotDiff:
+    pools_added: List[str] = field(default_factory=list)
+    pools_removed: List[str] = field(default_factory=list)
+    pools_changed: Dict[str, PoolDiff] = field(default_factory=dict)
+
+    def __bool__(self) -> bool:
+        return bool(self.pools_added or self.pools_removed or self.pools_changed)
+
+
+def take_snapshot(pools: Iterable) -> Dict[str, Any]:
+    """
+    Returns independent copies of the given pool states, keyed by pool address
+    """
+    return {pool.address: pool.copy() for pool in pools}
+
+
+def diff_pools(old: Any, new: Any) -> PoolDiff:
+    """
+    Compares two states of the same pool: every state attribute (reserves, price,
+    tick, liquidity, fee...) and, for V3 pools, the set of initialized ticks
+    """
+    diff = PoolDiff(pool=new.address)
+
+    old_fields, new_fields = vars(old), vars(new)
+    for name in sorted(set(old_fields) | set(new_fields)):
+        if name.startswith("_") or name in _IGNORED_FIELDS:
+            continue
+        old_value, new_value = old_fields.get(name), new_fields.get(name)
+        if old_value != new_value:
+            diff.changed_fields[name] = (old_value, new_value)
+
+    old_ticks = getattr(old, "ticks", {})
+    new_ticks = getattr(new, "ticks", {})
+    diff.ticks_added = sorted(set(new_ticks) - set(old_ticks))
+    diff.ticks_removed = sorted(set(old_ticks) - set(new_ticks))
+    diff.ticks_changed = sorted(
+        tick
+        for tick in set(old_ticks) & set(new_ticks)
+        if old_ticks[tick] != new_ticks[tick]
+    )
+    return diff
+
+
+def diff_snapshots(old: Dict[str, Any], new: Dict[str, Any]) -> SnapshotDiff:
+    """
+    Compares two snapshots (address -> pool state, e.g. from take_snapshot), listing
+    the pools added and removed and the per-pool changes of those present in both.
+    Pools without changes are omitted
+    """
+    diff = SnapshotDiff(
+        pools_added=sorted(set(new) - set(old)),
+        pools_removed=sorted(set(old) - set(new)),
+    )
+    for address in sorted(set(old) & set(new)):
+        pool_diff = diff_pools(old[address], new[address])
+        if pool_diff:
+            diff.pools_changed[address] = pool_diff
+    return diff
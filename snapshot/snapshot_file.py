@@ -0,0 +1,106 @@
+import json
+import pickle
+from fractions import Fraction
+from typing import Any, Dict, Optional
+from ..pool_state import (
+    CurveCryptoPoolState,
+    CurveStableswapPoolState,
+    SolidlyPoolState,
+    V2PoolState,
+    V3PoolState,
+    V3TickInfo,
+)
+from ..uniswap_v3 import ExchangeVariant
+
+__all__ = ["BINARY_SNAPSHOT_MAGIC", "save_snapshot", "load_snapshot"]
+
+_POOL_TYPES = {
+    cls.__name__: cls
+    for cls in (
+        CurveCryptoPoolState,
+        CurveStableswapPoolState,
+        SolidlyPoolState,
+        V2PoolState,
+        V3PoolState,
+    )
+}
+
+# prefix of the binary format, followed by a pickle of the snapshot dict
+BINARY_SNAPSHOT_MAGIC = b"DBSNAP1\n"
+
+
+def _encode_value(value):
+    if isinstance(value, Fraction):
+        return {"fraction": [value.numerator, value.denominator]}
+    if isinstance(value, ExchangeVariant):
+        return {"variant": value.name}
+    return value
+
+
+def _decode_value(value):
+    if isinstance(value, dict) and "fraction" in value:
+        return Fraction(*value["fraction"])
+    if isinstance(value, dict) and "variant" in value:
+        return ExchangeVariant[value["variant"]]
+    return value
+
+
+def _encode_pool(pool) -> Dict:
+    state = {}
+    for name, value in vars(pool).items():
+        if name == "ticks":
+            value = {
+                str(tick): [info.liquidity_gross, info.liquidity_net]
+                for tick, info in sorted(value.items())
+            }
+        state[name] = _encode_value(value)
+    return {"type": type(pool).__name__, "state": state}
+
+
+def _decode_pool(encoded: Dict):
+    cls = _POOL_TYPES.get(encoded["type"])
+    assert cls is not None, f"Unknown pool state type {encoded['type']}"
+    pool = cls.__new__(cls)
+    for name, value in encoded["state"].items():
+        if name == "ticks":
+            value = {int(tick): V3TickInfo(*info) for tick, info in value.items()}
+        setattr(pool, name, _decode_value(value))
+    return pool
+
+
+def save_snapshot(
+    snapshot: Dict[str, Any], path: str, binary: Optional[bool] = None
+) -> None:
+    """
+    Writes a snapshot (address -> pool state, e.g. from take_snapshot) to a file.
+    The JSON format is human-readable and stable across versions, the binary format
+    (a pickle) is faster to load. By default, the binary format is used unless the
+    path ends with ".json"
+    """
+    if binary is None:
+        binary = not path.endswith(".json")
+    if binary:
+        with open(path, "wb") as file:
+            file.write(BINARY_SNAPSHOT_MAGIC)
+            pickle.dump(snapshot, file, protocol=pickle.HIGHEST_PROTOCOL)
+        return
+    with open(path, "w") as file:
+        json.dump(
+            {address: _encode_pool(pool) for address, pool in snapshot.items()},
+            file,
+            indent=1,
+        )
+
+
+def load_snapshot(path: str) -> Dict[str, Any]:
+    """
+    Reads a snapshot written by save_snapshot, detecting its format. Only load binary
+    snapshots from trusted sources, since unpickling can execute arbitrary code
+    """
+    with open(path, "rb") as file:
+        data = file.read()
+    if data.startswith(BINARY_SNAPSHOT_MAGIC):
+        return pickle.loads(data[len(BINARY_SNAPSHOT_MAGIC) :])
+    return {
+        address: _decode_pool(encoded) for address, encoded in json.loads(data).items()
+    }
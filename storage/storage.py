@@ -0,0 +1,174 @@
+from typing import Dict, List, Union
+from eth_utils import keccak
+from ..constants import MAX_UINT128, MAX_UINT160
+
+__all__ = [
+    "V3_SLOT0_SLOT",
+    "V3_FEE_GROWTH_GLOBAL0_SLOT",
+    "V3_FEE_GROWTH_GLOBAL1_SLOT",
+    "V3_PROTOCOL_FEES_SLOT",
+    "V3_LIQUIDITY_SLOT",
+    "V3_TICKS_SLOT",
+    "V3_TICK_BITMAP_SLOT",
+    "V3_POSITIONS_SLOT",
+    "V3_OBSERVATIONS_SLOT",
+    "mapping_slot",
+    "slot_to_hex",
+    "v3_slots",
+    "v3_tick_slots",
+    "v3_tick_bitmap_slot",
+    "v3_position_key",
+    "v3_position_slots",
+    "v3_observation_slot",
+    "decode_v3_slot0",
+    "decode_v3_tick_liquidity",
+]
+
+# Storage layout of UniswapV3Pool (v1.0.0 core contracts)
+V3_SLOT0_SLOT = 0
+V3_FEE_GROWTH_GLOBAL0_SLOT = 1
+V3_FEE_GROWTH_GLOBAL1_SLOT = 2
+V3_PROTOCOL_FEES_SLOT = 3
+V3_LIQUIDITY_SLOT = 4
+V3_TICKS_SLOT = 5
+V3_TICK_BITMAP_SLOT = 6
+V3_POSITIONS_SLOT = 7
+V3_OBSERVATIONS_SLOT = 8
+
+
+def _key_bytes(key: Union[int, str, bytes]) -> bytes:
+    """
+    Encodes a mapping key as its 32-byte ABI word. Negative ints are sign-extended,
+    hex strings and bytes shorter than 32 bytes (e.g. addresses) are left-padded
+    """
+    if isinstance(key, int):
+        return (key % 2 ** 256).to_bytes(32, "big")
+    if isinstance(key, str):
+        key = bytes.fromhex(key[2:] if key.startswith("0x") else key)
+    assert len(key) <= 32, "Mapping keys must be at most 32 bytes"
+    return bytes(key).rjust(32, b"\x00")
+
+
+def mapping_slot(key: Union[int, str, bytes], base_slot: int) -> int:
+    """
+    Returns the storage slot of `mapping[key]` for a Solidity mapping declared at
+    `base_slot`: keccak256(key ++ base_slot)
+    """
+    return int.from_bytes(
+        keccak(_key_bytes(key) + base_slot.to_bytes(32, "big")), "big"
+    )
+
+
+def slot_to_hex(slot: int) -> str:
+    """
+    Formats a slot as the 32-byte hex position expected by eth_getStorageAt
+    """
+    return "0x" + slot.to_bytes(32, "big").hex()
+
+
+def v3_slots() -> Dict[str, int]:
+    """
+    Returns the fixed (non-mapping) storage slots of a Uniswap V3 pool
+    """
+    return {
+        "slot0": V3_SLOT0_SLOT,
+        "feeGrowthGlobal0X128": V3_FEE_GROWTH_GLOBAL0_SLOT,
+        "feeGrowthGlobal1X128": V3_FEE_GROWTH_GLOBAL1_SLOT,
+        "protocolFees": V3_PROTOCOL_FEES_SLOT,
+        "liquidity": V3_LIQUIDITY_SLOT,
+    }
+
+
+def v3_tick_slots(tick: int) -> List[int]:
+    """
+    Returns the 4 consecutive slots of `ticks[tick]` (Tick.Info):
+        0: liquidityGross (uint128) | liquidityNet (int128)
+        1: feeGrowthOutside0X128
+        2: feeGrowthOutside1X128
+        3: tickCumulativeOutside | secondsPerLiquidityOutsideX128 | secondsOutside |
+           initialized
+    """
+    base = mapping_slot(tick, V3_TICKS_SLOT)
+    return [base + offset for offset in range(4)]
+
+
+def v3_tick_bitmap_slot(word_position: int) -> int:
+    """
+    Returns the slot of `tickBitmap[word_position]`, where word_position is
+    (tick // tick_spacing) >> 8
+    """
+    return mapping_slot(word_position, V3_TICK_BITMAP_SLOT)
+
+
+def v3_position_key(owner: str, tick_lower: int, tick_upper: int) -> bytes:
+    """
+    Returns keccak256(abi.encodePacked(owner, tickLower, tickUpper)),
+    the key used by the pool's `positions` mapping
+    """
+    owner = bytes.fromhex(owner[2:] if owner.startswith("0x") else owner)
+    return keccak(
+        owner
+        + (tick_lower % 2 ** 24).to_bytes(3, "big")
+        + (tick_upper % 2 ** 24).to_bytes(3, "big")
+    )
+
+
+def v3_position_slots(owner: str, tick_lower: int, tick_upper: int) -> List[int]:
+    """
+    Returns the 4 consecutive slots of a position (Position.Info):
+        0: liquidity
+        1: feeGrowthInside0LastX128
+        2: feeGrowthInside1LastX128
+        3: tokensOwed0 (uint128) | tokensOwed1 (uint128)
+    """
+    base = mapping_slot(
+        v3_position_key(owner, tick_lower, tick_upper), V3_POSITIONS_SLOT
+    )
+    return [base + offset for offset in range(4)]
+
+
+def v3_observation_slot(index: int) -> int:
+    """
+    Returns the slot of `observations[index]` (fixed-size array, one slot each)
+    """
+    assert 0 <= index < 65535, "Observation index out of range"
+    return V3_OBSERVATIONS_SLOT + index
+
+
+def _to_int(value: Union[int, str, bytes]) -> int:
+    if isinstance(value, int):
+        return value
+    if isinstance(value, str):
+        return int(value, 16) if value not in ("0x", "") else 0
+    return int.from_bytes(value, "big")
+
+
+def _signed(value: int, bits: int) -> int:
+    return value - 2 ** bits if value >= 2 ** (bits - 1) else value
+
+
+def decode_v3_slot0(value: Union[int, str, bytes]) -> Dict[str, int]:
+    """
+    Unpacks the slot0 storage word
+    """
+    value = _to_int(value)
+    return {
+        "sqrtPriceX96": value & MAX_UINT160,
+        "tick": _signed((value >> 160) & (2 ** 24 - 1), 24),
+        "observationIndex": (value >> 184) & 0xFFFF,
+        "observationCardinality": (value >> 200) & 0xFFFF,
+        "observationCardinalityNext": (value >> 216) & 0xFFFF,
+        "feeProtocol": (value >> 232) & 0xFF,
+        "unlocked": bool((value >> 240) & 0xFF),
+    }
+
+
+def decode_v3_tick_liquidity(value: Union[int, str, bytes]) -> Dict[str, int]:
+    """
+    Unpacks the first Tick.Info word into liquidityGross and liquidityNet
+    """
+    value = _to_int(value)
+    return {
+        "liquidityGross": value & MAX_UINT128,
+        "liquidityNet": _signed(value >> 128, 128),
+    }
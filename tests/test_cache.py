@@ -0,0 +1,33 @@
+import pytest
+from degenbot import cached_function, configure_caches
+
+
+def test_type_error_raised_by_a_cached_function_is_not_retried():
+    calls = []
+
+    @cached_function
+    def fails(value):
+        calls.append(value)
+        raise TypeError("bad value")
+
+    configure_caches(True)
+    try:
+        with pytest.raises(TypeError):
+            fails(1)
+    finally:
+        configure_caches(False)
+    assert calls == [1]
+
+
+def test_unhashable_arguments_bypass_the_cache():
+    @cached_function
+    def length(value):
+        return len(value)
+
+    configure_caches(True)
+    try:
+        assert length(bytearray(3)) == 3
+        assert length(b"ab") == length(b"ab") == 2
+        assert length.cache_info().hits == 1
+    finally:
+        configure_caches(False)
@@ -0,0 +1,54 @@
+import pytest
+from degenbot import (
+    ema_price,
+    geometric_mean,
+    halfpow,
+    newton_d,
+    sqrt_int,
+    wad_exp,
+)
+
+
+# reference values of expWad in the solady and snekmate test suites
+@pytest.mark.parametrize(
+    "x, result",
+    [
+        (-3 * 10 ** 18, 49787068367863942),
+        (-(10 ** 18), 367879441171442321),
+        (0, 10 ** 18),
+        (5 * 10 ** 17, 1648721270700128146),
+        (10 ** 18, 2718281828459045235),
+    ],
+)
+def test_wad_exp(x, result):
+    assert wad_exp(x) == result
+
+
+def test_ema_price():
+    # no time elapsed keeps the stored average, after ma_time the last price has a
+    # weight of 1 - 1/e
+    assert ema_price(2 * 10 ** 18, 100, 10 ** 18, 600, 100) == 10 ** 18
+    alpha = 367879441171442321
+    assert ema_price(2 * 10 ** 18, 100, 10 ** 18, 600, 700) == (
+        2 * 10 ** 18 * (10 ** 18 - alpha) + 10 ** 18 * alpha
+    ) // 10 ** 18
+
+
+def test_ema_price_half_life():
+    # after ma_time * ln(2) the average is halfway to the last price, up to the
+    # rounding of the elapsed time to whole seconds
+    price = ema_price(2 * 10 ** 18, 0, 10 ** 18, 866, 600)
+    assert abs(price - 15 * 10 ** 17) < 10 ** 15
+
+
+def test_crypto_invariant_of_a_balanced_pool():
+    # with equal balances in the internal price units, D is their sum
+    xp = [30_000_000 * 10 ** 18] * 3
+    assert newton_d(1707629, 11809167828997, xp) == sum(xp)
+    assert geometric_mean(xp) == xp[0]
+
+
+def test_crypto_helpers():
+    assert halfpow(0) == 10 ** 18
+    assert halfpow(10 ** 18) == 5 * 10 ** 17
+    assert sqrt_int(4 * 10 ** 18) == 2 * 10 ** 18
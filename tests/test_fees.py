@@ -0,0 +1,52 @@
+import pytest
+from degenbot import (
+    BLOB_BASE_FEE_UPDATE_FRACTION,
+    OP_FJORD_DIVISOR,
+    OP_FJORD_MIN_TRANSACTION_SIZE,
+    blob_base_fee,
+    fake_exponential,
+    flz_compress_len,
+    next_base_fee,
+    op_l1_fee,
+)
+
+
+def test_next_base_fee_rejects_gas_limit_below_elasticity():
+    with pytest.raises(ValueError):
+        next_base_fee(10 ** 9, 1, 1)
+
+
+# EIP-1559: a full block raises the base fee by 1/8, an empty one lowers it by 1/8
+@pytest.mark.parametrize(
+    "gas_used, base_fee",
+    [
+        (30_000_000, 1_125_000_000),
+        (15_000_000, 1_000_000_000),
+        (0, 875_000_000),
+    ],
+)
+def test_next_base_fee(gas_used, base_fee):
+    assert next_base_fee(10 ** 9, gas_used, 30_000_000) == base_fee
+
+
+def test_blob_base_fee():
+    # EIP-4844: the minimum fee without excess blob gas, and floor(e) once the excess
+    # reaches the update fraction
+    fraction = BLOB_BASE_FEE_UPDATE_FRACTION
+    assert blob_base_fee(0) == 1
+    assert fake_exponential(1, fraction, fraction) == 2
+
+
+def test_flz_compress_len_of_literals():
+    # FastLZ stores incompressible data as runs of up to 32 literals, each after a
+    # one-byte header
+    assert flz_compress_len(b"") == 0
+    assert flz_compress_len(b"\x01") == 2
+    assert flz_compress_len(bytes(range(256))) == 256 + 8
+
+
+def test_op_l1_fee_of_a_small_transaction():
+    # the Fjord estimate never goes below the minimum transaction size
+    fee_scaled = 1368 * 10 ** 9 * 16 + 810949 * 1
+    expected = OP_FJORD_MIN_TRANSACTION_SIZE * 10 ** 6 * fee_scaled // OP_FJORD_DIVISOR
+    assert op_l1_fee(b"\x02" + b"\x00" * 10, 10 ** 9, 1, 1368, 810949) == expected
@@ -0,0 +1,45 @@
+import pytest
+from eth_utils import keccak
+from degenbot import (
+    EMPTY_CODE_HASH,
+    EMPTY_TRIE_ROOT,
+    ProofVerificationError,
+    rlp_encode,
+    verify_mpt_proof,
+)
+
+
+def test_empty_roots_match_the_yellow_paper():
+    assert EMPTY_TRIE_ROOT.hex() == (
+        "56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+    )
+    assert EMPTY_CODE_HASH.hex() == (
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    )
+
+
+def test_empty_proof_shows_absence_from_the_empty_trie():
+    assert verify_mpt_proof(EMPTY_TRIE_ROOT, b"\x01" * 20, []) is None
+
+
+def _single_leaf_trie(key: bytes, value: bytes):
+    # a trie holding one key is a single leaf node whose hex-prefix path is the
+    # whole keccak256(key), 64 nibbles with the even-length leaf flag 0x20
+    leaf = rlp_encode([b"\x20" + keccak(key), value])
+    return keccak(leaf), leaf
+
+
+def test_single_leaf_proof():
+    value = rlp_encode(10 ** 18)
+    root, leaf = _single_leaf_trie(b"\x01" * 20, value)
+
+    assert verify_mpt_proof(root, b"\x01" * 20, [leaf]) == value
+    assert verify_mpt_proof(root, b"\x02" * 20, [leaf]) is None
+
+
+def test_tampered_proof_is_rejected():
+    root, _ = _single_leaf_trie(b"\x01" * 20, rlp_encode(10 ** 18))
+    _, forged = _single_leaf_trie(b"\x01" * 20, rlp_encode(2 * 10 ** 18))
+
+    with pytest.raises(ProofVerificationError):
+        verify_mpt_proof(root, b"\x01" * 20, [forged])
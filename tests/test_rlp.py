@@ -0,0 +1,31 @@
+import pytest
+from degenbot import rlp_decode, rlp_encode
+
+# examples of the RLP specification (ethereum.org, "Recursive-length prefix")
+SPEC_VECTORS = [
+    (b"dog", "83646f67"),
+    ([b"cat", b"dog"], "c88363617483646f67"),
+    (b"", "80"),
+    ([], "c0"),
+    (0, "80"),
+    (b"\x00", "00"),
+    (15, "0f"),
+    (1024, "820400"),
+    ([[], [[]], [[], [[]]]], "c7c0c1c0c3c0c1c0"),
+    (
+        b"Lorem ipsum dolor sit amet, consectetur adipisicing elit",
+        "b838" + b"Lorem ipsum dolor sit amet, consectetur adipisicing elit".hex(),
+    ),
+]
+
+
+@pytest.mark.parametrize("item, encoded", SPEC_VECTORS)
+def test_rlp_encode_spec_vectors(item, encoded):
+    assert rlp_encode(item).hex() == encoded
+
+
+@pytest.mark.parametrize("item, encoded", SPEC_VECTORS)
+def test_rlp_decode_spec_vectors(item, encoded):
+    if isinstance(item, int):
+        item = item.to_bytes((item.bit_length() + 7) // 8, "big")
+    assert rlp_decode(bytes.fromhex(encoded)) == item
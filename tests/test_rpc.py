@@ -0,0 +1,49 @@
+import json
+import pytest
+from degenbot import HttpProvider, RpcResponseError, parse_call_batch_response
+
+
+def _provider(answer) -> HttpProvider:
+    provider = HttpProvider("http://localhost:8545", max_retries=0)
+
+    def _post_to(session, url, body):
+        requests = json.loads(body)
+        return json.dumps(answer(requests)).encode()
+
+    provider._post_to = _post_to
+    return provider
+
+
+def test_batch_request_matches_out_of_order_responses_by_id():
+    provider = _provider(
+        lambda requests: [
+            {"jsonrpc": "2.0", "id": request["id"], "result": request["method"]}
+            for request in reversed(requests)
+        ]
+    )
+
+    calls = [("eth_chainId", []), ("eth_blockNumber", []), ("eth_gasPrice", [])]
+    assert provider.batch_request(calls) == [method for method, _ in calls]
+
+
+def test_batch_request_raises_on_a_missing_response():
+    provider = _provider(
+        lambda requests: [
+            {"jsonrpc": "2.0", "id": request["id"], "result": "0x1"}
+            for request in requests[1:]
+        ]
+    )
+
+    with pytest.raises(RpcResponseError):
+        provider.batch_request([("eth_chainId", []), ("eth_blockNumber", [])])
+
+
+def test_parse_call_batch_response_matches_by_id():
+    response = [
+        {"jsonrpc": "2.0", "id": 2, "result": "0x02"},
+        {"jsonrpc": "2.0", "id": 1, "result": "0x01"},
+    ]
+
+    assert parse_call_batch_response(response, ids=[1, 2]) == [b"\x01", b"\x02"]
+    with pytest.raises(RpcResponseError):
+        parse_call_batch_response(response[:1], ids=[1, 2])
@@ -0,0 +1,42 @@
+from degenbot import (
+    ExchangeVariant,
+    V3PoolState,
+    V3TickInfo,
+    load_snapshot,
+    save_snapshot,
+)
+
+
+def _v3_pool() -> V3PoolState:
+    return V3PoolState(
+        "0x" + "33" * 20,
+        fee=500,
+        tick_spacing=10,
+        sqrt_price_x96=2 ** 96,
+        tick=0,
+        liquidity=10 ** 18,
+        ticks={
+            -10: V3TickInfo(10 ** 18, 10 ** 18),
+            10: V3TickInfo(10 ** 18, -(10 ** 18)),
+        },
+        variant=ExchangeVariant.PANCAKESWAP_V3,
+    )
+
+
+def _assert_round_trip(path: str) -> None:
+    pool = _v3_pool()
+    save_snapshot({pool.address: pool}, path)
+    loaded = load_snapshot(path)[pool.address]
+
+    assert vars(loaded).keys() == vars(pool).keys()
+    assert loaded.variant is ExchangeVariant.PANCAKESWAP_V3
+    assert loaded.ticks == pool.ticks
+    assert loaded.swap(10 ** 15, True) == _v3_pool().swap(10 ** 15, True)
+
+
+def test_v3_snapshot_json_round_trip(tmp_path):
+    _assert_round_trip(str(tmp_path / "snapshot.json"))
+
+
+def test_v3_snapshot_binary_round_trip(tmp_path):
+    _assert_round_trip(str(tmp_path / "snapshot.bin"))
@@ -0,0 +1,67 @@
+import pytest
+from degenbot import (
+    BYTES_PER_BLOB,
+    CELLS_PER_EXT_BLOB,
+    BlobSidecar,
+    BlobTransaction,
+    Signature,
+    kzg_to_versioned_hash,
+    rlp_decode,
+    rlp_encode,
+)
+
+COMMITMENT = b"\xc0" + b"\x00" * 47
+
+
+def _transaction(cell_proofs) -> BlobTransaction:
+    sidecar = BlobSidecar(
+        blobs=[b"\x00" * BYTES_PER_BLOB],
+        commitments=[COMMITMENT],
+        proofs=[b"\x01" * 48],
+        versioned_hashes=[kzg_to_versioned_hash(COMMITMENT)],
+        cell_proofs=cell_proofs,
+    )
+    return BlobTransaction(
+        chain_id=1,
+        nonce=0,
+        max_priority_fee_per_gas=10 ** 9,
+        max_fee_per_gas=10 ** 10,
+        gas=21_000,
+        to="0x" + "22" * 20,
+        max_fee_per_blob_gas=10 ** 9,
+        sidecar=sidecar,
+    )
+
+
+SIGNATURE = Signature(r=1, s=2, y_parity=0)
+
+
+def test_encode_network_version_0_wrapper():
+    transaction = _transaction([])
+    encoded = transaction.encode_network(SIGNATURE)
+
+    assert encoded[0] == 0x03
+    signed_fields, blobs, commitments, proofs = rlp_decode(encoded[1:])
+    assert b"\x03" + rlp_encode(signed_fields) == transaction.encode(SIGNATURE)
+    assert blobs == transaction.sidecar.blobs
+    assert commitments == [COMMITMENT]
+    assert proofs == [b"\x01" * 48]
+
+
+def test_encode_network_version_1_wrapper():
+    cell_proofs = [bytes([i]) * 48 for i in range(CELLS_PER_EXT_BLOB)]
+    transaction = _transaction(cell_proofs)
+    encoded = transaction.encode_network(SIGNATURE, wrapper_version=1)
+
+    assert encoded[0] == 0x03
+    signed_fields, version, blobs, commitments, proofs = rlp_decode(encoded[1:])
+    assert b"\x03" + rlp_encode(signed_fields) == transaction.encode(SIGNATURE)
+    assert version == b"\x01"
+    assert blobs == transaction.sidecar.blobs
+    assert commitments == [COMMITMENT]
+    assert proofs == cell_proofs
+
+
+def test_encode_network_version_1_requires_cell_proofs():
+    with pytest.raises(ValueError):
+        _transaction([]).encode_network(SIGNATURE, wrapper_version=1)
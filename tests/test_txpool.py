@@ -0,0 +1,45 @@
+from degenbot import NonceTracker, Transaction
+
+SENDER = "0x" + "11" * 20
+
+
+def _transaction(nonce: int, gas_price: int = 10 ** 9) -> Transaction:
+    return Transaction(
+        hash=bytes([nonce, gas_price // 10 ** 9]) * 16,
+        sender=SENDER,
+        to=None,
+        nonce=nonce,
+        value=0,
+        gas=21_000,
+        input=b"",
+        gas_price=gas_price,
+    )
+
+
+def test_confirm_excludes_the_mined_transaction():
+    tracker = NonceTracker()
+    stale, mined, later = _transaction(0), _transaction(1), _transaction(2)
+    for transaction in (stale, mined, later):
+        tracker.ingest(transaction)
+
+    assert tracker.confirm(SENDER, 1, mined.hash) == [stale]
+    assert len(tracker) == 1
+
+
+def test_confirm_without_a_hash_treats_the_pending_nonce_as_mined():
+    tracker = NonceTracker()
+    mined = _transaction(0)
+    tracker.ingest(mined)
+
+    assert tracker.confirm(SENDER, 0) == []
+    assert len(tracker) == 0
+
+
+def test_confirm_block_returns_the_transactions_replaced_by_a_mined_nonce():
+    tracker = NonceTracker()
+    pending = _transaction(0)
+    tracker.ingest(pending)
+    competing = _transaction(0, gas_price=2 * 10 ** 9)
+
+    assert tracker.confirm_block([competing]) == [pending]
+    assert len(tracker) == 0
@@ -0,0 +1,167 @@
+import math
+import pytest
+from degenbot import (
+    MAX_SQRT_RATIO,
+    MAX_TICK,
+    MIN_SQRT_RATIO,
+    MIN_TICK,
+    compute_swap_step,
+    get_amount0_delta,
+    get_amount1_delta,
+    get_next_sqrt_price_from_input,
+    get_next_sqrt_price_from_output,
+    get_sqrt_ratio_at_tick,
+    get_tick_at_sqrt_ratio,
+)
+
+# Expected values are those of the Uniswap v3-core test suite (TickMath.spec.ts,
+# SqrtPriceMath.spec.ts and SwapMath.spec.ts)
+
+
+def encode_price_sqrt(reserve1: int, reserve0: int) -> int:
+    # floor(sqrt(reserve1 / reserve0) * 2^96), as encodePriceSqrt in v3-core's tests
+    return math.isqrt((reserve1 << 192) // reserve0)
+
+
+PRICE_1 = encode_price_sqrt(1, 1)
+
+
+@pytest.mark.parametrize(
+    "tick, sqrt_ratio",
+    [
+        (MIN_TICK, 4295128739),
+        (MIN_TICK + 1, 4295343490),
+        (0, 2 ** 96),
+        (MAX_TICK - 1, 1461373636630004318706518188784493106690254656249),
+        (MAX_TICK, 1461446703485210103287273052203988822378723970342),
+    ],
+)
+def test_get_sqrt_ratio_at_tick(tick, sqrt_ratio):
+    assert get_sqrt_ratio_at_tick(tick) == sqrt_ratio
+
+
+def test_get_tick_at_sqrt_ratio_bounds():
+    assert get_tick_at_sqrt_ratio(MIN_SQRT_RATIO) == MIN_TICK
+    assert get_tick_at_sqrt_ratio(MAX_SQRT_RATIO - 1) == MAX_TICK - 1
+
+
+@pytest.mark.parametrize(
+    "liquidity, amount_in, zero_for_one, sqrt_price",
+    [
+        (10 ** 18, 10 ** 17, False, 87150978765690771352898345369),
+        (10 ** 18, 10 ** 17, True, 72025602285694852357767227579),
+        (10 * 10 ** 18, 2 ** 100, True, 624999999995069620),
+        (1, (2 ** 256 - 1) // 2, True, 1),
+    ],
+)
+def test_get_next_sqrt_price_from_input(liquidity, amount_in, zero_for_one, sqrt_price):
+    assert (
+        get_next_sqrt_price_from_input(PRICE_1, liquidity, amount_in, zero_for_one)
+        == sqrt_price
+    )
+
+
+@pytest.mark.parametrize(
+    "zero_for_one, sqrt_price",
+    [
+        (False, 88031291682515930659493278152),
+        (True, 71305346262837903834189555302),
+    ],
+)
+def test_get_next_sqrt_price_from_output(zero_for_one, sqrt_price):
+    assert (
+        get_next_sqrt_price_from_output(PRICE_1, 10 ** 18, 10 ** 17, zero_for_one)
+        == sqrt_price
+    )
+
+
+def test_amount_deltas_for_a_price_of_1_to_1_21():
+    price_1_21 = encode_price_sqrt(121, 100)
+
+    assert get_amount0_delta(PRICE_1, price_1_21, 10 ** 18, True) == 90909090909090910
+    assert get_amount0_delta(PRICE_1, price_1_21, 10 ** 18, False) == 90909090909090909
+    assert get_amount1_delta(PRICE_1, price_1_21, 10 ** 18, True) == 10 ** 17
+    assert get_amount1_delta(PRICE_1, price_1_21, 10 ** 18, False) == 10 ** 17 - 1
+
+
+_SQRT_P = 20282409603651670423947251286016
+
+# (current, target, liquidity, amount_remaining, fee) ->
+# (next sqrt price, amount in, amount out, fee amount)
+SWAP_STEP_VECTORS = [
+    # exact input capped at the price target, one for zero
+    (
+        (PRICE_1, encode_price_sqrt(101, 100), 2 * 10 ** 18, 10 ** 18, 600),
+        (
+            encode_price_sqrt(101, 100),
+            9975124224178055,
+            9925619580021728,
+            5988667735148,
+        ),
+    ),
+    # exact output capped at the price target, one for zero
+    (
+        (PRICE_1, encode_price_sqrt(101, 100), 2 * 10 ** 18, -(10 ** 18), 600),
+        (
+            encode_price_sqrt(101, 100),
+            9975124224178055,
+            9925619580021728,
+            5988667735148,
+        ),
+    ),
+    # exact input fully spent, one for zero
+    (
+        (PRICE_1, encode_price_sqrt(1000, 100), 2 * 10 ** 18, 10 ** 18, 600),
+        (
+            118818475322642227089037862318,
+            999400000000000000,
+            666399946655997866,
+            600000000000000,
+        ),
+    ),
+    # exact output fully received, one for zero
+    (
+        (PRICE_1, encode_price_sqrt(10000, 100), 2 * 10 ** 18, -(10 ** 18), 600),
+        (
+            158456325028528675187087900672,
+            2 * 10 ** 18,
+            10 ** 18,
+            1200720432259356,
+        ),
+    ),
+    # amount out is capped at the desired amount out
+    (
+        (
+            417332158212080721273783715441582,
+            1452870262520218020823638996,
+            159344665391607089467575320103,
+            -1,
+            1,
+        ),
+        (417332158212080721273783715441581, 1, 1, 1),
+    ),
+    # target price of 1 uses a partial input amount
+    (
+        (2, 1, 1, 3915081100057732413702495386755767, 1),
+        (1, 39614081257132168796771975168, 0, 39614120871253040049813),
+    ),
+    # entire input amount taken as fee
+    (
+        (2413, 79887613182836312, 1985041575832132834610021537970, 10, 1872),
+        (2413, 0, 0, 10),
+    ),
+    # intermediate insufficient liquidity, exact output
+    (
+        (_SQRT_P, _SQRT_P * 11 // 10, 1024, -4, 3000),
+        (_SQRT_P * 11 // 10, 26215, 0, 79),
+    ),
+    (
+        (_SQRT_P, _SQRT_P * 9 // 10, 1024, -263000, 3000),
+        (_SQRT_P * 9 // 10, 1, 26214, 1),
+    ),
+]
+
+
+@pytest.mark.parametrize("arguments, result", SWAP_STEP_VECTORS)
+def test_compute_swap_step(arguments, result):
+    assert compute_swap_step(*arguments) == result
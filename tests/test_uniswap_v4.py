@@ -0,0 +1,59 @@
+import math
+import pytest
+from degenbot import compute_v4_swap_step
+
+# Expected values are those of the Uniswap v4-core SwapMath tests, which port the
+# v3-core vectors with the sign of amountRemaining flipped (negative for an exact
+# input)
+
+
+def encode_price_sqrt(reserve1: int, reserve0: int) -> int:
+    return math.isqrt((reserve1 << 192) // reserve0)
+
+
+PRICE_1 = encode_price_sqrt(1, 1)
+
+
+@pytest.mark.parametrize(
+    "arguments, result",
+    [
+        # exact input capped at the price target, one for zero
+        (
+            (PRICE_1, encode_price_sqrt(101, 100), 2 * 10 ** 18, -(10 ** 18), 600),
+            (
+                encode_price_sqrt(101, 100),
+                9975124224178055,
+                9925619580021728,
+                5988667735148,
+            ),
+        ),
+        # exact input fully spent, one for zero
+        (
+            (PRICE_1, encode_price_sqrt(1000, 100), 2 * 10 ** 18, -(10 ** 18), 600),
+            (
+                118818475322642227089037862318,
+                999400000000000000,
+                666399946655997866,
+                600000000000000,
+            ),
+        ),
+        # exact output fully received, one for zero
+        (
+            (PRICE_1, encode_price_sqrt(10000, 100), 2 * 10 ** 18, 10 ** 18, 600),
+            (
+                158456325028528675187087900672,
+                2 * 10 ** 18,
+                10 ** 18,
+                1200720432259356,
+            ),
+        ),
+        # unlike V3, the input less fee is taken as the input when the target is
+        # not reached, so the fee is no longer the entire input
+        (
+            (2413, 79887613182836312, 1985041575832132834610021537970, -10, 1872),
+            (2413, 9, 0, 1),
+        ),
+    ],
+)
+def test_compute_v4_swap_step(arguments, result):
+    assert compute_v4_swap_step(*arguments) == result
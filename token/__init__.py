@@ -1 +1,3 @@
 from .token import *
+
+from .registry import *
\ No newline at end of file
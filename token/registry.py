@@ -0,0 +1,178 @@
+import json
+import threading
+from dataclasses import dataclass
+from enum import IntFlag
+from typing import Dict, Iterable, Iterator, List, Optional, Union
+from eth_utils import to_checksum_address
+from ..erc20.metadata import TokenMetadata
+from ..filters.filters import _address_key
+from ..units import format_units, parse_units
+
+__all__ = ["TokenFlags", "TokenInfo", "TokenRegistry"]
+
+
+class TokenFlags(IntFlag):
+    """
+    Known token behaviors that quoting and routing must account for
+    """
+
+    NONE = 0
+    FEE_ON_TRANSFER = 1
+    REBASING = 2
+    BLACKLISTABLE = 4
+    PAUSABLE = 8
+    # flagged as unsafe to trade, e.g. by score_token_bytecode
+    UNSAFE = 16
+
+
+@dataclass(frozen=True)
+class TokenInfo:
+    address: str
+    decimals: int
+    symbol: Optional[str] = None
+    flags: TokenFlags = TokenFlags.NONE
+
+
+class TokenRegistry:
+    """
+    Cache of token decimals, symbols and flags, keyed by address in any hex form, so
+    amounts can be formatted and flagged tokens skipped without passing metadata
+    around. Safe to share between threads
+    """
+
+    def __init__(self, tokens: Iterable[TokenInfo] = ()) -> None:
+        self._tokens: Dict[bytes, TokenInfo] = {}
+        self._lock = threading.Lock()
+        self.update(tokens)
+
+    def __repr__(self) -> str:
+        return f"TokenRegistry({len(self._tokens)} tokens)"
+
+    def __len__(self) -> int:
+        return len(self._tokens)
+
+    def __iter__(self) -> Iterator[TokenInfo]:
+        return iter(list(self._tokens.values()))
+
+    def __contains__(self, address: Union[bytes, str]) -> bool:
+        return _address_key(address) in self._tokens
+
+    def __getitem__(self, address: Union[bytes, str]) -> TokenInfo:
+        return self._tokens[_address_key(address)]
+
+    def get(
+        self, address: Union[bytes, str], default: Optional[TokenInfo] = None
+    ) -> Optional[TokenInfo]:
+        return self._tokens.get(_address_key(address), default)
+
+    def add(
+        self,
+        address: Union[bytes, str],
+        decimals: int,
+        symbol: Optional[str] = None,
+        flags: TokenFlags = TokenFlags.NONE,
+    ) -> TokenInfo:
+        """
+        Registers a token, replacing any previous entry for the address
+        """
+        assert 0 <= decimals <= 255, f"Invalid decimals {decimals}"
+        key = _address_key(address)
+        info = TokenInfo(to_checksum_address(key), decimals, symbol, TokenFlags(flags))
+        with self._lock:
+            self._tokens[key] = info
+        return info
+
+    def update(self, tokens: Iterable[TokenInfo]) -> None:
+        for token in tokens:
+            self.add(token.address, token.decimals, token.symbol, token.flags)
+
+    def set_flags(self, address: Union[bytes, str], flags: TokenFlags) -> TokenInfo:
+        """
+        Adds flags to a registered token
+        """
+        key = _address_key(address)
+        with self._lock:
+            info = self._tokens[key]
+            info = TokenInfo(
+                info.address, info.decimals, info.symbol, info.flags | flags
+            )
+            self._tokens[key] = info
+        return info
+
+    def decimals(self, address: Union[bytes, str]) -> int:
+        return self[address].decimals
+
+    def symbol(self, address: Union[bytes, str]) -> Optional[str]:
+        return self[address].symbol
+
+    def flags(self, address: Union[bytes, str]) -> TokenFlags:
+        info = self.get(address)
+        return TokenFlags.NONE if info is None else info.flags
+
+    def format(
+        self,
+        address: Union[bytes, str],
+        amount: int,
+        max_decimals: Optional[int] = None,
+    ) -> str:
+        """
+        Formats a raw amount of the token in whole units, see format_units
+        """
+        return format_units(amount, self.decimals(address), max_decimals)
+
+    def parse(self, address: Union[bytes, str], value: str) -> int:
+        """
+        Parses an amount of the token in whole units, see parse_units
+        """
+        return parse_units(value, self.decimals(address))
+
+    def import_metadata(self, metadata: Iterable[TokenMetadata]) -> int:
+        """
+        Registers tokens from decode_metadata_results, skipping those whose decimals
+        could not be read. Returns the number of tokens added
+        """
+        added = 0
+        for token in metadata:
+            if token.decimals is None or token.decimals > 255:
+                continue
+            self.add(token.address, token.decimals, token.symbol)
+            added += 1
+        return added
+
+    def export(self) -> List[Dict]:
+        """
+        Returns every token as a JSON-serializable record, sorted by address
+        """
+        return [
+            {
+                "address": info.address,
+                "decimals": info.decimals,
+                "symbol": info.symbol,
+                "flags": int(info.flags),
+            }
+            for _, info in sorted(self._tokens.items())
+        ]
+
+    def import_records(self, records: Iterable[Dict]) -> None:
+        """
+        Registers tokens from records in the format of export
+        """
+        for record in records:
+            self.add(
+                record["address"],
+                record["decimals"],
+                record.get("symbol"),
+                TokenFlags(record.get("flags", 0)),
+            )
+
+    def save(self, path: str) -> None:
+        with open(path, "w") as file:
+            json.dump(self.export(), file, indent=1)
+
+    @classmethod
+    def load(cls, path: str) -> "TokenRegistry":
+        with open(path) as file:
+            records = json.load(file)
+        registry = cls()
+        registry.import_records(records)
+        return registry
@@ -0,0 +1 @@
+from .tx_builder import *
\ No newline at end of file
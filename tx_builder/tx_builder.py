@@ -0,0 +1,202 @@
+import hashlib
+from dataclasses import dataclass, field
+from typing import Any, Dict, List, Sequence, Union
+from eth_utils import keccak
+from ..rlp import rlp_encode
+from ..signing import Signature, sign_hash
+
+__all__ = [
+    "BLOB_TX_TYPE",
+    "FIELD_ELEMENTS_PER_BLOB",
+    "BYTES_PER_BLOB",
+    "VERSIONED_HASH_VERSION_KZG",
+    "CELLS_PER_EXT_BLOB",
+    "load_trusted_setup",
+    "kzg_to_versioned_hash",
+    "BlobSidecar",
+    "build_blob_sidecar",
+    "BlobTransaction",
+]
+
+BLOB_TX_TYPE = 0x03
+FIELD_ELEMENTS_PER_BLOB = 4096
+BYTES_PER_BLOB = 32 * FIELD_ELEMENTS_PER_BLOB
+VERSIONED_HASH_VERSION_KZG = 0x01
+# cells of an erasure-coded blob, each with its own KZG proof (EIP-7594)
+CELLS_PER_EXT_BLOB = 128
+
+
+def _ckzg():
+    try:
+        import ckzg
+    except ImportError as e:
+        raise ImportError(
+            "Blob sidecars require the optional ckzg dependency (pip install ckzg)"
+        ) from e
+    return ckzg
+
+
+def _to_bytes(value: Union[bytes, str]) -> bytes:
+    if isinstance(value, str):
+        return bytes.fromhex(value[2:] if value.startswith("0x") else value)
+    return bytes(value)
+
+
+def load_trusted_setup(path: str, precompute: int = 0) -> Any:
+    """
+    Loads a KZG trusted setup file (e.g. trusted_setup.txt from the c-kzg repository)
+    for build_blob_sidecar
+    """
+    ckzg = _ckzg()
+    try:
+        return ckzg.load_trusted_setup(path, precompute)
+    except TypeError:
+        # ckzg < 2.0 takes no precompute argument
+        return ckzg.load_trusted_setup(path)
+
+
+def kzg_to_versioned_hash(commitment: bytes) -> bytes:
+    """
+    Returns the versioned hash of a KZG commitment, as listed in a blob transaction
+    """
+    return bytes([VERSIONED_HASH_VERSION_KZG]) + hashlib.sha256(commitment).digest()[1:]
+
+
+@dataclass
+class BlobSidecar:
+    blobs: List[bytes]
+    commitments: List[bytes]
+    proofs: List[bytes]
+    versioned_hashes: List[bytes]
+    # CELLS_PER_EXT_BLOB proofs per blob, blob by blob, for the version 1 wrapper
+    cell_proofs: List[bytes] = field(default_factory=list)
+
+
+def build_blob_sidecar(
+    blobs: Sequence[bytes], trusted_setup: Any, cell_proofs: bool = False
+) -> BlobSidecar:
+    """
+    Computes the KZG commitment, proof and versioned hash of each blob, using a setup
+    from load_trusted_setup. Blobs shorter than BYTES_PER_BLOB are zero-padded.
+    Every 32-byte field element must be below the BLS12-381 modulus, which ckzg
+    checks. With `cell_proofs`, the cell proofs required by the version 1 network
+    wrapper are computed too, which is far slower
+    """
+    ckzg = _ckzg()
+    sidecar = BlobSidecar([], [], [], [])
+    for blob in blobs:
+        blob = bytes(blob)
+        assert (
+            len(blob) <= BYTES_PER_BLOB
+        ), f"Blob of {len(blob)} bytes exceeds {BYTES_PER_BLOB} bytes"
+        blob = blob.ljust(BYTES_PER_BLOB, b"\x00")
+        commitment = bytes(ckzg.blob_to_kzg_commitment(blob, trusted_setup))
+        sidecar.blobs.append(blob)
+        sidecar.commitments.append(commitment)
+        sidecar.proofs.append(
+            bytes(ckzg.compute_blob_kzg_proof(blob, commitment, trusted_setup))
+        )
+        sidecar.versioned_hashes.append(kzg_to_versioned_hash(commitment))
+        if cell_proofs:
+            _, proofs = ckzg.compute_cells_and_kzg_proofs(blob, trusted_setup)
+            sidecar.cell_proofs.extend(bytes(proof) for proof in proofs)
+    return sidecar
+
+
+def _access_list(access_list: List[Dict]) -> List:
+    return [
+        [
+            _to_bytes(entry["address"]),
+            [_to_bytes(key).rjust(32, b"\x00") for key in entry["storageKeys"]],
+        ]
+        for entry in access_list
+    ]
+
+
+@dataclass
+class BlobTransaction:
+    """
+    An EIP-4844 (type 3) transaction. Blob transactions cannot create contracts, so
+    `to` is required. The blob versioned hashes are taken from the sidecar. The access
+    list uses the JSON-RPC format: [{"address": ..., "storageKeys": [...]}]
+    """
+
+    chain_id: int
+    nonce: int
+    max_priority_fee_per_gas: int
+    max_fee_per_gas: int
+    gas: int
+    to: str
+    max_fee_per_blob_gas: int
+    sidecar: BlobSidecar
+    value: int = 0
+    data: bytes = b""
+    access_list: List[Dict] = field(default_factory=list)
+
+    def _fields(self) -> List:
+        assert self.sidecar.versioned_hashes, "A blob transaction needs a blob"
+        return [
+            self.chain_id,
+            self.nonce,
+            self.max_priority_fee_per_gas,
+            self.max_fee_per_gas,
+            self.gas,
+            _to_bytes(self.to),
+            self.value,
+            _to_bytes(self.data),
+            _access_list(self.access_list),
+            self.max_fee_per_blob_gas,
+            self.sidecar.versioned_hashes,
+        ]
+
+    def signing_hash(self) -> bytes:
+        return keccak(bytes([BLOB_TX_TYPE]) + rlp_encode(self._fields()))
+
+    def sign(self, private_key: Union[bytes, str, int]) -> Signature:
+        return sign_hash(self.signing_hash(), private_key)
+
+    def encode(self, signature: Signature) -> bytes:
+        """
+        Returns the canonical signed encoding, without blobs, as included in blocks.
+        Its keccak is the transaction hash
+        """
+        return bytes([BLOB_TX_TYPE]) + rlp_encode(
+            self._fields() + [signature.y_parity, signature.r, signature.s]
+        )
+
+    def encode_network(self, signature: Signature, wrapper_version: int = 0) -> bytes:
+        """
+        Returns the network encoding with the blob sidecar attached, as accepted by
+        eth_sendRawTransaction. Version 0 is the EIP-4844 wrapper, 0x03 || rlp([signed
+        fields, blobs, commitments, proofs]). Version 1, required from Osaka (EIP-7594),
+        carries cell proofs instead: 0x03 || rlp([signed fields, 1, blobs,
+        commitments, cell proofs]), which needs a sidecar built with `cell_proofs`
+        """
+        signed_fields = self._fields() + [signature.y_parity, signature.r, signature.s]
+        if wrapper_version == 0:
+            wrapper = [
+                signed_fields,
+                self.sidecar.blobs,
+                self.sidecar.commitments,
+                self.sidecar.proofs,
+            ]
+        elif wrapper_version == 1:
+            expected = CELLS_PER_EXT_BLOB * len(self.sidecar.blobs)
+            if len(self.sidecar.cell_proofs) != expected:
+                raise ValueError(
+                    f"Expected {expected} cell proofs, found "
+                    f"{len(self.sidecar.cell_proofs)}"
+                )
+            wrapper = [
+                signed_fields,
+                wrapper_version,
+                self.sidecar.blobs,
+                self.sidecar.commitments,
+                self.sidecar.cell_proofs,
+            ]
+        else:
+            raise ValueError(f"Unsupported blob wrapper version {wrapper_version}")
+        return bytes([BLOB_TX_TYPE]) + rlp_encode(wrapper)
+
+    def transaction_hash(self, signature: Signature) -> bytes:
+        return keccak(self.encode(signature))
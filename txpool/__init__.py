@@ -0,0 +1,2 @@
+from .txpool import *
+from .projection import *
\ No newline at end of file
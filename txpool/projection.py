@@ -0,0 +1,163 @@
+from dataclasses import dataclass
+from typing import Any, Callable, Dict, List, Mapping, Optional, Sequence, Tuple
+from ..arbitrage.backrun import PendingSwap, _apply_swap
+from ..quoter import PathHop, quote_path_exact_output
+from ..router_decoder import DecodedSwap
+
+__all__ = [
+    "PoolResolver",
+    "ProjectedSwap",
+    "ProjectedState",
+    "pair_resolver",
+    "project_state",
+]
+
+# resolves an (token_in, token_out) pair to the address of the pool a router would
+# use for it, or None if it is not tracked
+PoolResolver = Callable[[str, str], Optional[str]]
+
+
+@dataclass
+class ProjectedSwap:
+    # position of the transaction in the decoded_swaps argument
+    index: int
+    decoded: DecodedSwap
+    # the predicted swap in each pool along the route
+    swaps: List[PendingSwap]
+    amount_in: int
+    amount_out: int
+
+
+class ProjectedState:
+    """
+    Pool states with pending transactions applied. Pools touched by a projected swap
+    are branches (copy-on-write copies), all others are read from the underlying
+    registry, which is never modified. Index it like the registry to quote against
+    the projected state
+    """
+
+    def __init__(self, registry: Mapping[str, object]) -> None:
+        self.registry = registry
+        # branched pool states, keyed by address
+        self.pools: Dict[str, object] = {}
+        self.applied: List[ProjectedSwap] = []
+        # (index, decoded swap, reason) for transactions that were not applied
+        self.skipped: List[Tuple[int, DecodedSwap, str]] = []
+
+    def __getitem__(self, address: str) -> Any:
+        pool = self.pools.get(address)
+        return pool if pool is not None else self.registry[address]
+
+    def __contains__(self, address: str) -> bool:
+        return address in self.pools or address in self.registry
+
+    def _branch(self, address: str):
+        pool = self.pools.get(address)
+        if pool is None:
+            pool = self.pools[address] = self.registry[address].branch()
+        return pool
+
+
+def _resolve_route(
+    decoded: DecodedSwap,
+    resolve_pool: PoolResolver,
+) -> Optional[List[Tuple[str, int, int]]]:
+    """
+    Returns the (pool, token_in index, token_out index) hops of a decoded swap through
+    two-token pools, whose token0 is the lower address of the pair
+    """
+    tokens = decoded.path or [decoded.token_in, decoded.token_out]
+    if None in tokens or len(tokens) < 2:
+        return None
+    route = []
+    for token_in, token_out in zip(tokens, tokens[1:]):
+        pool = resolve_pool(token_in, token_out)
+        if pool is None:
+            return None
+        zero_for_one = token_in.lower() < token_out.lower()
+        route.append((pool, 0 if zero_for_one else 1, 1 if zero_for_one else 0))
+    return route
+
+
+def pair_resolver(pools_by_pair: Mapping[Tuple[str, str], str]) -> PoolResolver:
+    """
+    Builds a PoolResolver from a mapping of token pairs to pool addresses. Pairs are
+    matched in either order and regardless of address case
+    """
+    lookup = {
+        tuple(sorted(token.lower() for token in pair)): pool
+        for pair, pool in pools_by_pair.items()
+    }
+    return lambda token_in, token_out: lookup.get(
+        tuple(sorted((token_in.lower(), token_out.lower())))
+    )
+
+
+def project_state(
+    registry: Mapping[str, object],
+    decoded_swaps: Sequence[DecodedSwap],
+    resolve_pool: PoolResolver,
+) -> ProjectedState:
+    """
+    Applies pending router and aggregator swaps (from decode_router_calldata), in the
+    order given, to branches of the registry's pool states, and returns the projected
+    state for quoting against the post-transaction pools.
+
+    Each swap is routed through the pools returned by `resolve_pool`. A swap is
+    skipped, leaving the projection unchanged, if its tokens or pools are unknown or
+    if it would revert against the projected state: an exact-input swap whose output
+    falls below its minimum, or an exact-output swap needing more than its maximum
+    input
+    """
+    projection = ProjectedState(registry)
+
+    for index, decoded in enumerate(decoded_swaps):
+        route = _resolve_route(decoded, resolve_pool)
+        if route is None:
+            projection.skipped.append((index, decoded, "unknown tokens or pools"))
+            continue
+        if any(pool not in projection for pool, _, _ in route):
+            projection.skipped.append((index, decoded, "pool not in the registry"))
+            continue
+
+        amount_in = decoded.amount_in
+        if not decoded.exact_input:
+            try:
+                amount_in = quote_path_exact_output(
+                    [
+                        PathHop(projection[pool], token_in, token_out)
+                        for pool, token_in, token_out in route
+                    ],
+                    decoded.amount_out,
+                )[0]
+            except Exception as e:
+                projection.skipped.append((index, decoded, f"cannot be filled: {e}"))
+                continue
+            if amount_in > decoded.amount_in:
+                projection.skipped.append((index, decoded, "exceeds maximum input"))
+                continue
+
+        branches = [projection._branch(pool) for pool, _, _ in route]
+        checkpoints = [branch.checkpoint() for branch in branches]
+        swaps = []
+        amount = amount_in
+        try:
+            for branch, (pool, token_in, token_out) in zip(branches, route):
+                swaps.append(PendingSwap(pool, token_in, token_out, amount))
+                amount = _apply_swap(branch, token_in, token_out, amount)
+        except Exception as e:
+            reason = f"simulation failed: {e}"
+        else:
+            if not decoded.exact_input or amount >= decoded.amount_out:
+                projection.applied.append(
+                    ProjectedSwap(index, decoded, swaps, amount_in, amount)
+                )
+                continue
+            reason = "output below minimum"
+
+        # the transaction would revert, so undo its partial effects
+        for branch, checkpoint in reversed(list(zip(branches, checkpoints))):
+            branch.restore(checkpoint)
+        projection.skipped.append((index, decoded, reason))
+
+    return projection
@@ -0,0 +1,252 @@
+from dataclasses import dataclass
+from typing import Dict, Iterable, List, Optional, Set, Tuple, Union
+from eth_utils import to_checksum_address
+from ..rpc import Transaction
+
+__all__ = ["TxFilter", "IngestResult", "SenderView", "NonceTracker"]
+
+
+def _to_bytes(value: Union[bytes, str]) -> bytes:
+    if isinstance(value, str):
+        return bytes.fromhex(value[2:] if value.startswith("0x") else value)
+    return bytes(value)
+
+
+class TxFilter:
+    """
+    Fast pre-filter for pending transactions: checks whether any watched address
+    (pool, router, token...) appears anywhere in a raw transaction or its calldata, at
+    any byte offset. This catches ABI-encoded arguments, packed V3 paths and the RLP
+    `to` field alike, so transactions that cannot touch a watched contract can be
+    discarded before decoding.
+
+    Small watch lists are scanned with one substring search per address, large ones
+    with a single pass over every 20-byte window
+    """
+
+    # above this many addresses, one pass over the data beats a search per address
+    WINDOW_SCAN_THRESHOLD = 32
+
+    def __init__(self, addresses: Iterable[Union[bytes, str]] = ()) -> None:
+        self._addresses: Set[bytes] = set()
+        for address in addresses:
+            self.add(address)
+
+    def __len__(self) -> int:
+        return len(self._addresses)
+
+    def __contains__(self, address: Union[bytes, str]) -> bool:
+        return _to_bytes(address) in self._addresses
+
+    def add(self, address: Union[bytes, str]) -> None:
+        address = _to_bytes(address)
+        assert len(address) == 20, f"Expected a 20-byte address, found {len(address)}"
+        self._addresses.add(address)
+
+    def remove(self, address: Union[bytes, str]) -> None:
+        self._addresses.discard(_to_bytes(address))
+
+    def _scan(self, data: bytes, first_only: bool) -> Set[bytes]:
+        found = set()
+        if len(self._addresses) <= self.WINDOW_SCAN_THRESHOLD:
+            for address in self._addresses:
+                if address in data:
+                    found.add(address)
+                    if first_only:
+                        break
+            return found
+
+        addresses = self._addresses
+        for offset in range(len(data) - 19):
+            window = data[offset : offset + 20]
+            if window in addresses:
+                found.add(window)
+                if first_only:
+                    break
+        return found
+
+    def match(self, raw_tx_or_calldata: Union[bytes, str]) -> bool:
+        """
+        Returns True if any watched address appears in the data
+        """
+        return bool(self._scan(_to_bytes(raw_tx_or_calldata), first_only=True))
+
+    def matches(self, raw_tx_or_calldata: Union[bytes, str]) -> Set[str]:
+        """
+        Returns the checksummed watched addresses appearing in the data
+        """
+        return {
+            to_checksum_address(address)
+            for address in self._scan(_to_bytes(raw_tx_or_calldata), first_only=False)
+        }
+
+
+@dataclass
+class IngestResult:
+    """
+    Outcome of NonceTracker.ingest:
+        - "new": the first transaction seen for this sender and nonce
+        - "replacement": replaced a pending transaction (available as `replaced`)
+        - "underpriced": same sender and nonce without a sufficient fee bump, ignored
+        - "duplicate": already tracked
+        - "stale": the nonce is already confirmed
+    """
+
+    status: str
+    replaced: Optional[Transaction] = None
+
+
+@dataclass
+class SenderView:
+    sender: str
+    confirmed_nonce: Optional[int]
+    pending: Dict[int, Transaction]
+    # missing nonces between the next executable nonce and the highest pending one
+    gaps: List[int]
+
+    @property
+    def executable(self) -> List[Transaction]:
+        """
+        Pending transactions that can be mined in sequence, stopping at the first gap
+        """
+        transactions = []
+        if not self.pending:
+            return transactions
+        nonce = (
+            self.confirmed_nonce
+            if self.confirmed_nonce is not None
+            else min(self.pending)
+        )
+        while nonce in self.pending:
+            transactions.append(self.pending[nonce])
+            nonce += 1
+        return transactions
+
+
+def _fees(transaction: Transaction) -> Tuple[int, int]:
+    if transaction.max_fee_per_gas is not None:
+        return (
+            transaction.max_fee_per_gas,
+            transaction.max_priority_fee_per_gas or 0,
+        )
+    return transaction.gas_price or 0, transaction.gas_price or 0
+
+
+class NonceTracker:
+    """
+    Tracks pending transactions per sender and nonce, detecting replacements (same
+    sender and nonce with both fee caps bumped by at least `price_bump_percent`, the
+    rule geth applies), transactions dropped when a competing nonce is mined, and
+    nonce gaps that keep later transactions from executing
+    """
+
+    def __init__(self, price_bump_percent: int = 10) -> None:
+        self.price_bump_percent = price_bump_percent
+        self._pending: Dict[str, Dict[int, Transaction]] = {}
+        self._confirmed: Dict[str, int] = {}
+        self._by_hash: Dict[bytes, Transaction] = {}
+
+    def __len__(self) -> int:
+        return len(self._by_hash)
+
+    def _is_bump(self, new: Transaction, old: Transaction) -> bool:
+        multiplier = 100 + self.price_bump_percent
+        return all(
+            new_fee * 100 >= old_fee * multiplier
+            for new_fee, old_fee in zip(_fees(new), _fees(old))
+        )
+
+    def ingest(self, transaction: Transaction) -> IngestResult:
+        sender = transaction.sender.lower()
+        if transaction.hash in self._by_hash:
+            return IngestResult("duplicate")
+        confirmed = self._confirmed.get(sender)
+        if confirmed is not None and transaction.nonce < confirmed:
+            return IngestResult("stale")
+
+        pending = self._pending.setdefault(sender, {})
+        existing = pending.get(transaction.nonce)
+        if existing is not None and not self._is_bump(transaction, existing):
+            return IngestResult("underpriced")
+
+        pending[transaction.nonce] = transaction
+        self._by_hash[transaction.hash] = transaction
+        if existing is None:
+            return IngestResult("new")
+        del self._by_hash[existing.hash]
+        return IngestResult("replacement", existing)
+
+    def drop(self, transaction_hash: bytes) -> Optional[Transaction]:
+        """
+        Removes a transaction, e.g. after the node evicted it
+        """
+        transaction = self._by_hash.pop(transaction_hash, None)
+        if transaction is not None:
+            pending = self._pending[transaction.sender.lower()]
+            del pending[transaction.nonce]
+            if not pending:
+                del self._pending[transaction.sender.lower()]
+        return transaction
+
+    def confirm(
+        self, sender: str, nonce: int, transaction_hash: Optional[bytes] = None
+    ) -> List[Transaction]:
+        """
+        Records that the transaction with `nonce` from `sender` was mined, removing
+        every pending transaction at or below it. Returns the removed transactions
+        other than the mined one, identified by `transaction_hash`. Without a hash,
+        the pending transaction at `nonce` is assumed to be the mined one
+        """
+        sender = sender.lower()
+        self._confirmed[sender] = max(self._confirmed.get(sender, 0), nonce + 1)
+        pending = self._pending.get(sender, {})
+        dropped = []
+        for pending_nonce in sorted(pending):
+            if pending_nonce > nonce:
+                break
+            transaction = pending.pop(pending_nonce)
+            del self._by_hash[transaction.hash]
+            if pending_nonce == nonce and transaction_hash in (None, transaction.hash):
+                continue
+            dropped.append(transaction)
+        if not pending:
+            self._pending.pop(sender, None)
+        return dropped
+
+    def confirm_block(self, transactions: Iterable[Transaction]) -> List[Transaction]:
+        """
+        Applies every transaction of a mined block, returning the pending transactions
+        dropped because a different transaction with the same nonce was mined
+        """
+        dropped = []
+        for transaction in transactions:
+            dropped.extend(
+                self.confirm(transaction.sender, transaction.nonce, transaction.hash)
+            )
+        return dropped
+
+    def set_confirmed_nonce(self, sender: str, nonce: int) -> None:
+        """
+        Sets the next nonce expected from `sender`, e.g. from eth_getTransactionCount
+        """
+        self._confirmed[sender.lower()] = nonce
+
+    def nonce_gaps(self, sender: str) -> List[int]:
+        sender = sender.lower()
+        pending = self._pending.get(sender)
+        if not pending:
+            return []
+        start = self._confirmed.get(sender, min(pending))
+        return [nonce for nonce in range(start, max(pending)) if nonce not in pending]
+
+    def sender_view(self, sender: str) -> SenderView:
+        key = sender.lower()
+        return SenderView(
+            sender=sender,
+            confirmed_nonce=self._confirmed.get(key),
+            pending=dict(self._pending.get(key, {})),
+            gaps=self.nonce_gaps(key),
+        )
+
+    def senders(self) -> List[str]:
+        return list(self._pending)
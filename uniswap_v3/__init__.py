@@ -0,0 +1,5 @@
+from .tick_math import *
+from .sqrt_price_math import *
+from .swap_math import *
+from .variants import *
+from .swap import *
\ No newline at end of file
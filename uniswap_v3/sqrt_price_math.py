@@ -0,0 +1,128 @@
+from ..constants import MAX_UINT160, MAX_UINT256, Q96
+from ..exceptions import TickMathError
+
+__all__ = [
+    "RESOLUTION",
+    "get_next_sqrt_price_from_amount0_rounding_up",
+    "get_next_sqrt_price_from_amount1_rounding_down",
+    "get_next_sqrt_price_from_input",
+    "get_next_sqrt_price_from_output",
+    "get_amount0_delta",
+    "get_amount1_delta",
+]
+
+# Port of Uniswap V3 SqrtPriceMath.sol
+RESOLUTION = 96
+
+
+def _div_rounding_up(numerator: int, denominator: int) -> int:
+    return -(-numerator // denominator)
+
+
+def get_next_sqrt_price_from_amount0_rounding_up(
+    sqrt_price_x96: int, liquidity: int, amount: int, add: bool
+) -> int:
+    if amount == 0:
+        return sqrt_price_x96
+    numerator1 = liquidity << RESOLUTION
+
+    product = amount * sqrt_price_x96
+    if add:
+        # the contract takes this path only if neither the product nor the
+        # denominator overflows uint256, and otherwise falls back to a formula with
+        # different rounding
+        if product <= MAX_UINT256 and numerator1 + product <= MAX_UINT256:
+            return _div_rounding_up(numerator1 * sqrt_price_x96, numerator1 + product)
+        denominator = numerator1 // sqrt_price_x96 + amount
+        if denominator > MAX_UINT256:
+            raise TickMathError("Input overflows the sqrt price denominator")
+        return _div_rounding_up(numerator1, denominator)
+
+    if product > MAX_UINT256 or numerator1 <= product:
+        raise TickMathError("Output exceeds the token0 available at this liquidity")
+    result = _div_rounding_up(numerator1 * sqrt_price_x96, numerator1 - product)
+    if result > MAX_UINT160:
+        raise TickMathError("Next sqrt price overflows uint160")
+    return result
+
+
+def get_next_sqrt_price_from_amount1_rounding_down(
+    sqrt_price_x96: int, liquidity: int, amount: int, add: bool
+) -> int:
+    if add:
+        result = sqrt_price_x96 + (amount << RESOLUTION) // liquidity
+        if result > MAX_UINT160:
+            raise TickMathError("Next sqrt price overflows uint160")
+        return result
+
+    quotient = _div_rounding_up(amount << RESOLUTION, liquidity)
+    if sqrt_price_x96 <= quotient:
+        raise TickMathError("Output exceeds the token1 available at this liquidity")
+    return sqrt_price_x96 - quotient
+
+
+def get_next_sqrt_price_from_input(
+    sqrt_price_x96: int, liquidity: int, amount_in: int, zero_for_one: bool
+) -> int:
+    """
+    Returns the sqrt price after adding `amount_in` of token0 (zero_for_one) or token1
+    """
+    assert sqrt_price_x96 > 0 and liquidity > 0, "Price and liquidity must be positive"
+    if zero_for_one:
+        return get_next_sqrt_price_from_amount0_rounding_up(
+            sqrt_price_x96, liquidity, amount_in, True
+        )
+    return get_next_sqrt_price_from_amount1_rounding_down(
+        sqrt_price_x96, liquidity, amount_in, True
+    )
+
+
+def get_next_sqrt_price_from_output(
+    sqrt_price_x96: int, liquidity: int, amount_out: int, zero_for_one: bool
+) -> int:
+    """
+    Returns the sqrt price after removing `amount_out` of token1 (zero_for_one) or
+    token0
+    """
+    assert sqrt_price_x96 > 0 and liquidity > 0, "Price and liquidity must be positive"
+    if zero_for_one:
+        return get_next_sqrt_price_from_amount1_rounding_down(
+            sqrt_price_x96, liquidity, amount_out, False
+        )
+    return get_next_sqrt_price_from_amount0_rounding_up(
+        sqrt_price_x96, liquidity, amount_out, False
+    )
+
+
+def get_amount0_delta(
+    sqrt_ratio_a_x96: int, sqrt_ratio_b_x96: int, liquidity: int, round_up: bool
+) -> int:
+    """
+    Returns the amount of token0 between two sqrt prices for a liquidity amount
+    """
+    if sqrt_ratio_a_x96 > sqrt_ratio_b_x96:
+        sqrt_ratio_a_x96, sqrt_ratio_b_x96 = sqrt_ratio_b_x96, sqrt_ratio_a_x96
+    assert sqrt_ratio_a_x96 > 0, "Sqrt prices must be positive"
+
+    numerator1 = liquidity << RESOLUTION
+    numerator2 = sqrt_ratio_b_x96 - sqrt_ratio_a_x96
+    if round_up:
+        return _div_rounding_up(
+            _div_rounding_up(numerator1 * numerator2, sqrt_ratio_b_x96),
+            sqrt_ratio_a_x96,
+        )
+    return (numerator1 * numerator2 // sqrt_ratio_b_x96) // sqrt_ratio_a_x96
+
+
+def get_amount1_delta(
+    sqrt_ratio_a_x96: int, sqrt_ratio_b_x96: int, liquidity: int, round_up: bool
+) -> int:
+    """
+    Returns the amount of token1 between two sqrt prices for a liquidity amount
+    """
+    if sqrt_ratio_a_x96 > sqrt_ratio_b_x96:
+        sqrt_ratio_a_x96, sqrt_ratio_b_x96 = sqrt_ratio_b_x96, sqrt_ratio_a_x96
+
+    if round_up:
+        return _div_rounding_up(liquidity * (sqrt_ratio_b_x96 - sqrt_ratio_a_x96), Q96)
+    return liquidity * (sqrt_ratio_b_x96 - sqrt_ratio_a_x96) // Q96
@@ -0,0 +1,292 @@
+import bisect
+import time
+from dataclasses import dataclass
+from typing import TYPE_CHECKING, Callable, List, Optional, Tuple
+from .swap_math import compute_swap_step
+from .tick_math import get_sqrt_ratio_at_tick, get_tick_at_sqrt_ratio
+from .variants import ExchangeVariant
+from ..constants import MAX_SQRT_RATIO, MAX_TICK, MIN_SQRT_RATIO, MIN_TICK, Q128
+from ..exceptions import SwapSimulationError
+from ..log import get_logger
+from ..metrics import increment_counter, metrics_enabled, observe_latency
+
+if TYPE_CHECKING:
+    from ..pool_state import V3PoolState
+
+__all__ = [
+    "V3SwapResult",
+    "V3SwapStep",
+    "next_initialized_tick_within_one_word",
+    "simulate_v3_swap",
+]
+
+_logger = get_logger("uniswap_v3")
+
+
+@dataclass
+class V3SwapResult:
+    # signed balance changes of the pool (positive = paid into the pool)
+    amount0: int
+    amount1: int
+    sqrt_price_x96: int
+    tick: int
+    liquidity: int
+    # number of initialized ticks crossed
+    ticks_crossed: int
+    # fees charged on the input, the part of them kept by the protocol, and the
+    # resulting increase of the input token's feeGrowthGlobalX128
+    fee_amount: int = 0
+    protocol_fee: int = 0
+    fee_growth_global_x128: int = 0
+    # per-step breakdown, only recorded when requested
+    trace: Optional[List["V3SwapStep"]] = None
+
+
+@dataclass
+class V3SwapStep:
+    sqrt_price_start_x96: int
+    sqrt_price_x96: int
+    # the tick the step was bounded by, and whether it was initialized and crossed
+    tick_next: int
+    initialized: bool
+    crossed: bool
+    # liquidity in range during the step, and after crossing tick_next
+    liquidity: int
+    liquidity_after: int
+    amount_in: int
+    amount_out: int
+    fee_amount: int
+    protocol_fee: int
+    amount_remaining: int
+
+
+def next_initialized_tick_within_one_word(
+    initialized_ticks: List[int], tick: int, tick_spacing: int, lte: bool
+) -> Tuple[int, bool]:
+    """
+    Equivalent of TickBitmap.nextInitializedTickWithinOneWord over a sorted list of
+    initialized ticks. Stopping at bitmap word boundaries matters for exactness: the
+    swap loop rounds at every step, so steps must split exactly where the pool's do
+    """
+    compressed = tick // tick_spacing
+    if lte:
+        word_start = compressed - compressed % 256
+        i = bisect.bisect_right(initialized_ticks, compressed * tick_spacing) - 1
+        if i >= 0 and initialized_ticks[i] >= word_start * tick_spacing:
+            return initialized_ticks[i], True
+        return word_start * tick_spacing, False
+
+    compressed += 1
+    word_end = compressed - compressed % 256 + 255
+    i = bisect.bisect_left(initialized_ticks, compressed * tick_spacing)
+    if i < len(initialized_ticks) and initialized_ticks[i] <= word_end * tick_spacing:
+        return initialized_ticks[i], True
+    return word_end * tick_spacing, False
+
+
+def _walk_ticks(
+    pool_state,
+    amount_specified: int,
+    zero_for_one: bool,
+    sqrt_price_limit_x96: int,
+    fee_pips: int,
+    protocol_fee: int = 0,
+    trace: bool = False,
+    fee_protocol: int = 0,
+    variant: ExchangeVariant = ExchangeVariant.UNISWAP_V3,
+    swap_step: Callable = compute_swap_step,
+) -> Tuple[V3SwapResult, int]:
+    """
+    The swap loop shared by the V3 and V4 simulations, stepping through initialized
+    ticks with `fee_pips` charged on the input. The protocol's share of every step's
+    fee is accumulated and returned along with the result: `protocol_fee` is the V4
+    protocol fee in pips for the swap direction, `fee_protocol` the V3 protocol fee of
+    the direction, as encoded by the pool's `variant`. `swap_step` computes each
+    step, with the signature and sign convention of compute_swap_step. With `trace`,
+    every step is recorded
+    """
+    if amount_specified == 0:
+        raise SwapSimulationError("Swap amount must be non-zero")
+
+    sqrt_price_x96 = pool_state.sqrt_price_x96
+    if sqrt_price_limit_x96 is None:
+        sqrt_price_limit_x96 = (
+            MIN_SQRT_RATIO + 1 if zero_for_one else MAX_SQRT_RATIO - 1
+        )
+    if zero_for_one:
+        valid_limit = MIN_SQRT_RATIO < sqrt_price_limit_x96 < sqrt_price_x96
+    else:
+        valid_limit = sqrt_price_x96 < sqrt_price_limit_x96 < MAX_SQRT_RATIO
+    if not valid_limit:
+        raise SwapSimulationError(
+            f"Invalid sqrt price limit {sqrt_price_limit_x96} (SPL)"
+        )
+
+    exact_input = amount_specified > 0
+    initialized_ticks = sorted(pool_state.ticks)
+    amount_remaining = amount_specified
+    amount_calculated = 0
+    amount_to_protocol = 0
+    fee_total = 0
+    fee_growth_global_x128 = 0
+    tick = pool_state.tick
+    liquidity = pool_state.liquidity
+    ticks_crossed = 0
+    steps = [] if trace else None
+
+    while amount_remaining != 0 and sqrt_price_x96 != sqrt_price_limit_x96:
+        sqrt_price_start_x96 = sqrt_price_x96
+        tick_next, initialized = next_initialized_tick_within_one_word(
+            initialized_ticks, tick, pool_state.tick_spacing, zero_for_one
+        )
+        tick_next = max(MIN_TICK, min(MAX_TICK, tick_next))
+        sqrt_price_next_x96 = get_sqrt_ratio_at_tick(tick_next)
+
+        if (
+            sqrt_price_next_x96 < sqrt_price_limit_x96
+            if zero_for_one
+            else sqrt_price_next_x96 > sqrt_price_limit_x96
+        ):
+            sqrt_price_target_x96 = sqrt_price_limit_x96
+        else:
+            sqrt_price_target_x96 = sqrt_price_next_x96
+
+        sqrt_price_x96, amount_in, amount_out, fee_amount = swap_step(
+            sqrt_price_x96,
+            sqrt_price_target_x96,
+            liquidity,
+            amount_remaining,
+            fee_pips,
+            variant.fee_denominator,
+        )
+
+        if exact_input:
+            amount_remaining -= amount_in + fee_amount
+            amount_calculated -= amount_out
+        else:
+            amount_remaining += amount_out
+            amount_calculated += amount_in + fee_amount
+
+        if protocol_fee:
+            # the protocol's share depends on the input, not on the LP fee
+            protocol_delta = (
+                fee_amount
+                if fee_pips == protocol_fee
+                else (amount_in + fee_amount) * protocol_fee // 1_000_000
+            )
+        else:
+            protocol_delta = variant.protocol_fee_share(fee_amount, fee_protocol)
+        amount_to_protocol += protocol_delta
+        fee_total += fee_amount
+        if liquidity > 0:
+            fee_growth_global_x128 += (fee_amount - protocol_delta) * Q128 // liquidity
+
+        liquidity_before = liquidity
+        crossed = False
+        if sqrt_price_x96 == sqrt_price_next_x96:
+            if initialized:
+                liquidity_net = pool_state.ticks[tick_next].liquidity_net
+                liquidity += -liquidity_net if zero_for_one else liquidity_net
+                if liquidity < 0:
+                    raise SwapSimulationError(
+                        f"Negative liquidity after crossing tick {tick_next}"
+                    )
+                ticks_crossed += 1
+                crossed = True
+            tick = tick_next - 1 if zero_for_one else tick_next
+        elif sqrt_price_x96 != sqrt_price_start_x96:
+            tick = get_tick_at_sqrt_ratio(sqrt_price_x96)
+
+        if steps is not None:
+            steps.append(
+                V3SwapStep(
+                    sqrt_price_start_x96=sqrt_price_start_x96,
+                    sqrt_price_x96=sqrt_price_x96,
+                    tick_next=tick_next,
+                    initialized=initialized,
+                    crossed=crossed,
+                    liquidity=liquidity_before,
+                    liquidity_after=liquidity,
+                    amount_in=amount_in,
+                    amount_out=amount_out,
+                    fee_amount=fee_amount,
+                    protocol_fee=protocol_delta,
+                    amount_remaining=amount_remaining,
+                )
+            )
+
+    if amount_remaining:
+        _logger.debug(
+            "%s: swap reached the price limit with %d of %d unfilled",
+            getattr(pool_state, "address", pool_state),
+            amount_remaining,
+            amount_specified,
+        )
+
+    if zero_for_one == exact_input:
+        amount0, amount1 = amount_specified - amount_remaining, amount_calculated
+    else:
+        amount0, amount1 = amount_calculated, amount_specified - amount_remaining
+
+    result = V3SwapResult(
+        amount0=amount0,
+        amount1=amount1,
+        sqrt_price_x96=sqrt_price_x96,
+        tick=tick,
+        liquidity=liquidity,
+        ticks_crossed=ticks_crossed,
+        fee_amount=fee_total,
+        protocol_fee=amount_to_protocol,
+        fee_growth_global_x128=fee_growth_global_x128,
+        trace=steps,
+    )
+    return result, amount_to_protocol
+
+
+def _variant(pool_state) -> ExchangeVariant:
+    return getattr(pool_state, "variant", ExchangeVariant.UNISWAP_V3)
+
+
+def simulate_v3_swap(
+    pool_state: "V3PoolState",
+    amount_specified: int,
+    zero_for_one: bool,
+    sqrt_price_limit_x96: Optional[int] = None,
+    return_trace: bool = False,
+) -> V3SwapResult:
+    """
+    Simulates UniswapV3Pool.swap against a V3PoolState without modifying it. A
+    positive `amount_specified` is an exact input, negative an exact output. Without a
+    price limit the swap may run to the end of the tick range.
+
+    The pool state's `variant` selects the fork's fee unit and protocol fee encoding.
+    If the state has a `fee_protocol` (slot0's packed feeProtocol, e.g. 4 bits for
+    token0 and then 4 bits for token1 on Uniswap), the protocol's share of the fees is
+    split off as the pool does. This leaves the swap amounts unchanged but lowers the
+    fee growth credited to LPs.
+
+    With `return_trace`, the result's `trace` lists every step of the swap loop (the
+    tick it stopped at, liquidity, amounts and fee), for comparing against on-chain
+    execution
+    """
+    start = time.perf_counter()
+    variant = _variant(pool_state)
+    result, _ = _walk_ticks(
+        pool_state,
+        amount_specified,
+        zero_for_one,
+        sqrt_price_limit_x96,
+        pool_state.fee,
+        trace=return_trace,
+        fee_protocol=variant.directional_fee_protocol(
+            getattr(pool_state, "fee_protocol", 0), zero_for_one
+        ),
+        variant=variant,
+    )
+
+    if metrics_enabled():
+        increment_counter("v3_swaps_simulated")
+        increment_counter("v3_ticks_crossed", result.ticks_crossed)
+        observe_latency("v3_swap_simulation_seconds", time.perf_counter() - start)
+
+    return result
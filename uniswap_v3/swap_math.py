@@ -0,0 +1,102 @@
+from typing import Tuple
+from .sqrt_price_math import (
+    get_amount0_delta,
+    get_amount1_delta,
+    get_next_sqrt_price_from_input,
+    get_next_sqrt_price_from_output,
+)
+
+__all__ = ["V3_FEE_DENOMINATOR", "compute_swap_step"]
+
+# Port of Uniswap V3 SwapMath.sol
+V3_FEE_DENOMINATOR = 1_000_000
+
+
+def compute_swap_step(
+    sqrt_ratio_current_x96: int,
+    sqrt_ratio_target_x96: int,
+    liquidity: int,
+    amount_remaining: int,
+    fee_pips: int,
+    fee_denominator: int = V3_FEE_DENOMINATOR,
+) -> Tuple[int, int, int, int]:
+    """
+    Computes a single swap step within one liquidity range, exactly as
+    SwapMath.computeSwapStep. A positive `amount_remaining` is an exact input,
+    negative an exact output. `fee_pips` is in units of 1/fee_denominator. Returns
+    (sqrt_ratio_next_x96, amount_in, amount_out, fee_amount)
+    """
+    zero_for_one = sqrt_ratio_current_x96 >= sqrt_ratio_target_x96
+    exact_in = amount_remaining >= 0
+
+    if exact_in:
+        amount_remaining_less_fee = (
+            amount_remaining * (fee_denominator - fee_pips) // fee_denominator
+        )
+        amount_in = (
+            get_amount0_delta(
+                sqrt_ratio_target_x96, sqrt_ratio_current_x96, liquidity, True
+            )
+            if zero_for_one
+            else get_amount1_delta(
+                sqrt_ratio_current_x96, sqrt_ratio_target_x96, liquidity, True
+            )
+        )
+        if amount_remaining_less_fee >= amount_in:
+            sqrt_ratio_next_x96 = sqrt_ratio_target_x96
+        else:
+            sqrt_ratio_next_x96 = get_next_sqrt_price_from_input(
+                sqrt_ratio_current_x96,
+                liquidity,
+                amount_remaining_less_fee,
+                zero_for_one,
+            )
+    else:
+        amount_out = (
+            get_amount1_delta(
+                sqrt_ratio_target_x96, sqrt_ratio_current_x96, liquidity, False
+            )
+            if zero_for_one
+            else get_amount0_delta(
+                sqrt_ratio_current_x96, sqrt_ratio_target_x96, liquidity, False
+            )
+        )
+        if -amount_remaining >= amount_out:
+            sqrt_ratio_next_x96 = sqrt_ratio_target_x96
+        else:
+            sqrt_ratio_next_x96 = get_next_sqrt_price_from_output(
+                sqrt_ratio_current_x96, liquidity, -amount_remaining, zero_for_one
+            )
+
+    reached_target = sqrt_ratio_target_x96 == sqrt_ratio_next_x96
+
+    if zero_for_one:
+        if not (reached_target and exact_in):
+            amount_in = get_amount0_delta(
+                sqrt_ratio_next_x96, sqrt_ratio_current_x96, liquidity, True
+            )
+        if not (reached_target and not exact_in):
+            amount_out = get_amount1_delta(
+                sqrt_ratio_next_x96, sqrt_ratio_current_x96, liquidity, False
+            )
+    else:
+        if not (reached_target and exact_in):
+            amount_in = get_amount1_delta(
+                sqrt_ratio_current_x96, sqrt_ratio_next_x96, liquidity, True
+            )
+        if not (reached_target and not exact_in):
+            amount_out = get_amount0_delta(
+                sqrt_ratio_current_x96, sqrt_ratio_next_x96, liquidity, False
+            )
+
+    # cap the output amount to not exceed the remaining output amount
+    if not exact_in and amount_out > -amount_remaining:
+        amount_out = -amount_remaining
+
+    if exact_in and sqrt_ratio_next_x96 != sqrt_ratio_target_x96:
+        # the entire remaining amount was used, so the rest is taken as fee
+        fee_amount = amount_remaining - amount_in
+    else:
+        fee_amount = -(-amount_in * fee_pips // (fee_denominator - fee_pips))
+
+    return sqrt_ratio_next_x96, amount_in, amount_out, fee_amount
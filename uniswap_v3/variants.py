@@ -0,0 +1,65 @@
+from enum import Enum
+from typing import Dict
+from .swap_math import V3_FEE_DENOMINATOR
+from ..constants import PANCAKESWAP_V3_TICK_SPACINGS, TICK_SPACINGS
+
+__all__ = ["ExchangeVariant"]
+
+# PancakeSwap V3 packs two 16-bit protocol fees into slot0, as a fraction of the swap
+# fee in units of 1/10000
+_PANCAKESWAP_PROTOCOL_FEE_SP = 65536
+_PANCAKESWAP_PROTOCOL_FEE_DENOMINATOR = 10_000
+
+
+class ExchangeVariant(Enum):
+    """
+    V3 forks whose pool contracts differ from Uniswap's in the parameters of the swap
+    loop. The variant of a V3PoolState selects how its swaps are simulated
+    """
+
+    UNISWAP_V3 = "uniswap_v3"
+    PANCAKESWAP_V3 = "pancakeswap_v3"
+
+    @property
+    def fee_denominator(self) -> int:
+        """
+        The unit of a pool's `fee`, e.g. 1_000_000 for fees in hundredths of a bip
+        """
+        return V3_FEE_DENOMINATOR
+
+    @property
+    def tick_spacings(self) -> Dict[int, int]:
+        """
+        The tick spacing enabled by the factory for each fee tier
+        """
+        return _TICK_SPACINGS[self]
+
+    def directional_fee_protocol(self, fee_protocol: int, zero_for_one: bool) -> int:
+        """
+        Unpacks the protocol fee of one swap direction from slot0's feeProtocol
+        """
+        if self is ExchangeVariant.PANCAKESWAP_V3:
+            return (
+                fee_protocol % _PANCAKESWAP_PROTOCOL_FEE_SP
+                if zero_for_one
+                else fee_protocol >> 16
+            )
+        return fee_protocol % 16 if zero_for_one else fee_protocol >> 4
+
+    def protocol_fee_share(self, fee_amount: int, fee_protocol: int) -> int:
+        """
+        The part of a swap step's fee kept by the protocol, for the directional
+        protocol fee. Uniswap encodes the share as a denominator (the protocol keeps
+        1/fee_protocol of the fee), PancakeSwap as a numerator over 10000
+        """
+        if not fee_protocol:
+            return 0
+        if self is ExchangeVariant.PANCAKESWAP_V3:
+            return fee_amount * fee_protocol // _PANCAKESWAP_PROTOCOL_FEE_DENOMINATOR
+        return fee_amount // fee_protocol
+
+
+_TICK_SPACINGS = {
+    ExchangeVariant.UNISWAP_V3: TICK_SPACINGS,
+    ExchangeVariant.PANCAKESWAP_V3: PANCAKESWAP_V3_TICK_SPACINGS,
+}
\ No newline at end of file
@@ -0,0 +1,2 @@
+from .swap import *
+from .swap_math import *
\ No newline at end of file
@@ -0,0 +1,155 @@
+from dataclasses import dataclass
+from typing import TYPE_CHECKING, Optional, Tuple
+from ..exceptions import SwapSimulationError
+from ..uniswap_v3.swap import _walk_ticks
+from .swap_math import compute_v4_swap_step
+
+if TYPE_CHECKING:
+    from ..pool_state import V3PoolState
+
+__all__ = [
+    "DYNAMIC_FEE_FLAG",
+    "OVERRIDE_FEE_FLAG",
+    "MAX_LP_FEE",
+    "MAX_PROTOCOL_FEE",
+    "V4SwapParams",
+    "V4SwapResult",
+    "is_dynamic_fee",
+    "calculate_swap_fee",
+    "simulate_v4_swap",
+]
+
+# set in a pool key's fee to mark a pool whose LP fee is managed by its hook
+DYNAMIC_FEE_FLAG = 0x800000
+# set in the fee returned by beforeSwap to override a dynamic LP fee for one swap
+OVERRIDE_FEE_FLAG = 0x400000
+MAX_LP_FEE = 1_000_000
+# cap of each direction of the protocol fee, in pips
+MAX_PROTOCOL_FEE = 1_000
+
+
+@dataclass
+class V4SwapParams:
+    """
+    Arguments of PoolManager.swap. Following V4, a negative `amount_specified` is an
+    exact input and a positive one an exact output
+    """
+
+    zero_for_one: bool
+    amount_specified: int
+    sqrt_price_limit_x96: Optional[int] = None
+
+
+@dataclass
+class V4SwapResult:
+    # balance changes of the swapper (negative = owed to the pool), as BalanceDelta
+    amount0: int
+    amount1: int
+    sqrt_price_x96: int
+    tick: int
+    liquidity: int
+    ticks_crossed: int
+    # fees charged on the input, in pips, and the input amount kept by the protocol
+    lp_fee: int
+    swap_fee: int
+    amount_to_protocol: int
+
+
+def is_dynamic_fee(fee: int) -> bool:
+    return fee == DYNAMIC_FEE_FLAG
+
+
+def calculate_swap_fee(protocol_fee: int, lp_fee: int) -> int:
+    """
+    Combines a directional protocol fee and the LP fee (both in pips) into the fee
+    charged on a swap's input, as ProtocolFeeLibrary.calculateSwapFee. The protocol
+    fee is taken first and the LP fee from the remainder
+    """
+    return protocol_fee + lp_fee - protocol_fee * lp_fee // 1_000_000
+
+
+def _lp_fee(pool_state, hook_fee_override: Optional[int]) -> int:
+    fee = pool_state.fee
+    if not is_dynamic_fee(fee):
+        # hooks can only override the fee of dynamic fee pools, anything else is
+        # ignored like in PoolManager.swap
+        lp_fee = fee
+    elif hook_fee_override is not None and hook_fee_override & OVERRIDE_FEE_FLAG:
+        lp_fee = hook_fee_override & ~OVERRIDE_FEE_FLAG
+    else:
+        # the fee last set by the hook with updateDynamicLPFee
+        lp_fee = getattr(pool_state, "lp_fee", 0)
+    if lp_fee > MAX_LP_FEE:
+        raise SwapSimulationError(f"LP fee {lp_fee} is too large")
+    return lp_fee
+
+
+def _swap_step(
+    sqrt_price_current_x96: int,
+    sqrt_price_target_x96: int,
+    liquidity: int,
+    amount_remaining: int,
+    fee_pips: int,
+    fee_denominator: int,
+) -> Tuple[int, int, int, int]:
+    # the shared loop uses the V3 sign convention
+    return compute_v4_swap_step(
+        sqrt_price_current_x96,
+        sqrt_price_target_x96,
+        liquidity,
+        -amount_remaining,
+        fee_pips,
+    )
+
+
+def simulate_v4_swap(
+    pool_state: "V3PoolState",
+    params: V4SwapParams,
+    hook_fee_override: Optional[int] = None,
+) -> V4SwapResult:
+    """
+    Simulates PoolManager.swap against a pool state without modifying it. The state
+    needs the attributes of a V3PoolState, with `fee` holding the pool key's fee, and
+    for V4 pools optionally `lp_fee` (the current dynamic LP fee) and `protocol_fee`
+    (slot0's packed protocol fee: 12 bits for zero-for-one swaps, then 12 bits for the
+    other direction).
+
+    `hook_fee_override` is the fee returned by the hook's beforeSwap, which replaces
+    the LP fee of a dynamic fee pool if OVERRIDE_FEE_FLAG is set. Balance deltas
+    returned by hooks are not modeled
+    """
+    if params.amount_specified == 0:
+        raise SwapSimulationError("Swap amount must be non-zero")
+
+    lp_fee = _lp_fee(pool_state, hook_fee_override)
+    protocol_fee = getattr(pool_state, "protocol_fee", 0)
+    protocol_fee = (
+        protocol_fee % 4096 if params.zero_for_one else (protocol_fee >> 12) % 4096
+    )
+    swap_fee = calculate_swap_fee(protocol_fee, lp_fee) if protocol_fee else lp_fee
+
+    exact_input = params.amount_specified < 0
+    if not exact_input and swap_fee >= MAX_LP_FEE:
+        raise SwapSimulationError("Exact output swaps need a fee below 100%")
+
+    # the shared loop uses the V3 sign convention
+    result, amount_to_protocol = _walk_ticks(
+        pool_state,
+        -params.amount_specified,
+        params.zero_for_one,
+        params.sqrt_price_limit_x96,
+        swap_fee,
+        protocol_fee,
+        swap_step=_swap_step,
+    )
+    return V4SwapResult(
+        amount0=-result.amount0,
+        amount1=-result.amount1,
+        sqrt_price_x96=result.sqrt_price_x96,
+        tick=result.tick,
+        liquidity=result.liquidity,
+        ticks_crossed=result.ticks_crossed,
+        lp_fee=lp_fee,
+        swap_fee=swap_fee,
+        amount_to_protocol=amount_to_protocol,
+    )
@@ -0,0 +1,103 @@
+from typing import Tuple
+from ..uniswap_v3.sqrt_price_math import (
+    get_amount0_delta,
+    get_amount1_delta,
+    get_next_sqrt_price_from_input,
+    get_next_sqrt_price_from_output,
+)
+
+__all__ = ["compute_v4_swap_step"]
+
+# Port of Uniswap V4 SwapMath.sol
+_MAX_SWAP_FEE = 1_000_000
+
+
+def compute_v4_swap_step(
+    sqrt_price_current_x96: int,
+    sqrt_price_target_x96: int,
+    liquidity: int,
+    amount_remaining: int,
+    fee_pips: int,
+) -> Tuple[int, int, int, int]:
+    """
+    Computes a single swap step within one liquidity range, exactly as V4's
+    SwapMath.computeSwapStep. Following V4, a negative `amount_remaining` is an exact
+    input and a positive one an exact output. Returns
+    (sqrt_price_next_x96, amount_in, amount_out, fee_amount).
+
+    Unlike V3, a step that exhausts an exact input takes the input less fee as
+    `amount_in` rather than recomputing it from the price change, and an exact output
+    step that stops short of the target pays out exactly the remaining amount
+    """
+    zero_for_one = sqrt_price_current_x96 >= sqrt_price_target_x96
+
+    if amount_remaining < 0:
+        amount_remaining_less_fee = (
+            -amount_remaining * (_MAX_SWAP_FEE - fee_pips) // _MAX_SWAP_FEE
+        )
+        amount_in = (
+            get_amount0_delta(
+                sqrt_price_target_x96, sqrt_price_current_x96, liquidity, True
+            )
+            if zero_for_one
+            else get_amount1_delta(
+                sqrt_price_current_x96, sqrt_price_target_x96, liquidity, True
+            )
+        )
+        if amount_remaining_less_fee >= amount_in:
+            sqrt_price_next_x96 = sqrt_price_target_x96
+            # with a 100% fee, amount_in is 0 here
+            fee_amount = (
+                amount_in
+                if fee_pips == _MAX_SWAP_FEE
+                else -(-amount_in * fee_pips // (_MAX_SWAP_FEE - fee_pips))
+            )
+        else:
+            # the target is not reached, so the rest of the input is taken as fee
+            amount_in = amount_remaining_less_fee
+            sqrt_price_next_x96 = get_next_sqrt_price_from_input(
+                sqrt_price_current_x96,
+                liquidity,
+                amount_remaining_less_fee,
+                zero_for_one,
+            )
+            fee_amount = -amount_remaining - amount_in
+        amount_out = (
+            get_amount1_delta(
+                sqrt_price_next_x96, sqrt_price_current_x96, liquidity, False
+            )
+            if zero_for_one
+            else get_amount0_delta(
+                sqrt_price_current_x96, sqrt_price_next_x96, liquidity, False
+            )
+        )
+    else:
+        amount_out = (
+            get_amount1_delta(
+                sqrt_price_target_x96, sqrt_price_current_x96, liquidity, False
+            )
+            if zero_for_one
+            else get_amount0_delta(
+                sqrt_price_current_x96, sqrt_price_target_x96, liquidity, False
+            )
+        )
+        if amount_remaining >= amount_out:
+            sqrt_price_next_x96 = sqrt_price_target_x96
+        else:
+            amount_out = amount_remaining
+            sqrt_price_next_x96 = get_next_sqrt_price_from_output(
+                sqrt_price_current_x96, liquidity, amount_out, zero_for_one
+            )
+        amount_in = (
+            get_amount0_delta(
+                sqrt_price_next_x96, sqrt_price_current_x96, liquidity, True
+            )
+            if zero_for_one
+            else get_amount1_delta(
+                sqrt_price_current_x96, sqrt_price_next_x96, liquidity, True
+            )
+        )
+        # exact output swaps cannot have a 100% fee
+        fee_amount = -(-amount_in * fee_pips // (_MAX_SWAP_FEE - fee_pips))
+
+    return sqrt_price_next_x96, amount_in, amount_out, fee_amount
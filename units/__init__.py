@@ -0,0 +1 @@
+from .units import *
\ No newline at end of file
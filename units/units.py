@@ -0,0 +1,82 @@
+import re
+from typing import Iterable, List, Optional, Union
+
+__all__ = ["format_units", "parse_units", "format_units_batch", "parse_units_batch"]
+
+_UNITS_PATTERN = re.compile(r"([+-]?)(\d*)(?:\.(\d*))?")
+
+
+def format_units(amount: int, decimals: int, max_decimals: Optional[int] = None) -> str:
+    """
+    Formats an integer token amount in whole units, e.g. 1500000 with 6 decimals as
+    "1.5". Trailing zeros are removed, keeping at least one fractional digit like
+    ethers' formatUnits. With `max_decimals`, the fraction is truncated (toward zero)
+    to that many digits
+    """
+    assert decimals >= 0, f"Invalid decimals {decimals}"
+    sign = "-" if amount < 0 else ""
+    whole, fraction = divmod(abs(amount), 10 ** decimals)
+    fraction_digits = str(fraction).rjust(decimals, "0") if decimals else ""
+    if max_decimals is not None:
+        fraction_digits = fraction_digits[:max_decimals]
+    fraction_digits = fraction_digits.rstrip("0") or "0"
+    if sign and not whole and not fraction_digits.strip("0"):
+        # the amount truncated to zero
+        sign = ""
+    return f"{sign}{whole}.{fraction_digits}"
+
+
+def parse_units(value: str, decimals: int) -> int:
+    """
+    Parses a decimal string in whole units into an integer token amount, e.g. "1.5"
+    with 6 decimals as 1500000. Raises ValueError for malformed strings and for
+    fractions more precise than `decimals`, instead of silently rounding
+    """
+    assert decimals >= 0, f"Invalid decimals {decimals}"
+    match = _UNITS_PATTERN.fullmatch(value.strip().replace("_", ""))
+    if match is None or not (match.group(2) or match.group(3)):
+        raise ValueError(f"Invalid amount {value!r}")
+    sign, whole, fraction = match.group(1), match.group(2), match.group(3) or ""
+    fraction = fraction.rstrip("0")
+    if len(fraction) > decimals:
+        raise ValueError(f"{value!r} has more than {decimals} decimals")
+    amount = int(whole or "0") * 10 ** decimals
+    if fraction:
+        amount += int(fraction.ljust(decimals, "0"))
+    return -amount if sign == "-" else amount
+
+
+def _per_item(decimals: Union[int, Iterable[int]], count: int) -> List[int]:
+    if not hasattr(decimals, "__iter__"):
+        return [int(decimals)] * count
+    decimals = [int(value) for value in decimals]
+    assert len(decimals) == count, "Expected one decimals value per amount"
+    return decimals
+
+
+def format_units_batch(
+    amounts: Iterable[int],
+    decimals: Union[int, Iterable[int]],
+    max_decimals: Optional[int] = None,
+) -> List[str]:
+    """
+    Formats many amounts, with one decimals value for all of them or one per amount
+    (e.g. a dataframe's amount and decimals columns). numpy integer scalars are
+    accepted
+    """
+    amounts = [int(amount) for amount in amounts]
+    return [
+        format_units(amount, amount_decimals, max_decimals)
+        for amount, amount_decimals in zip(amounts, _per_item(decimals, len(amounts)))
+    ]
+
+
+def parse_units_batch(
+    values: Iterable[str],
+    decimals: Union[int, Iterable[int]],
+) -> List[int]:
+    values = list(values)
+    return [
+        parse_units(value, value_decimals)
+        for value, value_decimals in zip(values, _per_item(decimals, len(values)))
+    ]
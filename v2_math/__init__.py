@@ -0,0 +1,2 @@
+from .v2_math import *
+from .fee_on_transfer import *
\ No newline at end of file
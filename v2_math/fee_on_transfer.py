@@ -0,0 +1,115 @@
+from fractions import Fraction
+from typing import Iterable, Tuple
+from ..constants import BPS_DENOMINATOR
+
+__all__ = [
+    "apply_transfer_tax",
+    "get_amount_out_with_tax",
+    "get_amount_in_with_tax",
+    "effective_tax_bps",
+    "estimate_tax_bps",
+]
+
+
+def apply_transfer_tax(amount: int, tax_bps: int) -> int:
+    """
+    Returns the amount received from a transfer of `amount` by a token that takes
+    `tax_bps` basis points (rounded down) on every transfer
+    """
+    assert 0 <= tax_bps <= BPS_DENOMINATOR, f"Tax of {tax_bps} bps is out of range"
+    return amount - amount * tax_bps // BPS_DENOMINATOR
+
+
+def _amount_before_tax(amount_received: int, tax_bps: int) -> int:
+    """
+    Returns the smallest transfer that delivers at least `amount_received`
+    """
+    assert tax_bps < BPS_DENOMINATOR, "A 100% tax cannot deliver any amount"
+    amount = -(-amount_received * BPS_DENOMINATOR // (BPS_DENOMINATOR - tax_bps))
+    # rounding of the tax can leave the estimate one unit short or long
+    while amount > 0 and apply_transfer_tax(amount - 1, tax_bps) >= amount_received:
+        amount -= 1
+    while apply_transfer_tax(amount, tax_bps) < amount_received:
+        amount += 1
+    return amount
+
+
+def get_amount_out_with_tax(
+    amount_in: int,
+    reserves: Tuple[int, int],
+    fee: Fraction = Fraction(3, 1000),
+    buy_tax_bps: int = 0,
+    sell_tax_bps: int = 0,
+    zero_for_one: bool = True,
+) -> int:
+    """
+    Quotes an exact-input V2 swap involving fee-on-transfer tokens, as executed by the
+    router's SupportingFeeOnTransferTokens functions. `sell_tax_bps` is taken from the
+    input when it is transferred to the pair and `buy_tax_bps` from the output on its
+    way to the recipient. Returns the amount received by the recipient
+    """
+    reserve0, reserve1 = reserves
+    reserve_in, reserve_out = (
+        (reserve0, reserve1) if zero_for_one else (reserve1, reserve0)
+    )
+    amount_in = apply_transfer_tax(amount_in, sell_tax_bps)
+    amount_in_with_fee = amount_in * (fee.denominator - fee.numerator)
+    amount_out = (
+        amount_in_with_fee
+        * reserve_out
+        // (reserve_in * fee.denominator + amount_in_with_fee)
+    )
+    return apply_transfer_tax(amount_out, buy_tax_bps)
+
+
+def get_amount_in_with_tax(
+    amount_out: int,
+    reserves: Tuple[int, int],
+    fee: Fraction = Fraction(3, 1000),
+    buy_tax_bps: int = 0,
+    sell_tax_bps: int = 0,
+    zero_for_one: bool = True,
+) -> int:
+    """
+    Returns the input to send so that the recipient receives at least `amount_out`
+    after both transfer taxes, with the same conventions as get_amount_out_with_tax
+    """
+    reserve0, reserve1 = reserves
+    reserve_in, reserve_out = (
+        (reserve0, reserve1) if zero_for_one else (reserve1, reserve0)
+    )
+    pool_amount_out = _amount_before_tax(amount_out, buy_tax_bps)
+    assert pool_amount_out < reserve_out, "Insufficient liquidity for the output"
+    pool_amount_in = (
+        reserve_in
+        * pool_amount_out
+        * fee.denominator
+        // ((reserve_out - pool_amount_out) * (fee.denominator - fee.numerator))
+        + 1
+    )
+    return _amount_before_tax(pool_amount_in, sell_tax_bps)
+
+
+def effective_tax_bps(amount_sent: int, amount_received: int) -> Fraction:
+    """
+    Backs out the transfer tax, in basis points, from a matching pair of observed
+    amounts: for a sell, the amount transferred by the seller and the input recorded
+    by the pair's Swap event, for a buy, the Swap output and the amount in the
+    Transfer to the buyer
+    """
+    assert amount_sent > 0, "Cannot infer a tax from an empty transfer"
+    return Fraction((amount_sent - amount_received) * BPS_DENOMINATOR, amount_sent)
+
+
+def estimate_tax_bps(observations: Iterable[Tuple[int, int]]) -> Fraction:
+    """
+    Estimates a token's transfer tax from many (amount_sent, amount_received) pairs,
+    weighting each by its size so that dust transfers, where rounding dominates, have
+    little influence
+    """
+    total_sent = total_taxed = 0
+    for amount_sent, amount_received in observations:
+        total_sent += amount_sent
+        total_taxed += amount_sent - amount_received
+    assert total_sent > 0, "No transfers to estimate a tax from"
+    return Fraction(total_taxed * BPS_DENOMINATOR, total_sent)
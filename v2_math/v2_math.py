@@ -0,0 +1,65 @@
+from fractions import Fraction
+from typing import Union
+import numpy
+
+__all__ = ["get_amounts_out_bulk"]
+
+
+def _reserve_columns(reserves: numpy.ndarray):
+    if reserves.dtype.names is not None:
+        return reserves["reserve0"], reserves["reserve1"]
+    assert (
+        reserves.ndim == 2 and reserves.shape[1] == 2
+    ), f"Expected an (n, 2) array of reserves, found shape {reserves.shape}"
+    return reserves[:, 0], reserves[:, 1]
+
+
+def get_amounts_out_bulk(
+    reserves: numpy.ndarray,
+    amounts_in: Union[int, float, numpy.ndarray],
+    fee: Fraction = Fraction(3, 1000),
+    zero_for_one: bool = True,
+) -> numpy.ndarray:
+    """
+    Quotes exact-input swaps against many V2 pools at once, with the pair contract's
+    getAmountOut formula. `reserves` is a structured array with "reserve0" and
+    "reserve1" fields or an (n, 2) array, and `amounts_in` is a scalar or an array
+    with one input per pool.
+
+    If any input is a float array, the quote is computed in vectorized float64
+    arithmetic and a float64 array is returned, which is the fast path for research
+    over thousands of pools. Otherwise the exact integer result for each pool is
+    returned in an object array of Python ints, since uint112 reserves do not fit
+    numpy's fixed-width integer types. Pools without reserves quote zero
+    """
+    reserves = numpy.asarray(reserves)
+    reserve0, reserve1 = _reserve_columns(reserves)
+    reserves_in, reserves_out = (
+        (reserve0, reserve1) if zero_for_one else (reserve1, reserve0)
+    )
+    amounts_in = numpy.broadcast_to(numpy.asarray(amounts_in), reserves_in.shape)
+    fee_multiplier = fee.denominator - fee.numerator
+
+    if any(
+        column.dtype.kind == "f" for column in (reserves_in, reserves_out, amounts_in)
+    ):
+        reserves_in = reserves_in.astype(numpy.float64)
+        amount_in_with_fee = amounts_in.astype(numpy.float64) * fee_multiplier
+        denominator = reserves_in * fee.denominator + amount_in_with_fee
+        with numpy.errstate(divide="ignore", invalid="ignore"):
+            amounts_out = (
+                amount_in_with_fee * reserves_out.astype(numpy.float64) / denominator
+            )
+        return numpy.where(denominator > 0, amounts_out, 0.0)
+
+    amounts_out = numpy.empty(len(reserves_in), dtype=object)
+    # tolist converts every element to a Python int, so the products cannot overflow
+    for i, (reserve_in, reserve_out, amount_in) in enumerate(
+        zip(reserves_in.tolist(), reserves_out.tolist(), amounts_in.tolist())
+    ):
+        amount_in_with_fee = amount_in * fee_multiplier
+        denominator = reserve_in * fee.denominator + amount_in_with_fee
+        amounts_out[i] = (
+            amount_in_with_fee * reserve_out // denominator if denominator else 0
+        )
+    return amounts_out
@@ -0,0 +1 @@
+from .vault_math import *
\ No newline at end of file
@@ -0,0 +1,141 @@
+from typing import Optional
+
+__all__ = [
+    "FEE_BASIS_POINTS",
+    "convert_to_shares",
+    "convert_to_assets",
+    "preview_deposit",
+    "preview_mint",
+    "preview_withdraw",
+    "preview_redeem",
+]
+
+FEE_BASIS_POINTS = 10_000
+
+
+def _mul_div(a: int, b: int, denominator: int, round_up: bool) -> int:
+    assert denominator > 0, "Division by zero"
+    return -(-a * b // denominator) if round_up else a * b // denominator
+
+
+def convert_to_shares(
+    assets: int,
+    total_assets: int,
+    total_supply: int,
+    decimals_offset: Optional[int] = 0,
+    round_up: bool = False,
+) -> int:
+    """
+    Converts an asset amount to vault shares at the vault's current exchange rate.
+
+    With an integer `decimals_offset`, the conversion matches OpenZeppelin's ERC4626
+    (v4.9+), which adds 10**decimals_offset virtual shares and one virtual asset. With
+    `decimals_offset=None`, it matches Solmate and earlier OpenZeppelin vaults, which
+    mint shares 1:1 while the supply is zero.
+
+    EIP-4626 requires convertToShares to round down, which is the default
+    """
+    assert assets >= 0, "Assets must be non-negative"
+    if decimals_offset is None:
+        if total_supply == 0:
+            return assets
+        return _mul_div(assets, total_supply, total_assets, round_up)
+    return _mul_div(
+        assets, total_supply + 10 ** decimals_offset, total_assets + 1, round_up
+    )
+
+
+def convert_to_assets(
+    shares: int,
+    total_assets: int,
+    total_supply: int,
+    decimals_offset: Optional[int] = 0,
+    round_up: bool = False,
+) -> int:
+    """
+    Converts a share amount to vault assets at the vault's current exchange rate. See
+    convert_to_shares for the meaning of `decimals_offset`.
+
+    EIP-4626 requires convertToAssets to round down, which is the default
+    """
+    assert shares >= 0, "Shares must be non-negative"
+    if decimals_offset is None:
+        if total_supply == 0:
+            return shares
+        return _mul_div(shares, total_assets, total_supply, round_up)
+    return _mul_div(
+        shares, total_assets + 1, total_supply + 10 ** decimals_offset, round_up
+    )
+
+
+def _fee_on_raw(amount: int, fee_bps: int) -> int:
+    # fee charged on top of `amount`
+    return _mul_div(amount, fee_bps, FEE_BASIS_POINTS, round_up=True)
+
+
+def _fee_on_total(amount: int, fee_bps: int) -> int:
+    # fee part of an `amount` that already includes it
+    return _mul_div(amount, fee_bps, fee_bps + FEE_BASIS_POINTS, round_up=True)
+
+
+def preview_deposit(
+    assets: int,
+    total_assets: int,
+    total_supply: int,
+    decimals_offset: Optional[int] = 0,
+    entry_fee_bps: int = 0,
+) -> int:
+    """
+    Returns the shares minted for depositing `assets`, rounded down. The entry fee
+    (in basis points) is taken out of the deposited assets, as in OpenZeppelin's
+    ERC4626Fees
+    """
+    assets -= _fee_on_total(assets, entry_fee_bps)
+    return convert_to_shares(assets, total_assets, total_supply, decimals_offset)
+
+
+def preview_mint(
+    shares: int,
+    total_assets: int,
+    total_supply: int,
+    decimals_offset: Optional[int] = 0,
+    entry_fee_bps: int = 0,
+) -> int:
+    """
+    Returns the assets required to mint `shares`, rounded up, including the entry fee
+    """
+    assets = convert_to_assets(
+        shares, total_assets, total_supply, decimals_offset, round_up=True
+    )
+    return assets + _fee_on_raw(assets, entry_fee_bps)
+
+
+def preview_withdraw(
+    assets: int,
+    total_assets: int,
+    total_supply: int,
+    decimals_offset: Optional[int] = 0,
+    exit_fee_bps: int = 0,
+) -> int:
+    """
+    Returns the shares burned to withdraw `assets`, rounded up, including the exit fee
+    """
+    assets += _fee_on_raw(assets, exit_fee_bps)
+    return convert_to_shares(
+        assets, total_assets, total_supply, decimals_offset, round_up=True
+    )
+
+
+def preview_redeem(
+    shares: int,
+    total_assets: int,
+    total_supply: int,
+    decimals_offset: Optional[int] = 0,
+    exit_fee_bps: int = 0,
+) -> int:
+    """
+    Returns the assets received for redeeming `shares`, rounded down, after the exit
+    fee
+    """
+    assets = convert_to_assets(shares, total_assets, total_supply, decimals_offset)
+    return assets - _fee_on_total(assets, exit_fee_bps)
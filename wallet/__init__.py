@@ -0,0 +1 @@
+from .wallet import *
\ No newline at end of file
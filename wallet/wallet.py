@@ -0,0 +1,196 @@
+import threading
+from dataclasses import dataclass, field
+from typing import Callable, Dict, List, Optional, Set
+
+__all__ = ["NonceReservation", "NonceManager"]
+
+
+@dataclass
+class _AccountNonces:
+    # the next nonce the chain will accept, i.e. the confirmed transaction count
+    confirmed: int
+    # one above the highest nonce handed out
+    next: int
+    reserved: Set[int] = field(default_factory=set)
+    in_flight: Set[int] = field(default_factory=set)
+
+    def gaps(self) -> List[int]:
+        return [
+            nonce
+            for nonce in range(self.confirmed, self.next)
+            if nonce not in self.reserved and nonce not in self.in_flight
+        ]
+
+
+class NonceReservation:
+    """
+    A nonce held for a transaction being built. Call sent() once the transaction has
+    been broadcast, otherwise the nonce returns to the manager when the reservation is
+    released or exits its `with` block. An abandoned reservation keeps its nonce
+    """
+
+    def __init__(self, manager: "NonceManager", address: str, nonce: int) -> None:
+        self.manager = manager
+        self.address = address
+        self.nonce = nonce
+        self._done = False
+
+    def __repr__(self) -> str:
+        return f"NonceReservation({self.address}, {self.nonce})"
+
+    def __enter__(self) -> "NonceReservation":
+        return self
+
+    def __exit__(self, *args: object) -> None:
+        self.release()
+
+    def sent(self) -> None:
+        if not self._done:
+            self._done = True
+            self.manager._mark_sent(self.address, self.nonce)
+
+    def release(self) -> None:
+        if not self._done:
+            self._done = True
+            self.manager._release(self.address, self.nonce)
+
+
+class NonceManager:
+    """
+    Allocates nonces per address, tracking the confirmed count, reservations and
+    transactions in flight, so that concurrent senders never reuse a nonce. Released
+    nonces below the highest allocated one are gaps that would stall every later
+    transaction, so they are handed out again first.
+
+    `get_transaction_count` (e.g. HttpProvider.get_transaction_count) is called with
+    an address the first time it is seen. Without it, sync must be called first. All
+    methods are thread-safe
+    """
+
+    def __init__(
+        self, get_transaction_count: Optional[Callable[[str], int]] = None
+    ) -> None:
+        self._get_transaction_count = get_transaction_count
+        self._lock = threading.Lock()
+        self._accounts: Dict[str, _AccountNonces] = {}
+
+    def _account(self, address: str) -> _AccountNonces:
+        # called with the lock held
+        key = address.lower()
+        account = self._accounts.get(key)
+        if account is None:
+            assert (
+                self._get_transaction_count is not None
+            ), f"No nonce known for {address}, call sync first"
+            count = self._get_transaction_count(address)
+            account = self._accounts[key] = _AccountNonces(count, count)
+        return account
+
+    def sync(self, address: str, transaction_count: int) -> None:
+        """
+        Sets the confirmed transaction count of an address, e.g. from
+        eth_getTransactionCount at the latest block. Transactions in flight below it
+        are considered mined
+        """
+        with self._lock:
+            key = address.lower()
+            account = self._accounts.get(key)
+            if account is None:
+                self._accounts[key] = _AccountNonces(
+                    transaction_count, transaction_count
+                )
+            else:
+                self._set_confirmed(account, transaction_count)
+
+    @staticmethod
+    def _set_confirmed(account: _AccountNonces, transaction_count: int) -> None:
+        account.confirmed = transaction_count
+        account.next = max(account.next, transaction_count)
+        account.in_flight = {
+            nonce for nonce in account.in_flight if nonce >= transaction_count
+        }
+        account.reserved = {
+            nonce for nonce in account.reserved if nonce >= transaction_count
+        }
+
+    def confirm(self, address: str, nonce: int) -> None:
+        """
+        Records that the transaction with `nonce` was mined
+        """
+        with self._lock:
+            account = self._account(address)
+            if nonce >= account.confirmed:
+                self._set_confirmed(account, nonce + 1)
+
+    def reserve(self, address: str) -> NonceReservation:
+        """
+        Reserves the lowest free nonce of an address
+        """
+        with self._lock:
+            account = self._account(address)
+            gaps = account.gaps()
+            if gaps:
+                nonce = gaps[0]
+            else:
+                nonce = account.next
+                account.next += 1
+            account.reserved.add(nonce)
+        return NonceReservation(self, address, nonce)
+
+    def _mark_sent(self, address: str, nonce: int) -> None:
+        with self._lock:
+            account = self._account(address)
+            account.reserved.discard(nonce)
+            if nonce >= account.confirmed:
+                account.in_flight.add(nonce)
+
+    def _release(self, address: str, nonce: int) -> None:
+        """
+        Returns a nonce, e.g. of a transaction that failed to broadcast or was dropped
+        from the mempool
+        """
+        with self._lock:
+            account = self._accounts.get(address.lower())
+            if account is None:
+                return
+            account.reserved.discard(nonce)
+            account.in_flight.discard(nonce)
+            # shrink the window if the highest nonces are all free again
+            while (
+                account.next > account.confirmed
+                and account.next - 1 not in account.reserved
+                and account.next - 1 not in account.in_flight
+            ):
+                account.next -= 1
+
+    def drop(self, address: str, nonce: int) -> None:
+        """
+        Frees the nonce of a transaction in flight that will not be mined (dropped or
+        replaced elsewhere), so that it can be reused
+        """
+        self._release(address, nonce)
+
+    def confirmed_nonce(self, address: str) -> int:
+        with self._lock:
+            return self._account(address).confirmed
+
+    def in_flight(self, address: str) -> List[int]:
+        with self._lock:
+            return sorted(self._account(address).in_flight)
+
+    def gaps(self, address: str) -> List[int]:
+        """
+        Returns the free nonces below the highest allocated one, which block every
+        transaction in flight above them
+        """
+        with self._lock:
+            return self._account(address).gaps()
+
+    def next_nonce(self, address: str) -> int:
+        """
+        Returns the nonce the next reservation would get, without reserving it
+        """
+        with self._lock:
+            account = self._account(address)
+            gaps = account.gaps()
+            return gaps[0] if gaps else account.next